@@ -11,6 +11,7 @@ use std::{
 
 use crate::{
     abs::{ranked::Ranked, Abstract},
+    float::Real,
     Polytope,
 };
 
@@ -140,7 +141,7 @@ impl Orientation {
 
     /// Returns the "sign" associated with a flag, which is either `1.0` or
     /// `-1.0`.
-    pub fn sign(&self) -> f64 {
+    pub fn sign(&self) -> Real {
         match self {
             Self::Even => 1.0,
             Self::Odd => -1.0,
@@ -258,6 +258,12 @@ impl Abstract {
         Some(ElementHash::new(self, rank, idx)?.to_vertices())
     }
 
+    /// Gets the indices of the edges of an element in the polytope, if it
+    /// exists. Vertices and the minimal element have no edges.
+    pub fn element_edges(&self, rank: usize, idx: usize) -> Option<Vec<usize>> {
+        Some(ElementHash::new(self, rank, idx)?.to_edges())
+    }
+
     /// Gets both elements with a given rank and index as a polytope and the
     /// indices of its vertices on the original polytope, if it exists.
     pub fn element_and_vertices(&self, rank: usize, idx: usize) -> Option<(Vec<usize>, Self)> {
@@ -1033,7 +1039,7 @@ impl IndexMut<(usize, usize)> for Abstract {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test;
+    use crate::{choose, orthoplex_counts, test};
 
     /// Checks a nullitope.
     #[test]
@@ -1041,6 +1047,37 @@ mod tests {
         test(&Abstract::nullitope(), [1]);
     }
 
+    /// Checks the vertices and edges of the elements of a cube.
+    #[test]
+    fn element_vertices() {
+        let cube = Abstract::cube();
+
+        for idx in 0..6 {
+            assert_eq!(cube.element_vertices(3, idx).unwrap().len(), 4);
+            assert_eq!(cube.element_edges(3, idx).unwrap().len(), 4);
+        }
+
+        assert_eq!(cube.element_vertices(4, 0).unwrap().len(), 8);
+        assert_eq!(cube.element_edges(4, 0).unwrap().len(), 12);
+        assert_eq!(cube.element_edges(2, 5).unwrap(), vec![5]);
+        assert!(cube.element_edges(1, 0).unwrap().is_empty());
+        assert!(cube.element_edges(3, 6).is_none());
+    }
+
+    /// Checks the f-vectors of some polytopes.
+    #[test]
+    fn f_vector() {
+        assert!(Abstract::nullitope().f_vector().is_empty());
+        assert_eq!(Abstract::point().f_vector(), vec![1]);
+        assert_eq!(Abstract::polygon(5).f_vector(), vec![5, 5, 1]);
+        assert_eq!(Abstract::cube().f_vector(), vec![8, 12, 6, 1]);
+
+        let tesseract = Abstract::hypercube(5);
+        let f_vector = tesseract.f_vector();
+        assert_eq!(f_vector, vec![16, 32, 24, 8, 1]);
+        assert_eq!(f_vector, tesseract.el_count_iter().skip(1).collect::<Vec<_>>());
+    }
+
     /// Checks a point.
     #[test]
     fn point() {
@@ -1079,18 +1116,6 @@ mod tests {
         test(&Abstract::octahedron(), [1, 6, 12, 8, 1])
     }
 
-    /// Returns the values C(*n*, 0), ..., C(*n*, *n*).
-    fn choose(n: usize) -> Vec<usize> {
-        let mut res = Vec::with_capacity(n + 1);
-        res.push(1);
-
-        for k in 0..n {
-            res.push(res[k] * (n - k) / (k + 1));
-        }
-
-        res
-    }
-
     /// Checks simplices.
     #[test]
     fn simplex() {
@@ -1102,15 +1127,6 @@ mod tests {
         }
     }
 
-    /// Returns an iterator over the element counts of an n-hypercube.
-    fn orthoplex_counts(n: usize) -> impl DoubleEndedIterator<Item = usize> {
-        choose(n - 1)
-            .into_iter()
-            .enumerate()
-            .map(|(k, c)| c << k)
-            .chain(std::iter::once(1))
-    }
-
     /// Checks hypercubes.
     #[test]
     fn hypercube() {
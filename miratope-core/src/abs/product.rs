@@ -321,4 +321,29 @@ mod tests {
     fn duocomb() {
         test_duoproduct(Abstract::duocomb, |m, n| [1, m * n, 2 * m * n, m * n, 1])
     }
+
+    /// Checks that the element counts of a duoprism are the convolution of
+    /// those of its factors, and that its lattice is valid.
+    #[test]
+    fn duoprism_convolution() {
+        let factors = [
+            Abstract::point(),
+            Abstract::dyad(),
+            Abstract::polygon(5),
+            Abstract::simplex(4),
+            Abstract::cube(),
+            Abstract::orthoplex(5),
+        ];
+
+        for p in &factors {
+            for q in &factors {
+                test(&p.duoprism(q), crate::duoprism_counts(p, q));
+            }
+        }
+
+        test(
+            &Abstract::polygon(4).duoprism(&Abstract::polygon(4)),
+            Abstract::hypercube(5).el_count_iter(),
+        );
+    }
 }
@@ -329,6 +329,13 @@ pub trait Ranked:
         self.ranks().is_valid().unwrap();
     }
 
+    /// Returns whether `self` satisfies every axiom of an abstract polytope,
+    /// including strong connectivity, so that compounds aren't valid. To find
+    /// out which axiom fails, use [`Ranks::is_polytope`].
+    fn is_valid_polytope(&self) -> bool {
+        self.ranks().is_polytope().is_ok()
+    }
+
     /// Returns the rank of the structure, i.e. the length of the `Ranks` minus
     /// one.
     ///
@@ -344,11 +351,41 @@ pub trait Ranked:
         self.ranks().get(rank).map(ElementList::len).unwrap_or(0)
     }
 
-    /// Returns an iterator over the element counts of the structure.
+    /// Returns an iterator over the element counts of the structure. Unlike
+    /// [`Self::f_vector`], this starts with the count of the minimal element.
     fn el_count_iter(&self) -> iter::Map<slice::Iter<'_, ElementList>, LenFn> {
         self.ranks().iter().map(ElementList::len as LenFn)
     }
 
+    /// Returns the [f-vector](https://en.wikipedia.org/wiki/Face_(geometry)#f-vector)
+    /// of the structure: the element counts from the vertices up to the
+    /// maximal element. The f-vector of a cube is `[8, 12, 6, 1]`, and that of
+    /// the nullitope is empty.
+    fn f_vector(&self) -> Vec<usize> {
+        self.el_count_iter().skip(1).collect()
+    }
+
+    /// Returns the [Euler characteristic](https://en.wikipedia.org/wiki/Euler_characteristic)
+    /// of the structure, the alternating sum of the counts of its proper
+    /// elements, starting with the vertices. This is 2 for every polyhedron
+    /// homeomorphic to a sphere, and 0 for every polychoron.
+    fn euler_characteristic(&self) -> i64 {
+        let counts = self.f_vector();
+        let proper = counts.len().saturating_sub(1);
+
+        counts[..proper]
+            .iter()
+            .enumerate()
+            .map(|(r, &count)| {
+                if r % 2 == 0 {
+                    count as i64
+                } else {
+                    -(count as i64)
+                }
+            })
+            .sum()
+    }
+
     /// Returns a reference to an element of the polytope. To actually get the
     /// entire polytope it defines, use [`Polytope::element`](crate::Polytope::element).
     fn get_element(&self, rank: usize, idx: usize) -> Option<&Element> {
@@ -702,6 +739,11 @@ impl ElementHash {
         self.to_elements(1)
     }
 
+    /// Gets the indices of the edges in the original polytope.
+    pub fn to_edges(&self) -> Vec<usize> {
+        self.to_elements(2)
+    }
+
     /// Gets the indices of the vertices of a given element in a polytope.
     pub fn to_polytope(&self, poly: &Abstract) -> Abstract {
         // TODO: use an AbstractBuilder instead, probably.
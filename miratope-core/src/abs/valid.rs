@@ -1,9 +1,7 @@
 //! Contains the code that verifies whether a set of [`Ranks`] correspond to a
 //! valid [`Abstract`](crate::Abstract) polytope.
 
-// TODO: finish these!
-
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use strum_macros::Display;
 use vec_like::VecLike;
@@ -161,7 +159,6 @@ impl Ranks {
         self.is_dyadic()?;
 
         Ok(())
-        // && self.is_strongly_connected()
     }
 
     /// Determines whether the polytope is bounded, i.e. whether it has a single
@@ -274,7 +271,7 @@ impl Ranks {
 
         // For every element, by looking through the subelements of its
         // subelements, we need to find each exactly twice.
-        for r in 2..self.rank() {
+        for r in 2..=self.rank() {
             for (idx, el) in self[r].iter().enumerate() {
                 let mut hash_sub_subs = HashMap::new();
 
@@ -316,18 +313,285 @@ impl Ranks {
         Ok(())
     }
 
-    /// Determines whether the polytope is connected. A valid non-compound
-    /// polytope should always return `true`.
-    pub fn is_connected(&self, _section: Section) -> bool {
-        todo!()
-        /*
-        let section = self.get_section(section).unwrap();
-        section.flags().count() == section.oriented_flags().count() */
+    /// Checks whether the ranks form a valid polytope that isn't a compound,
+    /// i.e. whether they satisfy all of the axioms of an abstract polytope.
+    /// Unlike [`Self::is_valid`], this also checks strong connectivity.
+    pub fn is_polytope(&self) -> AbstractResult<()> {
+        self.is_valid()?;
+        self.is_strongly_connected()
+    }
+
+    /// Determines whether a section of the polytope is connected, i.e. whether
+    /// any two elements strictly between its lowest and highest elements can
+    /// be joined by a chain of such elements, each incident to the next.
+    /// Sections of rank at most 1 are always connected.
+    pub fn is_connected(&self, section: Section) -> bool {
+        let (lo_rank, lo_idx) = section.lo();
+        let (hi_rank, hi_idx) = section.hi();
+        if hi_rank < lo_rank + 3 {
+            return true;
+        }
+
+        // The elements below the highest element, down to the lowest rank.
+        let mut below = HashSet::new();
+        let mut layer = vec![hi_idx];
+        for r in (lo_rank + 1..hi_rank).rev() {
+            let mut next = Vec::new();
+            for idx in layer {
+                for &sub in &self[(r + 1, idx)].subs {
+                    if below.insert((r, sub)) {
+                        next.push(sub);
+                    }
+                }
+            }
+            layer = next;
+        }
+
+        // The elements strictly between both elements of the section.
+        let mut inside = HashSet::new();
+        let mut layer = vec![lo_idx];
+        for r in lo_rank + 1..hi_rank {
+            let mut next = Vec::new();
+            for idx in layer {
+                for &sup in &self[(r - 1, idx)].sups {
+                    if below.contains(&(r, sup)) && inside.insert((r, sup)) {
+                        next.push(sup);
+                    }
+                }
+            }
+            layer = next;
+        }
+
+        // Walks through the inside of the section from any element.
+        let start = match inside.iter().next() {
+            Some(&el) => el,
+            None => return false,
+        };
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        let mut stack = vec![start];
+        while let Some((r, idx)) = stack.pop() {
+            let el = &self[(r, idx)];
+            let subs = el.subs.iter().map(|&sub| (r - 1, sub));
+            let sups = el.sups.iter().map(|&sup| (r + 1, sup));
+
+            for other in subs.chain(sups) {
+                if inside.contains(&other) && visited.insert(other) {
+                    stack.push(other);
+                }
+            }
+        }
+
+        visited.len() == inside.len()
+    }
+
+    /// Determines whether the polytope is strongly connected, i.e. whether all
+    /// of its sections are connected. A valid non-compound polytope should
+    /// always return `Ok(())`.
+    pub fn is_strongly_connected(&self) -> AbstractResult<()> {
+        let rank = self.rank();
+
+        for lo_rank in 0..=rank {
+            for lo_idx in 0..self[lo_rank].len() {
+                // Goes through every element above the lowest one.
+                let mut layer = vec![lo_idx];
+                for hi_rank in lo_rank + 1..=rank {
+                    let mut next = HashSet::new();
+                    for idx in layer {
+                        next.extend(self[(hi_rank - 1, idx)].sups.iter().copied());
+                    }
+                    layer = next.into_iter().collect();
+
+                    for &hi_idx in &layer {
+                        let section = Section::new(lo_rank, lo_idx, hi_rank, hi_idx);
+                        if !self.is_connected(section) {
+                            return Err(AbstractError::Connected(section));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AbstractError, IncidenceType};
+    use crate::{
+        abs::{Abstract, AbstractBuilder, Ranked, Section},
+        conc::{Concrete, ConcretePolytope},
+        Polytope,
+    };
+
+    use nalgebra::dvector;
+    use vec_like::VecLike;
+
+    /// Checks that some polytopes satisfy all of the axioms.
+    #[test]
+    fn valid() {
+        for poly in [
+            Abstract::point(),
+            Abstract::polygon(5),
+            Abstract::cube(),
+            Abstract::simplex(5),
+            Abstract::orthoplex(5),
+            Abstract::polygon(3).duoprism(&Abstract::polygon(4)),
+            Abstract::cube().antiprism(),
+        ] {
+            assert!(poly.is_valid_polytope());
+        }
+    }
+
+    /// Checks that compounds are valid, but not strongly connected.
+    #[test]
+    fn compound() {
+        let compound =
+            Abstract::compound(vec![Abstract::polygon(3), Abstract::polygon(4)].into_iter());
+        assert!(compound.ranks().is_valid().is_ok());
+        assert!(!compound.is_valid_polytope());
+
+        match compound.ranks().is_polytope() {
+            Err(AbstractError::Connected(section)) => {
+                assert_eq!(section, Section::new(0, 0, 3, 0))
+            }
+            res => panic!("expected a connectivity error, got {:?}", res),
+        }
+    }
+
+    /// Checks two cubes sharing a single vertex. The polytope is connected,
+    /// but the section above the shared vertex isn't.
+    #[test]
+    fn shared_vertex() {
+        let cube = Concrete::cube();
+        let mut other = cube.clone();
+        other.translate(&dvector![1.0, 1.0, 1.0]);
+
+        let poly = Concrete::compound_dedup([cube, other]);
+        assert_eq!(poly.euler_characteristic(), 3);
+
+        match poly.abs.ranks().is_polytope() {
+            Err(AbstractError::Connected(section)) => {
+                assert_eq!(section.lo_rank, 1);
+                assert_eq!(section.hi(), (4, 0));
+            }
+            res => panic!("expected a connectivity error, got {:?}", res),
+        }
+    }
+
+    /// Checks that a vertex in three edges breaks the diamond property.
+    #[test]
+    fn dyadic() {
+        let mut builder = AbstractBuilder::new();
+        builder.push_min();
+        builder.push_vertices(4);
+        builder.push(
+            vec![vec![0, 1], vec![1, 2], vec![0, 2], vec![0, 3]]
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        );
+        builder.push_max();
+
+        match builder.ranks().is_valid() {
+            Err(AbstractError::Dyadic { section, more }) => {
+                assert_eq!(section, Section::new(1, 0, 3, 0));
+                assert!(more);
+            }
+            res => panic!("expected a dyadicity error, got {:?}", res),
+        }
+    }
+
+    /// Checks that a polytope with two maximal elements is unbounded.
+    #[test]
+    fn bounded() {
+        let mut builder = AbstractBuilder::new();
+        builder.push_min();
+        builder.push_vertices(2);
+
+        match builder.ranks().is_valid() {
+            Err(AbstractError::Bounded {
+                min_count,
+                max_count,
+            }) => {
+                assert_eq!(min_count, 1);
+                assert_eq!(max_count, 2);
+            }
+            res => panic!("expected a boundedness error, got {:?}", res),
+        }
+    }
+
+    /// Checks that an edge with a non-existent vertex is found.
+    #[test]
+    fn index() {
+        let mut cube = Concrete::cube();
+        cube[(2, 3)].subs.push(8);
+
+        match cube.is_valid() {
+            Err(AbstractError::Index {
+                el,
+                incidence_type: IncidenceType::Subelement,
+                index,
+            }) => {
+                assert_eq!(el, (2, 3));
+                assert_eq!(index, 8);
+            }
+            res => panic!("expected an index error, got {:?}", res),
+        }
+    }
+
+    /// Checks that an edge listed under a vertex it doesn't belong to is found.
+    #[test]
+    fn consistency() {
+        let mut cube = Concrete::cube();
+        let edge = cube[(1, 0)].sups.pop().unwrap();
+
+        match cube.is_valid() {
+            Err(AbstractError::Consistency {
+                el,
+                incidence_type: IncidenceType::Subelement,
+                index,
+            }) => {
+                assert_eq!(el, (2, edge));
+                assert_eq!(index, 0);
+            }
+            res => panic!("expected a consistency error, got {:?}", res),
+        }
+    }
+
+    /// Checks that a vertex in no edges is found.
+    #[test]
+    fn ranked() {
+        let mut builder = AbstractBuilder::new();
+        builder.push_min();
+        builder.push_vertices(4);
+        builder.push(
+            vec![vec![0, 1], vec![1, 2], vec![0, 2]]
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        );
+        builder.push_max();
+
+        match builder.ranks().is_valid() {
+            Err(AbstractError::Ranked {
+                el,
+                incidence_type: IncidenceType::Superelement,
+            }) => assert_eq!(el, (1, 3)),
+            res => panic!("expected a rankedness error, got {:?}", res),
+        }
     }
 
-    /// Determines whether the polytope is strongly connected. A valid
-    /// non-compound polytope should always return `true`.
-    pub fn is_strongly_connected(&self) -> bool {
-        todo!()
+    /// Checks the Euler characteristics of some polytopes.
+    #[test]
+    fn euler_characteristic() {
+        assert_eq!(Abstract::point().euler_characteristic(), 0);
+        assert_eq!(Abstract::dyad().euler_characteristic(), 2);
+        assert_eq!(Abstract::polygon(7).euler_characteristic(), 0);
+        assert_eq!(Abstract::cube().euler_characteristic(), 2);
+        assert_eq!(Abstract::orthoplex(4).euler_characteristic(), 2);
+        assert_eq!(Abstract::hypercube(5).euler_characteristic(), 0);
+        assert_eq!(Abstract::simplex(6).euler_characteristic(), 2);
     }
 }
@@ -1,192 +1,410 @@
-use std::collections::BTreeSet;
-use std::f64::NEG_INFINITY;
-
-use super::Concrete;
-use petgraph::{graph::NodeIndex, Directed, Direction, Graph};
-
-/// An entry in the priority queue used in Shell.
-enum QueueEntry<'a> {
-    /// Represents the event where at a certain time, the first facet that
-    /// contains a certain point becomes visible. This facet will have the
-    /// specified vertices and normal vector.
-    Point {
-        time: f64,
-        normal: Vector,
-        point: Point,
-        vertices: Vec<Point>,
-    },
-
-    /// Represents the event where at a certain time, a facet containing a
-    /// horizon peak and the horizon ridges specified by an element's neighbors
-    /// becomes visible. This facet will have the specified normal vector.
-    Peak {
-        time: f64,
-        normal: Vector,
-        element: ShellElement<'a>,
-    },
+//! Contains the code to compute the convex hull of a set of points in any
+//! number of dimensions.
+//!
+//! The facets of every face are found via
+//! [gift wrapping](https://en.wikipedia.org/wiki/Gift_wrapping_algorithm),
+//! and the full face lattice is built by recursing into them. Faces are
+//! identified by the sorted indices of the points they contain, which makes
+//! coplanar facets merge automatically.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    abs::{AbstractBuilder, Ranked, SubelementList},
+    conc::{Concrete, ConcretePolytope},
+    float::{Float, Real},
+    geometry::{Matrix, Point, Subspace, Vector},
+    group::Group,
+    Polytope,
+};
+
+use vec_like::*;
+
+/// Stores the points whose hull we're computing, together with the facets of
+/// every face found so far.
+struct Hull<'a> {
+    /// The points, without duplicates.
+    points: &'a [Point<Real>],
+
+    /// Maps the points on each face to the points on each of its facets.
+    facets: HashMap<Vec<usize>, Vec<Vec<usize>>>,
 }
 
-impl<'a> QueueEntry<'a> {
-    /// Returns the time associated with an event.
-    pub fn time(&self) -> f64 {
-        match self {
-            QueueEntry::Point { time: t, .. } => *t,
-            QueueEntry::Peak { time: t, .. } => *t,
-        }
-    }
+/// Rotates a unit vector `n` by an angle `theta` towards a second
+/// perpendicular unit vector `u`.
+fn rotate(n: &Vector<Real>, u: &Vector<Real>, theta: Real) -> Vector<Real> {
+    let (sin, cos) = theta.sin_cos();
+    n * cos + u * sin
 }
 
-impl<'a> PartialEq for QueueEntry<'a> {
-    fn eq(&self, other: &Self) -> bool {
-        self.time() == other.time()
+impl<'a> Hull<'a> {
+    /// Initializes a new hull from a list of distinct points.
+    fn new(points: &'a [Point<Real>]) -> Self {
+        Self {
+            points,
+            facets: HashMap::new(),
+        }
     }
-}
 
-impl<'a> Eq for QueueEntry<'a> {}
+    /// Returns the facets of the hull of the points with the given indices,
+    /// each of them given as a sorted list of point indices.
+    fn facets(&mut self, face: &[usize]) -> Vec<Vec<usize>> {
+        if let Some(facets) = self.facets.get(face) {
+            return facets.clone();
+        }
 
-impl<'a> PartialOrd for QueueEntry<'a> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.time().partial_cmp(&other.time())
-    }
-}
+        let subspace = Subspace::from_points(face.iter().map(|&i| &self.points[i]));
+        let local: Vec<_> = face
+            .iter()
+            .map(|&i| subspace.flatten(&self.points[i]))
+            .collect();
 
-impl<'a> Ord for QueueEntry<'a> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        let facets = match subspace.rank() {
+            // Points have no proper faces other than the nullitope.
+            0 => Vec::new(),
+
+            // The facets of a segment are its two endpoints.
+            1 => {
+                let (mut min, mut max) = (0, 0);
+                for (i, p) in local.iter().enumerate() {
+                    if p[0] < local[min][0] {
+                        min = i;
+                    }
+                    if p[0] > local[max][0] {
+                        max = i;
+                    }
+                }
+
+                vec![vec![face[min]], vec![face[max]]]
+            }
+
+            _ => self.gift_wrap(face, &local),
+        };
+
+        self.facets.insert(face.to_vec(), facets.clone());
+        facets
     }
-}
 
-/// The metadata returned after deleting an element from a [`ShellQueue`]. This
-/// data specifies the minimum time in the queue, the union of all
-/// [`Point`](ShellQueueEntry::Point) and [`Peak`](ShellQueueEntry::Peak)
-/// entries at this time, and their common normal vector.
-struct QueueData<'a> {
-    time: f64,
-    normal: Vector,
-    vertices: Vec<Point>,
-    elements: Vec<ShellElement<'a>>,
-    points: Vec<Point>,
-}
+    /// Rotates the hyperplane through `o` with outer normal `n` towards `u`
+    /// until it hits another point. Returns the new normal and the indices of
+    /// all points on the new hyperplane.
+    fn wrap(
+        local: &[Point<Real>],
+        o: &Point<Real>,
+        n: &Vector<Real>,
+        u: &Vector<Real>,
+    ) -> (Vector<Real>, Vec<usize>) {
+        let mut theta = Real::PI;
+
+        for p in local {
+            let w = p - o;
+            let (a, b) = (w.dot(n), w.dot(u));
+
+            // Points on the pivot don't constrain the rotation. Every other
+            // point lies below the hyperplane, so its angle is in [0, π], up to
+            // the sign of zero.
+            if a.abs() > Real::EPS || b.abs() > Real::EPS {
+                theta = theta.min((-a).atan2(b).abs());
+            }
+        }
 
-#[derive(Deref, DerefMut)]
-struct Queue<'a>(BTreeSet<QueueEntry<'a>>);
+        let normal = rotate(n, u, theta);
+        let face = (0..local.len())
+            .filter(|&i| (&local[i] - o).dot(&normal).abs() < Real::EPS)
+            .collect();
 
-impl<'a> Queue<'a> {
-    pub fn new() -> Self {
-        Self(BTreeSet::new())
+        (normal, face)
     }
 
-    pub fn delete_min() -> QueueData<'a> {
-        todo!()
+    /// Finds the facets of the hull of a face, given the coordinates of its
+    /// points in a full rank subspace.
+    fn gift_wrap(&mut self, face: &[usize], local: &[Point<Real>]) -> Vec<Vec<usize>> {
+        let dim = local[0].len();
+
+        // The point with the least first coordinate lies on some face.
+        let mut first = 0;
+        for (i, p) in local.iter().enumerate() {
+            if p[0] < local[first][0] {
+                first = i;
+            }
+        }
+        let o = &local[first];
+        let mut normal = Vector::from_fn(dim, |i, _| if i == 0 { -1.0 } else { 0.0 });
+        let mut points: Vec<_> = (0..local.len())
+            .filter(|&i| (&local[i] - o).dot(&normal).abs() < Real::EPS)
+            .collect();
+
+        // We rotate the hyperplane until the face it touches is a facet.
+        loop {
+            let subspace = Subspace::from_points(points.iter().map(|&i| &local[i]));
+            if subspace.rank() + 1 == dim {
+                break;
+            }
+
+            // A direction perpendicular to both the face and its normal.
+            let u = local
+                .iter()
+                .find_map(|p| {
+                    let v = p - subspace.project(p);
+                    (&v - &normal * v.dot(&normal)).try_normalize(Real::EPS)
+                })
+                .expect("the points should be full rank");
+
+            let (new_normal, new_points) = Self::wrap(local, o, &normal, &u);
+            normal = new_normal;
+            points = new_points;
+        }
+
+        // Maps every point index into its index in the face.
+        let local_idx: HashMap<_, _> = face.iter().enumerate().map(|(i, &j)| (j, i)).collect();
+        let global = |points: Vec<usize>| points.into_iter().map(|i| face[i]).collect::<Vec<_>>();
+
+        let mut found = HashSet::new();
+        let first_facet = global(points);
+        found.insert(first_facet.clone());
+        let mut queue = vec![(first_facet, normal)];
+        let mut facets = Vec::new();
+
+        // We wrap around every ridge of every facet found.
+        while let Some((facet, normal)) = queue.pop() {
+            let facet_center = facet.iter().map(|i| &local[local_idx[i]]).sum::<Point<Real>>()
+                / facet.len() as Real;
+
+            for ridge in self.facets(&facet) {
+                let o = &local[local_idx[&ridge[0]]];
+                let subspace = Subspace::from_points(ridge.iter().map(|i| &local[local_idx[i]]));
+                let u = -subspace
+                    .normal(&facet_center)
+                    .expect("a facet should be strictly larger than its ridges");
+
+                let (new_normal, new_points) = Self::wrap(local, o, &normal, &u);
+                let new_facet = global(new_points);
+
+                if found.insert(new_facet.clone()) {
+                    queue.push((new_facet, new_normal));
+                }
+            }
+
+            facets.push(facet);
+        }
+
+        facets
     }
 }
 
-/// An element produced by the Shell algorithm. Note that the data it contains
-/// is quite different from that of a [`Element`](super::Element).
-struct ShellElement<'a> {
-    normal: Vector,
-    neighbors: Vec<NodeIndex>,
-    queue: &'a Queue<'a>,
-}
+impl Concrete {
+    /// Builds the [convex hull](https://polytope.miraheze.org/wiki/Convex_hull)
+    /// of a set of points, with its full face lattice. Coplanar facets are
+    /// merged, points that aren't vertices of the hull are discarded, and
+    /// points contained in a lower-dimensional subspace give a polytope of the
+    /// corresponding rank.
+    pub fn convex_hull(points: Vec<Point<Real>>) -> Self {
+        // We remove duplicate points.
+        let mut unique: Vec<Point<Real>> = Vec::with_capacity(points.len());
+        for p in points {
+            if unique.iter().all(|q| (q - &p).norm() > Real::EPS) {
+                unique.push(p);
+            }
+        }
 
-struct ShellEdge<'a>(&'a Point);
+        if unique.is_empty() {
+            return Self::nullitope();
+        }
 
-struct Line(Vector, Vector);
+        let rank = Subspace::from_points(unique.iter()).rank() + 1;
+        let mut hull = Hull::new(&unique);
 
-struct ShellPolytope<'a> {
-    dim: usize,
-    graph: Graph<ShellElement<'a>, ShellEdge<'a>, Directed>,
-}
+        // The faces of each rank, from the top down, and their indices.
+        let mut faces = vec![(0..unique.len()).collect::<Vec<_>>()];
+        let mut ranks = Vec::with_capacity(rank);
+
+        for _ in 2..=rank {
+            let mut indices = HashMap::new();
+            let mut subfaces = Vec::new();
+            let mut subelements = SubelementList::with_capacity(faces.len());
 
-impl<'a> ShellPolytope<'a> {
-    fn new(dim: usize) -> Self {
-        todo!()
-    }
-
-    fn convex_hull(vertices: Vec<Point>) -> Concrete {
-        let s = Subspace::from_points(&vertices);
-
-        // A vector that is contained in s, but is in "general position."
-        let y = 0.57 * &s.basis[0] + 0.43 * &s.basis[1];
-        let a = s.orthogonal_comp();
-        let x: Point = vertices.iter().sum::<Point>() / vertices.len() as f64;
-
-        let vertices = vertices.iter().collect::<Vec<_>>();
-
-        let mut poly = Self::new(vertices[0].nrows());
-
-        poly.shell(
-            Line(y, x),
-            NEG_INFINITY,
-            Vec::new(),
-            Vec::new(),
-            vertices.clone(),
-            vertices,
-            a,
-        );
-
-        poly.into()
-    }
-
-    fn shell(
-        &mut self,
-        line: Line,
-        time: f64,
-        ff: Vec<NodeIndex>,
-        hr: Vec<NodeIndex>,
-        u: Vec<&'a Point>,
-        t: Vec<&'a Point>,
-        n: Vec<Vector>,
-    ) -> ShellElement<'a> {
-        // Step 1
-        let q = self.graph.add_node(ShellElement {
-            normal: vec![].into(),
-            neighbors: Vec::new(),
-            queue: &Queue::new(),
-        });
-
-        for f in ff {
-            self.graph.add_edge(f, q, weight);
-        }
-
-        // Step 2
-        if t.len() == 1 {
-            let p = t[0];
-
-            self.graph.add_edge(
-                q,
-                if let Some(&e) = ff.get(0) {
-                    e
-                } else {
-                    self.graph.add_node(ShellElement {
-                        normal: Vector::zeros(self.dim),
-                        neighbors: Vec::new(),
+            for face in &faces {
+                let subs = hull
+                    .facets(face)
+                    .into_iter()
+                    .map(|facet| {
+                        let len = indices.len();
+                        *indices.entry(facet.clone()).or_insert_with(|| {
+                            subfaces.push(facet);
+                            len
+                        })
                     })
-                },
-                ShellEdge(&p),
-            );
+                    .collect::<Vec<_>>();
+
+                subelements.push(subs.into());
+            }
+
+            ranks.push(subelements);
+            faces = subfaces;
+        }
+
+        // The faces of rank 1 are the vertices.
+        let vertices = faces.into_iter().map(|face| unique[face[0]].clone()).collect::<Vec<_>>();
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        // We skip the maximal element, which is added separately.
+        for subelements in ranks.into_iter().skip(1).rev() {
+            builder.push(subelements);
+        }
+        if rank > 1 {
+            builder.push_max();
+        }
+
+        // Safety: the face lattice of a convex polytope is a valid polytope.
+        Self::new(vertices, unsafe { builder.build() })
+    }
+
+    /// Builds the convex hull of the orbit of a point under a group. See
+    /// [`Group::orbit`].
+    pub fn from_orbit<I: Iterator<Item = Matrix<Real>>>(
+        group: Group<I>,
+        seed: &Point<Real>,
+    ) -> Self {
+        Self::convex_hull(group.orbit(seed))
+    }
+
+    /// Moves every facet of a convex polytope outwards by a given distance
+    /// along its normal, or inwards if the distance is negative, and returns
+    /// the intersection of the resulting half-spaces.
+    ///
+    /// The intersection is computed by reciprocating the facet hyperplanes
+    /// about the gravicenter, taking the convex hull, and dualizing back.
+    /// Returns `None` if the polytope isn't of full rank, or if the offset
+    /// polytope doesn't contain the gravicenter of the original, as when it
+    /// collapses.
+    pub fn offset_facets(&self, distance: Real) -> Option<Self> {
+        let rank = self.rank();
+        let center = self.gravicenter()?;
+        if rank < 2 || center.len() + 1 != rank {
+            return None;
         }
 
-        // Step 3
-        let mut hp = Vec::new();
-        for f in hr {
-            for g in self.graph.neighbors_directed(f, Direction::Outgoing) {
-                hp.push(g);
-                self.graph[g].neighbors.push(f);
+        let mut poles = Vec::with_capacity(self.facet_count());
+        for idx in 0..self.facet_count() {
+            let subspace = Subspace::from_points(
+                self.element_vertices_ref(rank - 1, idx)?.into_iter(),
+            );
+            let normal = -subspace.normal(&center)?;
+            let height = (&subspace.offset - &center).dot(&normal) + distance;
+
+            if height < Real::EPS {
+                return None;
             }
+
+            poles.push(normal / height);
         }
-        todo!()
+
+        let mut poly = Self::convex_hull(poles).try_dual().ok()?;
+        poly.recenter_with(&-center);
+        Some(poly)
     }
 }
 
-impl<'a> Into<Concrete> for ShellPolytope<'a> {
-    fn into(self) -> Concrete {
-        todo!()
+#[cfg(test)]
+mod tests {
+    use crate::{
+        abs::Ranked,
+        conc::Concrete,
+        float::{Float, Real},
+        geometry::Point,
+        group::Group,
+        Polytope,
+    };
+
+    /// Checks that the convex hull of the vertices of a polytope has the same
+    /// element counts as the polytope.
+    fn test_hull(poly: Concrete) {
+        let hull = Concrete::convex_hull(poly.vertices.clone());
+        crate::test(&hull, poly.el_count_iter());
     }
-}
 
-impl Concrete {
-    pub fn convex_hull_plus(&self) -> Concrete {
-        convex_hull(self.vertices.clone())
+    #[test]
+    fn simplex() {
+        for rank in 3..=6 {
+            test_hull(Concrete::simplex(rank));
+        }
+    }
+
+    #[test]
+    fn hypercube() {
+        for rank in 3..=6 {
+            test_hull(Concrete::hypercube(rank));
+        }
+    }
+
+    #[test]
+    fn orthoplex() {
+        for rank in 3..=6 {
+            test_hull(Concrete::orthoplex(rank));
+        }
+    }
+
+    #[test]
+    fn orbit() {
+        let cube = Concrete::from_orbit(Group::hypercube(3), &Point::from_element(3, 1.0));
+        crate::test(&cube, [1, 8, 12, 6, 1]);
+
+        let cuboctahedron =
+            Concrete::from_orbit(Group::hypercube(3), &Point::from_vec(vec![1.0, 1.0, 0.0]));
+        crate::test(&cuboctahedron, [1, 12, 24, 14, 1]);
+    }
+
+    #[test]
+    fn cube() {
+        let hull = Concrete::convex_hull(Concrete::cube().vertices);
+        crate::test(&hull, [1, 8, 12, 6, 1]);
+    }
+
+    #[test]
+    fn interior_points() {
+        let mut vertices = Concrete::cube().vertices;
+        vertices.push(Point::zeros(3));
+        vertices.push(Point::from_vec(vec![0.5, 0.0, 0.0]));
+        vertices.push(vertices[0].clone());
+
+        let hull = Concrete::convex_hull(vertices);
+        crate::test(&hull, [1, 8, 12, 6, 1]);
+    }
+
+    #[test]
+    fn degenerate() {
+        let vertices = Concrete::polygon(5)
+            .vertices
+            .into_iter()
+            .map(|v| Point::from_vec(vec![v[0], v[1], v[0] + v[1], 0.0]))
+            .collect();
+
+        let hull = Concrete::convex_hull(vertices);
+        crate::test(&hull, [1, 5, 5, 1]);
+    }
+
+    #[test]
+    fn offset_cube() {
+        let cube = Concrete::cube();
+        let small = cube.offset_facets(-0.1).unwrap();
+        crate::test(&small, [1, 8, 12, 6, 1]);
+
+        // The smaller cube is concentric, with edge length 0.8.
+        for v in &small.vertices {
+            for &x in v.iter() {
+                assert!(approx::abs_diff_eq!(x.abs(), 0.4, epsilon = Real::EPS));
+            }
+        }
+
+        assert!(cube.offset_facets(-0.5).is_none());
+    }
+
+    #[test]
+    fn point() {
+        let hull = Concrete::convex_hull(vec![Point::zeros(3), Point::zeros(3)]);
+        crate::test(&hull, [1, 1]);
     }
 }
@@ -242,9 +242,55 @@ impl CycleBuilder {
     }
 }
 
+impl Cycle {
+    /// Builds the cycle that traverses a list of edges in the order they're
+    /// given, or returns `None` if consecutive edges don't share a vertex, or
+    /// if the last one doesn't close the cycle.
+    pub fn from_ordered_edges<T: AsRef<[usize]>>(edges: &[T]) -> Option<Self> {
+        let first = edges.first()?.as_ref();
+        let second = edges.get(1)?.as_ref();
+
+        // The cycle starts at the vertex of the first edge that isn't on the
+        // second one.
+        let start = if second.contains(&first[0]) {
+            first[1]
+        } else {
+            first[0]
+        };
+
+        let mut cycle = Self::with_capacity(edges.len());
+        let mut cur = start;
+        for edge in edges {
+            let edge = edge.as_ref();
+            cycle.push(cur);
+
+            cur = if edge[0] == cur {
+                edge[1]
+            } else if edge[1] == cur {
+                edge[0]
+            } else {
+                return None;
+            };
+        }
+
+        (cur == start).then(|| cycle)
+    }
+}
+
 impl CycleList {
     /// Builds a list of cycles from a list of edges.
     pub fn from_edges<T: AsRef<[usize]>, I: IntoIterator<Item = T>>(edges: I) -> Self {
         edges.into_iter().collect::<CycleBuilder>().build()
     }
+
+    /// Builds a list of cycles from the edges of a face. If these are listed
+    /// in the order in which a single cycle traverses them, as they are after
+    /// [`Concrete::orient`](crate::conc::Concrete::orient), the cycle follows
+    /// this order.
+    pub fn from_face_edges<T: AsRef<[usize]>>(edges: &[T]) -> Self {
+        match Cycle::from_ordered_edges(edges) {
+            Some(cycle) => Self(vec![cycle]),
+            None => Self::from_edges(edges),
+        }
+    }
 }
@@ -0,0 +1,143 @@
+//! Computes rotation-invariant fingerprints of polytopes, which can be used to
+//! quickly match a polytope against a catalog of known ones.
+
+use crate::{
+    conc::Concrete,
+    float::{Float, Real},
+};
+
+use super::ConcretePolytope;
+
+/// Evaluates the zonal polynomial of a given degree for the sphere in a given
+/// number of dimensions. These are the
+/// [Gegenbauer polynomials](https://en.wikipedia.org/wiki/Gegenbauer_polynomials)
+/// with parameter `(dim - 2) / 2`, or the Chebyshev polynomials when `dim` is
+/// 2. In three dimensions, these are the Legendre polynomials.
+fn zonal(dim: usize, degree: usize, x: Real) -> Real {
+    let (mut prev, mut cur) = (0.0, 1.0);
+
+    if dim <= 2 {
+        for n in 0..degree {
+            let next = if n == 0 { x } else { 2.0 * x * cur - prev };
+            prev = cur;
+            cur = next;
+        }
+    } else {
+        let lambda = (dim as Real - 2.0) / 2.0;
+        for n in 1..=degree {
+            let n = n as Real;
+            let next = (2.0 * x * (n + lambda - 1.0) * cur - (n + 2.0 * lambda - 2.0) * prev) / n;
+            prev = cur;
+            cur = next;
+        }
+    }
+
+    cur
+}
+
+impl Concrete {
+    /// Computes a rotation-invariant descriptor of the vertex distribution of a
+    /// polytope, made out of the magnitudes of its first `bands`
+    /// [spherical harmonic](https://en.wikipedia.org/wiki/Spherical_harmonics)
+    /// bands.
+    ///
+    /// The vertices are taken relative to the gravicenter and weighted by
+    /// their distance to it, relative to the circumradius. By the addition
+    /// theorem, the power in each band is a sum of zonal polynomials over
+    /// pairs of vertices, so it doesn't depend on the orientation of the
+    /// polytope, nor on the order of its vertices. Each band is normalized
+    /// only up to a constant factor.
+    pub fn shape_descriptor(&self, bands: usize) -> Vec<Real> {
+        let center = match self.gravicenter() {
+            Some(center) => center,
+            None => return vec![0.0; bands],
+        };
+        let dim = center.len();
+
+        let mut directions = Vec::with_capacity(self.vertices.len());
+        let mut weights = Vec::with_capacity(self.vertices.len());
+        for v in &self.vertices {
+            let v = v - &center;
+            let norm = v.norm();
+
+            if norm > Real::EPS {
+                directions.push(v / norm);
+                weights.push(norm);
+            }
+        }
+
+        // Makes the descriptor scale-invariant.
+        let max = weights.iter().copied().fold(0.0, Real::max);
+        if max == 0.0 {
+            return vec![0.0; bands];
+        }
+        let count = self.vertices.len() as Real;
+        for w in &mut weights {
+            *w /= max * count;
+        }
+
+        (0..bands)
+            .map(|band| {
+                let mut power = 0.0;
+                for (u, wu) in directions.iter().zip(&weights) {
+                    for (v, wv) in directions.iter().zip(&weights) {
+                        power += wu * wv * zonal(dim, band, u.dot(v).clamp(-1.0, 1.0));
+                    }
+                }
+
+                power.max(0.0).sqrt()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conc::{Concrete, ConcretePolytope},
+        float::{Float, Real},
+        geometry::Matrix,
+        Polytope,
+    };
+
+    /// The tolerance when comparing descriptors. Rounding errors pile up over
+    /// every band, which is noticeable in single precision.
+    const TOLERANCE: Real = if cfg!(feature = "f32") {
+        1e-4
+    } else {
+        Real::EPS
+    };
+
+    /// Returns the distance between two descriptors.
+    fn distance(p: &[Real], q: &[Real]) -> Real {
+        p.iter()
+            .zip(q)
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<Real>()
+            .sqrt()
+    }
+
+    #[test]
+    fn rotated_cube() {
+        let (sin, cos) = (0.7 as Real).sin_cos();
+        let (sin2, cos2) = (1.3 as Real).sin_cos();
+        let rotation = Matrix::from_row_slice(3, 3, &[cos, -sin, 0.0, sin, cos, 0.0, 0.0, 0.0, 1.0])
+            * Matrix::from_row_slice(3, 3, &[1.0, 0.0, 0.0, 0.0, cos2, -sin2, 0.0, sin2, cos2]);
+
+        let cube = Concrete::cube();
+        let mut rotated = cube.clone().apply(&rotation);
+        rotated.scale(2.5);
+
+        assert!(distance(&cube.shape_descriptor(8), &rotated.shape_descriptor(8)) < TOLERANCE);
+    }
+
+    #[test]
+    fn cube_octahedron() {
+        assert!(
+            distance(
+                &Concrete::cube().shape_descriptor(8),
+                &Concrete::octahedron().shape_descriptor(8)
+            ) > 0.01
+        );
+    }
+}
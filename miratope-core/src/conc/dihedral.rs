@@ -0,0 +1,142 @@
+//! Contains the code that measures the dihedral angles of a polytope.
+
+use crate::{
+    abs::Ranked,
+    conc::{Concrete, ConcretePolytope},
+    float::{Float, Real},
+    geometry::{centroid, Point, Subspace},
+};
+
+use vec_like::*;
+
+impl Concrete {
+    /// Returns the interior angle between the two facets meeting at a ridge,
+    /// or `None` if the ridge or its facets are degenerate.
+    ///
+    /// We measure the angle between the directions that go from the ridge into
+    /// each facet, orthogonally to the ridge. Each facet's centroid tells us
+    /// which side of the ridge the facet lies on.
+    fn dihedral_angle(&self, idx: usize) -> Option<Real> {
+        let rank = self.rank();
+        let dim = self.dim()?;
+        let ridge = self.get_element(rank - 2, idx)?;
+        if ridge.sups.len() != 2 {
+            return None;
+        }
+
+        // The vertices of an element.
+        let vertices = |r: usize, i: usize| -> Option<Vec<Point<Real>>> {
+            Some(
+                self.abs
+                    .element_vertices(r, i)?
+                    .into_iter()
+                    .map(|v| self.vertices[v].clone())
+                    .collect(),
+            )
+        };
+
+        let ridge_vertices = vertices(rank - 2, idx)?;
+        let ridge_space = Subspace::from_points(ridge_vertices.iter());
+        if ridge_space.rank() + 2 != dim {
+            return None;
+        }
+
+        let mut directions = Vec::with_capacity(2);
+        for &facet in ridge.sups.iter() {
+            let facet_vertices = vertices(rank - 1, facet)?;
+            if Subspace::from_points(facet_vertices.iter()).rank() + 1 != dim {
+                return None;
+            }
+
+            let center = centroid(&facet_vertices);
+            let mut direction = &center - ridge_space.project(&center);
+            if direction.normalize_mut() < Real::EPS {
+                return None;
+            }
+
+            directions.push(direction);
+        }
+
+        let cos = directions[0].dot(&directions[1]);
+        Some(cos.clamp(-1.0, 1.0).acos())
+    }
+
+    /// Computes the dihedral angles of a polytope, in radians, and groups the
+    /// ones that are equal up to [`Float::EPS`]. Returns each angle in
+    /// increasing order together with the number of ridges it occurs at, and
+    /// the number of ridges that were skipped, either because they don't join
+    /// exactly two facets, or because they or their facets aren't of full
+    /// rank.
+    pub fn dihedral_angle_classes(&self) -> (Vec<(usize, Real)>, usize) {
+        let rank = self.rank();
+        if rank < 3 || self.dim() != Some(rank - 1) {
+            return (Vec::new(), 0);
+        }
+
+        let mut angles = Vec::new();
+        let mut skipped = 0;
+        for idx in 0..self.el_count(rank - 2) {
+            match self.dihedral_angle(idx) {
+                Some(angle) => angles.push(angle),
+                None => skipped += 1,
+            }
+        }
+
+        angles.sort_by(|a, b| a.partial_cmp(b).expect("Angle is NaN"));
+        let mut classes: Vec<(usize, Real)> = Vec::new();
+        for angle in angles {
+            match classes.last_mut() {
+                Some((count, class)) if (angle - *class).fabs() < Real::EPS => *count += 1,
+                _ => classes.push((1, angle)),
+            }
+        }
+
+        (classes, skipped)
+    }
+
+    /// Computes the dihedral angles of a polytope, in radians, and groups the
+    /// ones that are equal up to [`Float::EPS`]. Returns each angle in
+    /// increasing order together with the number of ridges it occurs at.
+    /// Degenerate ridges are skipped.
+    pub fn dihedral_angles(&self) -> Vec<(usize, Real)> {
+        self.dihedral_angle_classes().0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conc::Concrete,
+        float::{Float, Real},
+        Polytope,
+    };
+
+    use approx::assert_abs_diff_eq;
+
+    /// Checks the dihedral angles of some polyhedra.
+    #[test]
+    fn polyhedra() {
+        let angles = Concrete::cube().dihedral_angles();
+        assert_eq!(angles.len(), 1);
+        assert_eq!(angles[0].0, 12);
+        assert_abs_diff_eq!(angles[0].1, Real::PI / 2.0, epsilon = Real::EPS);
+
+        let angles = Concrete::simplex(4).dihedral_angles();
+        assert_eq!(angles.len(), 1);
+        assert_eq!(angles[0].0, 6);
+        assert_abs_diff_eq!(angles[0].1, (1.0 as Real / 3.0).acos(), epsilon = Real::EPS);
+
+        let (angles, skipped) = Concrete::polygon(4).pyramid().dihedral_angle_classes();
+        assert_eq!(angles.iter().map(|&(count, _)| count).collect::<Vec<_>>(), [4, 4]);
+        assert_eq!(skipped, 0);
+    }
+
+    /// Checks that the angles of a polygon are its interior angles.
+    #[test]
+    fn polygon() {
+        let angles = Concrete::polygon(6).dihedral_angles();
+        assert_eq!(angles.len(), 1);
+        assert_eq!(angles[0].0, 6);
+        assert_abs_diff_eq!(angles[0].1, 2.0 * Real::PI / 3.0, epsilon = Real::EPS);
+    }
+}
@@ -5,7 +5,7 @@ use std::{collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque}, vec, it
 use crate::{
     abs::{Abstract, Element, ElementList, Ranked, Ranks, Subelements, Superelements, AbstractBuilder},
     conc::{Concrete, ConcretePolytope},
-    float::Float,
+    float::{Float, Real},
     group::{Group}, geometry::{Matrix, PointOrd, Subspace, Point}, Polytope
 };
 
@@ -16,7 +16,7 @@ use vec_like::*;
 /// Input for the faceting function
 pub enum GroupEnum {
     /// Group of matrices
-    ConcGroup(Group<vec::IntoIter<Matrix<f64>>>),
+    ConcGroup(Group<vec::IntoIter<Matrix<Real>>>),
     /// Group of vertex mappings
     VertexMap(Vec<Vec<usize>>),
     /// True: take chiral group
@@ -244,11 +244,11 @@ fn filter_irc(vec: &Vec<Vec<(usize,usize)>>) -> Vec<usize> {
 
 fn faceting_subdim(
     rank: usize,
-    plane: Subspace<f64>,
-    points: Vec<PointOrd<f64>>,
+    plane: Subspace<Real>,
+    points: Vec<PointOrd<Real>>,
     vertex_map: Vec<Vec<usize>>,
-    min_edge_length: Option<f64>,
-    max_edge_length: Option<f64>,
+    min_edge_length: Option<Real>,
+    max_edge_length: Option<Real>,
     max_per_hyperplane: Option<usize>,
 	uniform: bool,
     noble_package: Option<(&Vec<Vec<usize>>, &Vec<usize>, usize)>,
@@ -358,12 +358,12 @@ fn faceting_subdim(
             if !checked[rep][vertex] {
                 let edge_length = (&points[vertex].0-&points[rep].0).norm();
                 if let Some(min) = min_edge_length {
-                    if edge_length < min - f64::EPS {
+                    if edge_length < min - Real::EPS {
                         continue
                     }
                 }
                 if let Some(max) = max_edge_length {
-                    if edge_length > max + f64::EPS {
+                    if edge_length > max + Real::EPS {
                         continue
                     }
                 }
@@ -408,13 +408,13 @@ fn faceting_subdim(
                 for (v_i, v) in new_vertices.iter().enumerate() {
                     let edge_length = (&points[*v].0-&points[rep[0]].0).norm();
                     if let Some(min) = min_edge_length {
-                        if edge_length < min - f64::EPS {
+                        if edge_length < min - Real::EPS {
                             update = v_i;
                             break 'c;
                         }
                     }
                     if let Some(max) = max_edge_length {
-                        if edge_length > max + f64::EPS {
+                        if edge_length > max + Real::EPS {
                             update = v_i;
                             break 'c;
                         }
@@ -434,7 +434,7 @@ fn faceting_subdim(
 
                     let mut hyperplane_vertices = Vec::new();
                     for (idx, v) in flat_points.iter().enumerate() {
-                        if hyperplane.distance(&v.0) < f64::EPS {
+                        if hyperplane.distance(&v.0) < Real::EPS {
                             hyperplane_vertices.push(idx);
                         }
                     }
@@ -1089,13 +1089,13 @@ impl Concrete {
     /// If the symmetry group is not provided, it uses the full symmetry of the polytope.
     pub fn faceting(
         &mut self,
-        vertices: Vec<Point<f64>>,
+        vertices: Vec<Point<Real>>,
         symmetry: GroupEnum,
         any_single_edge_length: bool,
-        mut min_edge_length: Option<f64>,
-        mut max_edge_length: Option<f64>,
-        min_inradius: Option<f64>,
-        max_inradius: Option<f64>,
+        mut min_edge_length: Option<Real>,
+        mut max_edge_length: Option<Real>,
+        min_inradius: Option<Real>,
+        max_inradius: Option<Real>,
         exclude_hemis: bool,
         only_below_vertex: bool,
         noble: Option<usize>,
@@ -1117,7 +1117,7 @@ impl Concrete {
             return Vec::new()
         }
 
-        let mut vertices_ord = Vec::<PointOrd<f64>>::new();
+        let mut vertices_ord = Vec::<PointOrd<Real>>::new();
         for v in &vertices {
             vertices_ord.push(PointOrd::new(v.clone()));
         }
@@ -1175,7 +1175,7 @@ impl Concrete {
 
         println!("{} vertices in {} orbit{}", vertices.len(), orbit_idx, if orbit_idx == 1 {""} else {"s"});
 
-        let mut possible_lengths_set = BTreeSet::<OrderedFloat<f64>>::new();
+        let mut possible_lengths_set = BTreeSet::<OrderedFloat<Real>>::new();
         let mut possible_lengths = Vec::new();
 
         if any_single_edge_length {
@@ -1187,7 +1187,7 @@ impl Concrete {
                     possible_lengths_set.insert(OrderedFloat((vertices[rep].clone() - vertices[i].clone()).norm()));
                 }
             }
-            let mut possible_lengths_ordf: Vec<&OrderedFloat<f64>> = possible_lengths_set.iter().collect();
+            let mut possible_lengths_ordf: Vec<&OrderedFloat<Real>> = possible_lengths_set.iter().collect();
             possible_lengths_ordf.sort_unstable();
 
             if possible_lengths_ordf.len() > 0 {
@@ -1196,7 +1196,7 @@ impl Concrete {
             for idx in 0..possible_lengths_ordf.len()-1 {
                 let len1 = possible_lengths_ordf[idx].0;
                 let len2 = possible_lengths_ordf[idx+1].0;
-                if len2-len1 > f64::EPS {
+                if len2-len1 > Real::EPS {
                     possible_lengths.push(len2);
                 }
             }
@@ -1219,7 +1219,7 @@ impl Concrete {
 
             if only_below_vertex {
                 for v_orbit in &vertex_orbits {
-                    let mut map = BTreeMap::<OrderedFloat<f64>, Vec<usize>>::new();
+                    let mut map = BTreeMap::<OrderedFloat<Real>, Vec<usize>>::new();
                     let rep = v_orbit[0];
                     let point = &vertices[rep];
 
@@ -1251,12 +1251,12 @@ impl Concrete {
                         for v in &list[1..] {
                             let edge_length = (&vertices[*v]-&vertices[list[0]]).norm();
                             if let Some(min) = min_edge_length {
-                                if edge_length < min - f64::EPS {
+                                if edge_length < min - Real::EPS {
                                     continue 'd;
                                 }
                             }
                             if let Some(max) = max_edge_length {
-                                if edge_length > max + f64::EPS {
+                                if edge_length > max + Real::EPS {
                                     continue 'd;
                                 }
                             }
@@ -1270,24 +1270,24 @@ impl Concrete {
                         if hyperplane.is_hyperplane() {
                             let inradius = hyperplane.distance(&Point::zeros(self.dim().unwrap()));
                             if let Some(min) = min_inradius {
-                                if inradius < min - f64::EPS {
+                                if inradius < min - Real::EPS {
                                     continue
                                 }
                             }
                             if let Some(max) = max_inradius {
-                                if inradius > max + f64::EPS {
+                                if inradius > max + Real::EPS {
                                     continue
                                 }
                             }
                             if exclude_hemis {
-                                if inradius.abs() < f64::EPS {
+                                if inradius.abs() < Real::EPS {
                                     continue
                                 }
                             }
 
                             let mut hyperplane_vertices = Vec::new();
                             for (idx, v) in vertices.iter().enumerate() {
-                                if hyperplane.distance(&v) < f64::EPS {
+                                if hyperplane.distance(&v) < Real::EPS {
                                     hyperplane_vertices.push(idx);
                                 }
                             }
@@ -1337,12 +1337,12 @@ impl Concrete {
                         if !checked[rep][vertex] {
                             let edge_length = (&vertices[vertex]-&vertices[rep]).norm();
                             if let Some(min) = min_edge_length {
-                                if edge_length < min - f64::EPS {
+                                if edge_length < min - Real::EPS {
                                     continue;
                                 }
                             }
                             if let Some(max) = max_edge_length {
-                                if edge_length > max + f64::EPS {
+                                if edge_length > max + Real::EPS {
                                     continue;
                                 }
                             }
@@ -1383,12 +1383,12 @@ impl Concrete {
 
                             let edge_length = (&vertices[tuple[0]]-&vertices[new_vertex]).norm();
                             if let Some(min) = min_edge_length {
-                                if edge_length < min - f64::EPS {
+                                if edge_length < min - Real::EPS {
                                     wrong_edge = true;
                                 }
                             }
                             if let Some(max) = max_edge_length {
-                                if edge_length > max + f64::EPS {
+                                if edge_length > max + Real::EPS {
                                     wrong_edge = true;
                                 }
                             }
@@ -1445,12 +1445,12 @@ impl Concrete {
 
                         let edge_length = (&vertices[new_vertex]-&vertices[rep[0]]).norm();
                         if let Some(min) = min_edge_length {
-                            if edge_length < min - f64::EPS {
+                            if edge_length < min - Real::EPS {
                                 continue;
                             }
                         }
                         if let Some(max) = max_edge_length {
-                            if edge_length > max + f64::EPS {
+                            if edge_length > max + Real::EPS {
                                 continue;
                             }
                         }
@@ -1465,24 +1465,24 @@ impl Concrete {
                         if hyperplane.is_hyperplane() {
                             let inradius = hyperplane.distance(&Point::zeros(self.dim().unwrap()));
                             if let Some(min) = min_inradius {
-                                if inradius < min - f64::EPS {
+                                if inradius < min - Real::EPS {
                                     break
                                 }
                             }
                             if let Some(max) = max_inradius {
-                                if inradius > max + f64::EPS {
+                                if inradius > max + Real::EPS {
                                     break
                                 }
                             }
                             if exclude_hemis {
-                                if inradius.abs() < f64::EPS {
+                                if inradius.abs() < Real::EPS {
                                     break
                                 }
                             }
 
                             let mut hyperplane_vertices = Vec::new();
                             for (idx, v) in vertices.iter().enumerate() {
-                                if hyperplane.distance(&v) < f64::EPS {
+                                if hyperplane.distance(&v) < Real::EPS {
                                     hyperplane_vertices.push(idx);
                                 }
                             }
@@ -1653,7 +1653,7 @@ impl Concrete {
                         let mut all_vertices_idx = HashSet::new();
 
                         for (i, vertex) in vertices.iter().enumerate() {
-                            if subspace.distance(&vertex) < f64::EPS {
+                            if subspace.distance(&vertex) < Real::EPS {
                                 all_vertices_idx.insert(i);
                             }
                         }
@@ -2211,4 +2211,51 @@ impl Concrete {
             return output
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::GroupEnum;
+    use crate::{
+        abs::Ranked,
+        conc::{Concrete, ConcretePolytope},
+        float::{Float, Real},
+        Polytope,
+    };
+
+    /// Checks that the great icosahedron is among the facetings of the
+    /// icosahedron.
+    #[test]
+    fn great_icosahedron() {
+        let mut icosahedron = Concrete::icosahedron();
+        let vertices = icosahedron.vertices.clone();
+        let facetings = icosahedron.faceting(
+            vertices,
+            GroupEnum::Chiral(false),
+            false,
+            None,
+            None,
+            None,
+            None,
+            true,
+            false,
+            None,
+            None,
+            false,
+            false,
+            false,
+            false,
+            true,
+            false,
+            false,
+            String::new(),
+        );
+
+        let edge = (1.0 + Real::SQRT_5) / 2.0;
+        assert!(facetings.iter().any(|(faceting, _)| {
+            let mut faceting = faceting.clone();
+            faceting.element_sort();
+            faceting.el_count_iter().eq([1, 12, 30, 20, 1])
+                && faceting.is_equilateral_with(edge)
+        }));
+    }
+}
@@ -1,12 +1,20 @@
 //! Declares the [`Concrete`] polytope type and all associated data structures.
 
+pub mod convex;
 pub mod cycle;
+pub mod descriptor;
+pub mod dihedral;
 pub mod element_types;
 pub mod faceting;
+pub mod orientation;
+pub mod repair;
+pub mod section;
 pub mod symmetry;
+pub mod wiki;
+pub mod wythoff;
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     ops::{Index, IndexMut}, iter,
 };
 
@@ -18,8 +26,8 @@ use super::{
     DualError, Polytope,
 };
 use crate::{
-    abs::{AbstractBuilder, Element, ElementMap, Subelements, Superelements, Ranks},
-    float::Float,
+    abs::{AbstractBuilder, AbstractResult, Element, ElementMap, Subelements, Superelements, Ranks},
+    float::{Float, Real},
     geometry::*,
 };
 
@@ -35,7 +43,7 @@ pub struct Concrete {
     /// The list of vertices as points in Euclidean space.
     // todo: come up with a more compact representation, making use of the fact
     // all points have the same length?
-    pub vertices: Vec<Point<f64>>,
+    pub vertices: Vec<Point<Real>>,
 
     /// The underlying abstract polytope.
     pub abs: Abstract,
@@ -76,7 +84,7 @@ impl IndexMut<(usize, usize)> for Concrete {
 impl Concrete {
     /// Initializes a new concrete polytope from a set of vertices and an
     /// underlying abstract polytope. Does some debug assertions on the input.
-    pub fn new(vertices: Vec<Point<f64>>, abs: Abstract) -> Self {
+    pub fn new(vertices: Vec<Point<Real>>, abs: Abstract) -> Self {
         // There must be as many abstract vertices as concrete ones.
         debug_assert_eq!(
             abs.vertex_count(),
@@ -96,6 +104,368 @@ impl Concrete {
         // With no further info, we create a generic name for the polytope.
         Self { vertices, abs }
     }
+
+    /// Checks whether the polytope is bounded and dyadic, and whether all of
+    /// its indices refer to valid elements. Otherwise, returns an error
+    /// pointing to the first element or section at fault. These checks take
+    /// linear time, so they can be run on every polytope that's loaded.
+    pub fn is_valid(&self) -> AbstractResult<()> {
+        self.abs.ranks().is_valid()
+    }
+
+    /// Checks whether the polytope satisfies every axiom of an abstract
+    /// polytope, including strong connectivity. Unlike [`Self::is_valid`],
+    /// this takes quadratic time in the worst case.
+    pub fn is_polytope(&self) -> AbstractResult<()> {
+        self.abs.ranks().is_polytope()
+    }
+
+    /// Takes the cross-section of a polytope through a given hyperplane, only
+    /// checking the given edges for intersections. Any edges that intersect
+    /// the hyperplane must be included, in increasing order, for the result to
+    /// match [`ConcretePolytope::cross_section`].
+    ///
+    /// # Panics
+    /// Panics if the polytope is a polygon or lower.
+    pub(crate) fn cross_section_through<I: IntoIterator<Item = usize>>(
+        &self,
+        slice: &Hyperplane<Real>,
+        edges: I,
+    ) -> Self {
+        assert!(self.rank() >= 4);
+
+        let mut vertices = Vec::new();
+        let mut ranks = Vec::with_capacity(self.rank());
+
+        // We map all indices of k-elements in the original polytope to the
+        // indices of the new (k-1)-elements resulting from taking their
+        // intersections with the slicing hyperplane.
+        let mut hash_element = HashMap::new();
+
+        // Determines the vertices of the cross-section. Edges that meet at a
+        // vertex on the hyperplane all intersect it at the same point, so we
+        // merge coincident vertices.
+        let mut vertex_indices = BTreeMap::new();
+        for idx in edges {
+            let edge = &self[2][idx];
+            let segment = Segment(&self.vertices[edge.subs[0]], &self.vertices[edge.subs[1]]);
+
+            match slice.intersect(segment) {
+                // If we got ourselves a new vertex:
+                SegmentIntersection::Point(p) => {
+                    let len = vertices.len();
+                    let v = *vertex_indices
+                        .entry(PointOrd::new(p.clone()))
+                        .or_insert_with(|| {
+                            vertices.push(p);
+                            len
+                        });
+                    hash_element.insert(idx, v);
+                }
+
+                // An edge on the hyperplane isn't a vertex of the
+                // cross-section. Its endpoints are found through the other
+                // edges that meet at them, so the faces through it become the
+                // edge between them.
+                SegmentIntersection::Whole | SegmentIntersection::None => {}
+            }
+        }
+
+        let vertex_count = vertices.len();
+
+        // The slice does not intersect the polytope.
+        if vertex_count == 0 {
+            return Self::nullitope();
+        }
+
+        ranks.push(SubelementList::min());
+        ranks.push(SubelementList::vertices(vertex_count));
+
+        // Takes care of building everything else.
+        for r in 3..self.rank() {
+            let mut new_hash_element = HashMap::new();
+            let mut new_els = SubelementList::new();
+            let mut el_indices = HashMap::new();
+
+            for (idx, el) in self[r].iter().enumerate() {
+                let mut new_subs: Vec<_> = el
+                    .subs
+                    .iter()
+                    .filter_map(|sub| hash_element.get(sub).copied())
+                    .collect();
+                new_subs.sort_unstable();
+                new_subs.dedup();
+
+                // If we got ourselves a new edge. Elements that only touch the
+                // hyperplane would become degenerate, so we skip them, and
+                // elements that coincide after merging vertices are merged.
+                if new_subs.len() >= 2 {
+                    let len = new_els.len();
+                    let new_idx = *el_indices.entry(new_subs.clone()).or_insert_with(|| {
+                        new_els.push(new_subs.into());
+                        len
+                    });
+                    new_hash_element.insert(idx, new_idx);
+                }
+            }
+
+            ranks.push(new_els);
+            hash_element = new_hash_element;
+        }
+
+        // Adds a maximal element manually.
+        ranks.push(SubelementList::max(ranks.last().unwrap().len()));
+
+        // Splits compounds of dyads.
+        let (first, last) = ranks.split_at_mut(3);
+
+        if let (Some(edges), Some(faces)) = (first.last_mut(), last.first_mut()) {
+            // Keeps track of the indices of our new edges.
+            let mut edge_num = edges.len();
+            let mut new_edges = SubelementList::new();
+
+            // The superelements of all edges.
+            let mut edge_sups = Vec::new();
+            for _ in 0..edge_num {
+                edge_sups.push(Superelements::new());
+            }
+
+            for (idx, face) in faces.iter().enumerate() {
+                for &sub in face {
+                    edge_sups[sub].push(idx);
+                }
+            }
+
+            for (edge_idx, subs) in edges.iter_mut().enumerate() {
+                debug_assert_eq!(
+                    subs.len() % 2,
+                    0,
+                    "A line should always intersect a polygon an even amount of times!"
+                );
+                let comps = subs.len() / 2;
+
+                if comps > 1 {
+                    // Sorts the component's vertices lexicographically.
+                    subs.sort_unstable_by_key(|&x| PointOrd::new(vertices[x].clone()));
+
+                    // Splits the edge, adds the new split edges as subelements
+                    // to the edge's superelements.
+                    for _ in 1..comps {
+                        let v0 = subs.pop().unwrap();
+                        let v1 = subs.pop().unwrap();
+                        new_edges.push(vec![v0, v1].into());
+
+                        for &sup in &edge_sups[edge_idx] {
+                            faces[sup].push(edge_num);
+                        }
+
+                        edge_num += 1;
+                    }
+                }
+            }
+
+            // Adds the new edges.
+            edges.append(&mut new_edges);
+        }
+
+        // Builds the polytope.
+        // TODO: no need for ranks, just use the builder directly.
+        let mut builder = AbstractBuilder::new();
+        for subelements in ranks {
+            builder.push(subelements);
+        }
+
+        // Safety: TODO shit, this one's complicated... I'll come back to it.
+        unsafe {
+            let mut abs = builder.build();
+            abs.untangle_faces();
+            Self::new(vertices, abs)
+        }
+    }
+
+    /// Flattens a cross-section through a hyperplane into the coordinates of
+    /// the hyperplane, so that the projection of the origin becomes the new
+    /// origin. Returns `None` if the section is empty.
+    pub(crate) fn flatten_section(mut self, slice: &Hyperplane<Real>) -> Option<Self> {
+        if self.vertices.is_empty() {
+            return None;
+        }
+
+        let dim = slice.subspace.offset.len();
+        self.flatten_into(&slice.subspace);
+        self.recenter_with(&slice.flatten(&slice.project(&Point::zeros(dim))));
+        Some(self)
+    }
+
+    /// Returns the [dual](https://polytope.miraheze.org/wiki/Dual_polytope) of
+    /// a polytope using the unit hypersphere, or `None` if any facet passes
+    /// through the origin.
+    pub fn dual(&self) -> Option<Self> {
+        self.try_dual().ok()
+    }
+
+    /// Returns the [dual](https://polytope.miraheze.org/wiki/Dual_polytope) of
+    /// a polytope with respect to a given hypersphere, or `None` if any facet
+    /// passes through its center.
+    pub fn dual_with(&self, sphere: &Hypersphere<Real>) -> Option<Self> {
+        self.try_dual_with(sphere).ok()
+    }
+
+    /// Merges several polytopes of the same rank into a compound, identifying
+    /// the vertices that coincide up to floating point error. Returns the
+    /// nullitope if there are no components.
+    ///
+    /// # Panics
+    /// This method will panic if the polytopes have different ranks.
+    pub fn compound_dedup<I: IntoIterator<Item = Self>>(components: I) -> Self {
+        let compound = Self::compound(components.into_iter());
+        let rank = compound.rank();
+        if rank < 3 {
+            return compound;
+        }
+
+        // Maps every vertex to the first vertex that coincides with it.
+        let mut indices = BTreeMap::new();
+        let mut vertices = Vec::new();
+        let vertex_map: Vec<usize> = compound
+            .vertices
+            .iter()
+            .map(|v| {
+                *indices.entry(PointOrd::new(v.clone())).or_insert_with(|| {
+                    vertices.push(v.clone());
+                    vertices.len() - 1
+                })
+            })
+            .collect();
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        for r in 2..rank {
+            let mut subelements = SubelementList::with_capacity(compound.el_count(r));
+            for el in &compound[r] {
+                subelements.push(if r == 2 {
+                    el.subs.iter().map(|&v| vertex_map[v]).collect()
+                } else {
+                    el.subs.clone()
+                });
+            }
+            builder.push(subelements);
+        }
+
+        builder.push_max();
+
+        // Safety: identifying coincident vertices doesn't change the structure
+        // of the components.
+        Self::new(vertices, unsafe { builder.build() })
+    }
+
+    /// Gets the element with a given rank and index as a polytope, flattened
+    /// into its own subspace and centered at its gravicenter. For instance, a
+    /// face of a cube comes back as a 2D square. Returns `None` if the element
+    /// doesn't exist.
+    pub fn flat_element(&self, rank: usize, idx: usize) -> Option<Self> {
+        let mut element = self.element(rank, idx)?;
+        element.flatten();
+        element.recenter();
+        Some(element)
+    }
+
+    /// Builds the vertex figure of a vertex, whose vertices are the midpoints
+    /// of the edges through it. The result is flattened into its own subspace
+    /// and centered at its gravicenter. Returns `None` if the vertex doesn't
+    /// exist, or if the polytope has rank less than 2.
+    pub fn vertex_figure(&self, vertex: usize) -> Option<Self> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+        self.get_element(1, vertex)?;
+
+        // Maps the elements of each rank containing the vertex to their
+        // indices in the vertex figure.
+        let mut containing = vec![HashMap::new(); rank];
+        containing[1].insert(vertex, 0);
+        for r in 1..rank - 1 {
+            let mut els: Vec<_> = containing[r]
+                .keys()
+                .flat_map(|&idx| self[(r, idx)].sups.iter().copied())
+                .collect();
+            els.sort_unstable();
+            els.dedup();
+            containing[r + 1] = els.into_iter().enumerate().map(|(i, el)| (el, i)).collect();
+        }
+
+        // The elements of the figure, sorted by their index.
+        let sorted = |r: usize| {
+            let mut els: Vec<_> = containing[r].iter().map(|(&el, &i)| (i, el)).collect();
+            els.sort_unstable();
+            els.into_iter().map(|(_, el)| el)
+        };
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank - 1);
+        builder.push_min();
+        builder.push_vertices(containing[2].len());
+
+        for r in 3..rank {
+            let mut subelements = SubelementList::with_capacity(containing[r].len());
+            for el in sorted(r) {
+                subelements.push(
+                    self[(r, el)]
+                        .subs
+                        .iter()
+                        .filter_map(|sub| containing[r - 1].get(sub).copied())
+                        .collect(),
+                );
+            }
+            builder.push(subelements);
+        }
+
+        builder.push_max();
+
+        let vertices = sorted(2)
+            .map(|edge| {
+                let subs = &self[(2, edge)].subs;
+                (&self.vertices[subs[0]] + &self.vertices[subs[1]]) / 2.0
+            })
+            .collect();
+
+        // Safety: the elements containing a given vertex form a section of the
+        // polytope, which is itself a polytope.
+        let mut figure = Self::new(vertices, unsafe { builder.build() });
+        figure.flatten();
+        figure.recenter();
+        Some(figure)
+    }
+
+    /// Builds a regular [icosahedron](https://polytope.miraheze.org/wiki/Icosahedron)
+    /// with unit edge length, centered at the origin. Its vertices are the
+    /// cyclic permutations of (0, ±1/2, ±φ/2), where φ is the golden ratio.
+    pub fn icosahedron() -> Self {
+        let phi = (1.0 + Real::SQRT_5) / 2.0;
+        Self::convex_hull(cyclic_points(0.5, phi / 2.0))
+    }
+
+    /// Builds a regular [dodecahedron](https://polytope.miraheze.org/wiki/Dodecahedron)
+    /// with unit edge length, centered at the origin. Its vertices are the
+    /// points (±φ/2, ±φ/2, ±φ/2), together with the cyclic permutations of
+    /// (0, ±1/2, ±φ²/2), where φ is the golden ratio.
+    pub fn dodecahedron() -> Self {
+        let phi = (1.0 + Real::SQRT_5) / 2.0;
+        let mut points = cyclic_points(0.5, phi * phi / 2.0);
+
+        for i in 0..8 {
+            points.push(
+                (0..3)
+                    .map(|j| if i & (1 << j) == 0 { phi / 2.0 } else { -phi / 2.0 })
+                    .collect::<Vec<_>>()
+                    .into(),
+            );
+        }
+
+        Self::convex_hull(points)
+    }
 }
 
 impl Polytope for Concrete {
@@ -425,13 +795,13 @@ impl Polytope for Concrete {
             // equal to 0.
             for i in 0..dim {
                 let mut v = Point::zeros(dim);
-                v[i] = f64::HALF_SQRT_2;
+                v[i] = Real::HALF_SQRT_2;
                 vertices.push(v);
             }
 
             // Adds the remaining vertex, all of whose coordinates are equal.
-            let dim_f = dim as f64;
-            let a = (1.0 - (dim_f + 1.0).fsqrt()) * f64::HALF_SQRT_2 / dim_f;
+            let dim_f = dim as Real;
+            let a = (1.0 - (dim_f + 1.0).fsqrt()) * Real::HALF_SQRT_2 / dim_f;
             vertices.push(vec![a; dim].into());
 
             let mut simplex = Concrete::new(vertices, Abstract::simplex(rank));
@@ -446,7 +816,7 @@ impl Polytope for Concrete {
         if rank == 0 {
             Self::nullitope()
         } else {
-            Self::multitegum(iter::repeat(&Self::dyad_with(f64::SQRT_2)).take(rank - 1))
+            Self::multitegum(iter::repeat(&Self::dyad_with(Real::SQRT_2)).take(rank - 1))
         }
     }
 
@@ -462,13 +832,13 @@ impl Polytope for Concrete {
 /// The vertices are the padded vertices of `p`, followed by the padded
 /// vertices of `q`.
 fn duopyramid_vertices(
-    p: &[Point<f64>],
-    q: &[Point<f64>],
-    p_pad: &Point<f64>,
-    q_pad: &Point<f64>,
-    height: f64,
+    p: &[Point<Real>],
+    q: &[Point<Real>],
+    p_pad: &Point<Real>,
+    q_pad: &Point<Real>,
+    height: Real,
     tegum: bool,
-) -> Vec<Point<f64>> {
+) -> Vec<Point<Real>> {
     // Duotegums with points should just return the original polytopes.
     if tegum {
         if p.get(0).map(|vp| vp.len()) == Some(0) {
@@ -500,8 +870,22 @@ fn duopyramid_vertices(
         .collect()
 }
 
+/// Returns the cyclic permutations of the points (0, ±a, ±b).
+fn cyclic_points(a: Real, b: Real) -> Vec<Point<Real>> {
+    let mut points = Vec::with_capacity(12);
+
+    for (a, b) in [(a, b), (a, -b), (-a, b), (-a, -b)] {
+        let coords = [0.0, a, b];
+        for r in 0..3 {
+            points.push(Point::from_fn(3, |i, _| coords[(i + r) % 3]));
+        }
+    }
+
+    points
+}
+
 /// Generates the vertices for a duoprism with two given vertex sets.
-fn duoprism_vertices(p: &[Point<f64>], q: &[Point<f64>]) -> Vec<Point<f64>> {
+fn duoprism_vertices(p: &[Point<Real>], q: &[Point<Real>]) -> Vec<Point<Real>> {
     // The dimension of the points in p.
     let p_dim = if let Some(vp) = p.get(0) {
         vp.len()
@@ -539,12 +923,12 @@ pub trait ConcretePolytope: Polytope {
     fn con_mut(&mut self) -> &mut Concrete;
 
     /// Returns a reference to the concrete vertices of the polytope.
-    fn vertices(&self) -> &Vec<Point<f64>> {
+    fn vertices(&self) -> &Vec<Point<Real>> {
         &self.con().vertices
     }
 
     /// Returns a mutable reference to the concrete vertices of the polytope.
-    fn vertices_mut(&mut self) -> &mut Vec<Point<f64>> {
+    fn vertices_mut(&mut self) -> &mut Vec<Point<Real>> {
         &mut self.con_mut().vertices
     }
 
@@ -561,11 +945,11 @@ pub trait ConcretePolytope: Polytope {
     }
 
     /// Builds a dyad with a specified height.
-    fn dyad_with(height: f64) -> Self;
+    fn dyad_with(height: Real) -> Self;
 
     /// Builds the Grünbaumian star polygon `{n / d}` with unit circumradius,
     /// rotated by an angle.
-    fn grunbaum_star_polygon_with_rot(n: usize, d: usize, rot: f64) -> Self;
+    fn grunbaum_star_polygon_with_rot(n: usize, d: usize, rot: Real) -> Self;
 
     /// Builds the Grünbaumian star polygon `{n / d}` with unit circumradius. If
     /// `n` and `d` have a common factor, the result is a multiply-wound
@@ -587,11 +971,11 @@ pub trait ConcretePolytope: Polytope {
         use gcd::Gcd;
 
         let gcd = n.gcd(d);
-        let angle = f64::TAU / f64::usize(n);
+        let angle = Real::TAU / Real::usize(n);
 
         Self::compound(
             (0..gcd).into_iter().map(|k| {
-                Self::grunbaum_star_polygon_with_rot(n / gcd, d / gcd, f64::usize(k) * angle)
+                Self::grunbaum_star_polygon_with_rot(n / gcd, d / gcd, Real::usize(k) * angle)
             }),
         )
     }
@@ -602,14 +986,14 @@ pub trait ConcretePolytope: Polytope {
     /// # Panics
     /// Will panic if either `n < 2` or if `d < 1`, in which case there's
     /// nothing sensible to do.
-    fn star_polygon_with_edge(n: usize, d: usize, a: f64) -> Self {
+    fn star_polygon_with_edge(n: usize, d: usize, a: Real) -> Self {
         let mut p = Self::star_polygon(n, d);
-        p.scale(a / 2.0 / (f64::PI * d as f64 / n as f64).fsin());
+        p.scale(a / 2.0 / (Real::PI * d as Real / n as Real).fsin());
         p
     }
 
     /// Scales a polytope by a given factor.
-    fn scale(&mut self, k: f64) {
+    fn scale(&mut self, k: Real) {
         for v in self.vertices_mut() {
             *v *= k;
         }
@@ -623,14 +1007,60 @@ pub trait ConcretePolytope: Polytope {
     }
 
     /// Recenters a polytope so that a certain point is at the origin.
-    fn recenter_with(&mut self, p: &Point<f64>) {
+    fn recenter_with(&mut self, p: &Point<Real>) {
         for v in self.vertices_mut() {
             *v -= p;
         }
     }
 
+    /// Translates a polytope by a given vector.
+    fn translate(&mut self, v: &Vector<Real>) {
+        for p in self.vertices_mut() {
+            *p += v;
+        }
+    }
+
+    /// Scales a polytope so that its mean edge length is 1. Does nothing if
+    /// the polytope has no edges, or if all of them are degenerate.
+    fn normalize_edge_length(&mut self) {
+        let edge_count = self.edge_count();
+        if edge_count == 0 {
+            return;
+        }
+
+        let mean = self.edge_lengths().into_iter().sum::<Real>() / edge_count as Real;
+        if mean > Real::EPS {
+            self.scale(1.0 / mean);
+        }
+    }
+
+    /// Recenters a polytope so that its gravicenter is at the origin, and
+    /// scales it so that its farthest vertex is at distance 1. For polytopes
+    /// whose circumcenter is their gravicenter, this makes the circumradius 1.
+    fn normalize(&mut self) {
+        self.recenter();
+
+        let radius = self
+            .vertices()
+            .iter()
+            .map(|v| v.norm())
+            .fold(0.0, Real::max);
+        if radius > Real::EPS {
+            self.scale(1.0 / radius);
+        }
+    }
+
+    /// Returns the polytope after [normalizing](Self::normalize) it.
+    fn normalized(mut self) -> Self {
+        self.normalize();
+        self
+    }
+
     /// Applies a linear transformation to all vertices of a polytope.
-    fn apply(mut self, m: &Matrix<f64>) -> Self {
+    ///
+    /// # Panics
+    /// Panics if the matrix doesn't match the dimension of the vertices.
+    fn apply(mut self, m: &Matrix<Real>) -> Self {
         for v in self.vertices_mut() {
             *v = m * v as &_;
         }
@@ -639,11 +1069,39 @@ pub trait ConcretePolytope: Polytope {
     }
 
     /// Returns an arbitrary truncate of a polytope.
-    fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<f64>) -> Self;
+    fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<Real>) -> Self;
+
+    /// Cuts off every vertex of a polytope, so that every edge is cut at a
+    /// fraction `depth` of its length from either end. A depth of 1/2 gives
+    /// the rectification. Every vertex is replaced by a vertex figure, and
+    /// every other element by its truncate.
+    ///
+    /// # Panics
+    /// This method will panic if the depth isn't between 0 and 1/2.
+    fn truncate(&self, depth: Real) -> Self {
+        assert!(
+            (0.0..=0.5).contains(&depth),
+            "Truncation depth must be between 0 and 1/2."
+        );
+
+        if depth < Real::EPS {
+            return self.clone();
+        }
+
+        let mut poly = self.clone();
+        poly.element_sort();
+
+        // Every new vertex lies between a vertex and the midpoint of an edge.
+        if depth > 0.5 - Real::EPS {
+            poly.truncate_with(vec![1], vec![0.0, 1.0])
+        } else {
+            poly.truncate_with(vec![0, 1], vec![1.0 - 2.0 * depth, 2.0 * depth])
+        }
+    }
 
     /// Calculates the circumsphere of a polytope. Returns `None` if the
     /// polytope isn't circumscribable.
-    fn circumsphere(&self) -> Option<Hypersphere<f64>> {
+    fn circumsphere(&self) -> Option<Hypersphere<Real>> {
         let mut vertices = self.vertices().iter();
 
         let first_vertex = vertices.next()?.clone();
@@ -654,7 +1112,7 @@ pub trait ConcretePolytope: Polytope {
             // If the new vertex does not lie on the hyperplane of the others:
             if let Some(basis_vector) = subspace.add(vertex) {
                 // Calculates the new circumcenter.
-                let distance: f64 = ((&center - vertex).norm_squared()
+                let distance: Real = ((&center - vertex).norm_squared()
                     - (&center - &first_vertex).norm_squared())
                     / (2.0 * (vertex - &first_vertex).dot(basis_vector));
 
@@ -665,7 +1123,7 @@ pub trait ConcretePolytope: Polytope {
             else if abs_diff_ne!(
                 (&center - &first_vertex).norm(),
                 (&center - vertex).norm(),
-                epsilon = f64::EPS
+                epsilon = Real::EPS
             ) {
                 return None;
             }
@@ -679,15 +1137,15 @@ pub trait ConcretePolytope: Polytope {
 
     /// Calculates the gravicenter of a polytope, or returns `None` in the case
     /// of the nullitope.
-    fn gravicenter(&self) -> Option<Point<f64>> {
+    fn gravicenter(&self) -> Option<Point<Real>> {
         (!self.is_nullitope())
-            .then(|| self.vertices().iter().sum::<Point<f64>>() / (self.vertex_count()) as f64)
+            .then(|| self.vertices().iter().sum::<Point<Real>>() / (self.vertex_count()) as Real)
     }
 
     /// Gets the least and greatest distance of a vertex of the polytope,
     /// measuring from a specified direction, or returns `None` in the case of
     /// the nullitope.
-    fn minmax(&self, direction: Vector<f64>) -> Option<(f64, f64)> {
+    fn minmax(&self, direction: Vector<Real>) -> Option<(Real, Real)> {
         use itertools::{Itertools, MinMaxResult::*};
 
         let hyperplane = Hyperplane::new(direction, 0.0);
@@ -709,11 +1167,78 @@ pub trait ConcretePolytope: Polytope {
         }
     }
 
+    /// Returns the hyperplane of a facet, together with its unit normal
+    /// pointing away from the gravicenter of the polytope. Returns `None` if
+    /// the polytope isn't full-dimensional or the facet doesn't exist.
+    fn facet_hyperplane(&self, idx: usize) -> Option<(Subspace<Real>, Vector<Real>)> {
+        let rank = self.rank();
+        let center = self.gravicenter()?;
+        if rank < 2 || center.len() + 1 != rank {
+            return None;
+        }
+
+        let subspace = Subspace::from_points(self.element_vertices_ref(rank - 1, idx)?.into_iter());
+        let normal = -subspace.normal(&center)?;
+        Some((subspace, normal))
+    }
+
+    /// Returns the index of the facet whose outward normal points the most
+    /// towards a given direction, or `None` if the polytope isn't
+    /// full-dimensional.
+    fn facet_towards(&self, direction: &Vector<Real>) -> Option<usize> {
+        (0..self.facet_count())
+            .filter_map(|idx| {
+                let (_, normal) = self.facet_hyperplane(idx)?;
+                Some((idx, ordered_float::OrderedFloat(normal.dot(direction))))
+            })
+            .max_by_key(|&(_, dot)| dot)
+            .map(|(idx, _)| idx)
+    }
+
+    /// Projects the vertices of a polytope into the hyperplane of a facet,
+    /// from a point at a given distance outside of it. This gives the vertices
+    /// of a [Schlegel diagram](https://en.wikipedia.org/wiki/Schlegel_diagram),
+    /// in coordinates one dimension lower.
+    ///
+    /// Returns `None` if the polytope isn't full-dimensional, if the facet
+    /// doesn't exist, or if some vertex lies at the same height as the point
+    /// we project from.
+    fn schlegel_vertices(&self, facet: usize, distance: Real) -> Option<Vec<Point<Real>>> {
+        self.schlegel_project(facet, distance, self.vertices())
+    }
+
+    /// Projects a set of points as in [`Self::schlegel_vertices`].
+    fn schlegel_project<'a, I: IntoIterator<Item = &'a Point<Real>>>(
+        &self,
+        facet: usize,
+        distance: Real,
+        points: I,
+    ) -> Option<Vec<Point<Real>>> {
+        let (mut subspace, normal) = self.facet_hyperplane(facet)?;
+
+        // We center the diagram at the foot of the gravicenter.
+        subspace.offset = subspace.project(&self.gravicenter()?);
+        let viewpoint = &subspace.offset + &normal * distance;
+
+        points
+            .into_iter()
+            .map(|v| {
+                let height = (&viewpoint - v).dot(&normal);
+                if height < Real::EPS {
+                    return None;
+                }
+
+                let p = &viewpoint + (v - &viewpoint) * (distance / height);
+                Some(subspace.flatten(&p))
+            })
+            .collect()
+    }
+
     /// Returns a map from the elements in a polytope to a crude average of
     /// their vertices. Specifically, every vertex is mapped to itself, and
     /// every other element is mapped to the average of the images of its
     /// subelements.
-    fn avg_vertex_map(&self) -> ElementMap<Point<f64>> {
+    fn avg_vertex_map(&self) -> ElementMap<Point<Real>> {
         // Maps every element of the polytope to one of its vertices.
         let mut map = ElementMap::new();
         map.push(Vec::new());
@@ -733,8 +1258,8 @@ pub trait ConcretePolytope: Polytope {
                         el.subs
                             .iter()
                             .map(|&idx| &map[(r - 1, idx)])
-                            .sum::<Point<f64>>()
-                            / f64::usize(el.subs.len())
+                            .sum::<Point<Real>>()
+                            / Real::usize(el.subs.len())
                     })
                     .collect(),
             );
@@ -744,16 +1269,23 @@ pub trait ConcretePolytope: Polytope {
     }
 
     /// Returns the length of a given edge.
-    fn edge_len(&self, idx: usize) -> Option<f64> {
+    fn edge_len(&self, idx: usize) -> Option<Real> {
         let edge = self.get_element(2, idx)?;
         Some((&self.vertices()[edge.subs[0]] - &self.vertices()[edge.subs[1]]).norm())
     }
 
+    /// Returns the lengths of all edges of the polytope, in order.
+    fn edge_lengths(&self) -> Vec<Real> {
+        (0..self.edge_count())
+            .map(|idx| self.edge_len(idx).unwrap())
+            .collect()
+    }
+
     /// Checks whether a polytope is equilateral to a fixed precision, and with
     /// a specified edge length.
-    fn is_equilateral_with(&self, len: f64) -> bool {
+    fn is_equilateral_with(&self, len: Real) -> bool {
         (0..self.edge_count())
-            .all(|idx| abs_diff_eq!(self.edge_len(idx).unwrap(), len, epsilon = f64::EPS))
+            .all(|idx| abs_diff_eq!(self.edge_len(idx).unwrap(), len, epsilon = Real::EPS))
     }
 
     /// Checks whether a polytope is equilateral to a fixed precision.
@@ -765,7 +1297,7 @@ pub trait ConcretePolytope: Polytope {
     ///
     /// # Todo
     /// Maybe make this work in the general case?
-    fn midradius(&self) -> f64 {
+    fn midradius(&self) -> Real {
         let edge_subs = &self[(2, 0)].subs;
         (&self.vertices()[edge_subs[0]] + &self.vertices()[edge_subs[1]]).norm() / 2.0
     }
@@ -774,28 +1306,55 @@ pub trait ConcretePolytope: Polytope {
     /// place, or does nothing in case any facets go through the reciprocation
     /// center. In case of failure, returns the index of the facet through the
     /// projection center.
-    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<f64>) -> Result<(), Self::DualError>;
+    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<Real>) -> Result<(), Self::DualError>;
 
     /// Returns the dual of a polytope with a given reciprocation sphere, or
     /// `None` if any facets pass through the reciprocation center.
-    fn try_dual_with(&self, sphere: &Hypersphere<f64>) -> Result<Self, Self::DualError> {
+    fn try_dual_with(&self, sphere: &Hypersphere<Real>) -> Result<Self, Self::DualError> {
         let mut clone = self.clone();
         clone.try_dual_mut_with(sphere).map(|_| clone)
     }
 
     /// Builds a pyramid with a specified apex.
-    fn pyramid_with(&self, apex: Point<f64>) -> Self;
+    fn pyramid_with(&self, apex: Point<Real>) -> Self;
 
     /// Builds a prism with a specified height.
-    fn prism_with(&self, height: f64) -> Self;
+    fn prism_with(&self, height: Real) -> Self;
 
     /// Builds a uniform prism from an {n/d} polygon.
     fn uniform_prism(n: usize, d: usize) -> Self {
         Self::star_polygon_with_edge(n, d, 1.0).prism()
     }
 
+    /// Builds a pyramid over an equilateral base whose lateral edges have the
+    /// same length as those of the base. The apex is placed over the
+    /// circumcenter of the base. Returns `None` if the base has no edges, isn't
+    /// equilateral or circumscribable, or is too large for such an apex to
+    /// exist.
+    fn equilateral_pyramid(&self) -> Option<Self> {
+        let len = self.edge_len(0)?;
+        if !self.is_equilateral_with(len) {
+            return None;
+        }
+
+        let sphere = self.circumsphere()?;
+        let squared_height = len * len - sphere.squared_radius;
+        if squared_height < Real::EPS {
+            return None;
+        }
+
+        // The base is placed at height 1/2.
+        let apex: Vec<_> = sphere
+            .center
+            .iter()
+            .copied()
+            .chain(iter::once(0.5 - squared_height.sqrt()))
+            .collect();
+        Some(self.pyramid_with(apex.into()))
+    }
+
     /// Builds a tegum with two specified apices.
-    fn tegum_with(&self, apex1: Point<f64>, apex2: Point<f64>) -> Self;
+    fn tegum_with(&self, apex1: Point<Real>, apex2: Point<Real>) -> Self;
 
     /// Builds an [antiprism](https://polytope.miraheze.org/wiki/Antiprism),
     /// using the specified sets of vertices for the base and the dual base.
@@ -803,7 +1362,7 @@ pub trait ConcretePolytope: Polytope {
     /// The vertices of the base should be specified in the same order as those
     /// of the original polytope. The vertices of the dual face should be
     /// specified in the same order as the facets of the original polytope.
-    fn antiprism_with_vertices<I: Iterator<Item = Point<f64>>, J: Iterator<Item = Point<f64>>>(
+    fn antiprism_with_vertices<I: Iterator<Item = Point<Real>>, J: Iterator<Item = Point<Real>>>(
         &self,
         vertices: I,
         dual_vertices: J,
@@ -812,11 +1371,17 @@ pub trait ConcretePolytope: Polytope {
     /// Builds an [antiprism](https://polytope.miraheze.org/wiki/Antiprism)
     /// based on a given polytope. Uses the specified [`Hypersphere`] to build
     /// the dual base, and separates the bases by the given height.
+    ///
+    /// # Panics
+    /// In debug builds, panics if the height isn't positive, since the bases
+    /// would then either coincide or swap places.
     fn try_antiprism_with(
         &self,
-        sphere: &Hypersphere<f64>,
-        height: f64,
+        sphere: &Hypersphere<Real>,
+        height: Real,
     ) -> Result<Self, Self::DualError> {
+        debug_assert!(height > 0.0, "antiprism height {} isn't positive", height);
+
         let half_height = height / 2.0;
         let vertices = self.vertices().iter().map(|v| v.push(-half_height));
         let dual = self.try_dual_with(sphere)?;
@@ -831,17 +1396,47 @@ pub trait ConcretePolytope: Polytope {
     /// # Panics
     /// Panics if any facets pass through the inversion center. If you want to
     /// handle this possibility, use [`Self::try_antiprism_with`] instead.
-    fn antiprism_with(&self, sphere: &Hypersphere<f64>, height: f64) -> Self {
+    fn antiprism_with(&self, sphere: &Hypersphere<Real>, height: Real) -> Self {
         self.try_antiprism_with(sphere, height).unwrap()
     }
 
-    /// Builds a uniform antiprism of unit edge length.
+    /// Builds a uniform antiprism of unit edge length. Both the edges of the
+    /// bases and the lacing edges between them have length 1. If `n` and `d`
+    /// have a common factor, the result is a compound.
+    ///
+    /// # Panics
+    /// Will panic if `n < 2`, if `d < 1`, or if `n / d` is at most 3/2. In the
+    /// last case, the bases would coincide or the lacing edges couldn't reach
+    /// unit length.
     fn uniform_antiprism(n: usize, d: usize) -> Self {
+        assert!(n >= 2);
+        assert!(d >= 1);
+        assert!(3 * d < 2 * n, "no uniform {}/{} antiprism exists", n, d);
+
+        use gcd::Gcd;
+
+        // Compounds are made out of rotated copies of a single antiprism.
+        let gcd = n.gcd(d);
+        if gcd > 1 {
+            let angle = Real::TAU / Real::usize(n);
+
+            return Self::compound((0..gcd).map(|k| {
+                let (sin, cos) = (Real::usize(k) * angle).fsin_cos();
+                let rotation = Matrix::from_row_slice(
+                    3,
+                    3,
+                    &[cos, -sin, 0.0, sin, cos, 0.0, 0.0, 0.0, 1.0],
+                );
+
+                Self::uniform_antiprism(n / gcd, d / gcd).apply(&rotation)
+            }));
+        }
+
         let polygon = Self::star_polygon(n, d);
 
         // Appropriately scaled antiprism.
         if n != 2 * d {
-            let angle = f64::PI * f64::usize(d) / f64::usize(n);
+            let angle = Real::PI * Real::usize(d) / Real::usize(n);
             let cos = angle.fcos();
             let height = ((cos - (2.0 * angle).fcos()) * 2.0).fsqrt();
 
@@ -849,13 +1444,13 @@ pub trait ConcretePolytope: Polytope {
                 &Hypersphere::with_squared_radius(Point::zeros(2), cos),
                 height,
             );
-            antiprism.scale(0.5 / (f64::PI * d as f64 / n as f64).fsin());
+            antiprism.scale(0.5 / (Real::PI * d as Real / n as Real).fsin());
 
             antiprism
         }
-        // Digon compounds are a special case.
+        // The digon antiprism is a special case.
         else {
-            let half_height = f64::HALF_SQRT_2;
+            let half_height = Real::HALF_SQRT_2;
             let vertices = polygon.vertices().iter().map(|v| v.push(-half_height));
             let dual_vertices = polygon
                 .vertices()
@@ -871,7 +1466,7 @@ pub trait ConcretePolytope: Polytope {
 
     /// Gets the references to the (geometric) vertices of an element on the
     /// polytope.
-    fn element_vertices_ref(&self, rank: usize, idx: usize) -> Option<Vec<&Point<f64>>> {
+    fn element_vertices_ref(&self, rank: usize, idx: usize) -> Option<Vec<&Point<Real>>> {
         Some(
             self.abs()
                 .element_vertices(rank, idx)?
@@ -886,20 +1481,20 @@ pub trait ConcretePolytope: Polytope {
     fn duopyramid_with(
         &self,
         other: &Self,
-        self_offset: &Point<f64>,
-        other_offset: &Point<f64>,
-        height: f64,
+        self_offset: &Point<Real>,
+        other_offset: &Point<Real>,
+        height: Real,
     ) -> Self;
 
     /// Generates a duopyramid from two given polytopes with a given offset.
-    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self;
+    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<Real>, q_offset: &Point<Real>) -> Self;
 
     /// Computes the volume of a polytope by adding up the contributions of all
     /// flags. Returns `None` if the volume is undefined.
     ///
     /// # Panics
     /// You must call [`Polytope::element_sort`] before calling this method.
-    fn volume(&self) -> Option<f64> {
+    fn volume(&self) -> Option<Real> {
         let rank = self.rank();
 
         // We leave the nullitope's volume undefined.
@@ -973,25 +1568,52 @@ pub trait ConcretePolytope: Polytope {
             }
         }
 
-        Some(volume / f64::u32(crate::factorial(rank - 1)))
+        Some(volume / Real::u32(crate::factorial(rank - 1)))
     }
 
     /// Computes the volume of a polytope by adding up the contributions of all
     /// flags. Returns `None` if the volume is undefined.
-    fn volume_mut(&mut self) -> Option<f64> {
+    fn volume_mut(&mut self) -> Option<Real> {
         self.element_sort();
         self.volume()
     }
 
+    /// Computes the surface area of a polytope, that is, the sum of the
+    /// volumes of its facets. Returns `None` if the volume of any facet is
+    /// undefined.
+    fn surface_area(&self) -> Option<Real> {
+        let rank = self.rank();
+        if rank < 2 {
+            return None;
+        }
+
+        let mut area = 0.0;
+        for idx in 0..self.facet_count() {
+            area += self.element(rank - 1, idx)?.volume_mut()?;
+        }
+
+        Some(area)
+    }
+
     /// Projects the vertices of the polytope into the lowest dimension possible.
     /// If the polytope's subspace is already of full rank, this is a no-op.
     fn flatten(&mut self);
 
     /// Flattens the vertices of a polytope into a specified subspace.
-    fn flatten_into(&mut self, subspace: &Subspace<f64>);
+    fn flatten_into(&mut self, subspace: &Subspace<Real>);
 
     /// Slices the polytope through a given plane.
-    fn cross_section(&self, slice: &Hyperplane<f64>) -> Self;
+    fn cross_section(&self, slice: &Hyperplane<Real>) -> Self;
+
+    /// Slices the polytope through a given hyperplane, and flattens the
+    /// section into the coordinates of the hyperplane, so that the projection
+    /// of the origin becomes the new origin. Returns `None` if the polytope is
+    /// a polygon or lower, or if the hyperplane doesn't intersect it.
+    ///
+    /// Vertices lying on the hyperplane become vertices of the section, where
+    /// all of the edges through them meet, and elements that only touch the
+    /// hyperplane are discarded. See [`Self::cross_section`].
+    fn flat_cross_section(&self, slice: &Hyperplane<Real>) -> Option<Self>;
 	
 	/// Checks if is fissary.
     fn is_fissary(&self) -> bool;
@@ -1011,7 +1633,7 @@ impl ConcretePolytope for Concrete {
     }
 
     /// Builds a dyad with a specified height.
-    fn dyad_with(height: f64) -> Self {
+    fn dyad_with(height: Real) -> Self {
         let half_height = height / 2.0;
 
         Self::new(
@@ -1022,17 +1644,17 @@ impl ConcretePolytope for Concrete {
 
     /// Builds the Grünbaumian star polygon `{n / d}` with unit circumradius,
     /// rotated by an angle.
-    fn grunbaum_star_polygon_with_rot(n: usize, d: usize, rot: f64) -> Self {
+    fn grunbaum_star_polygon_with_rot(n: usize, d: usize, rot: Real) -> Self {
         assert!(n >= 2);
         assert!(d >= 1);
 
-        let angle = f64::TAU * f64::usize(d) / f64::usize(n);
+        let angle = Real::TAU * Real::usize(d) / Real::usize(n);
 
         Self::new(
             (0..n)
                 .into_iter()
                 .map(|k| {
-                    let (sin, cos) = (f64::usize(k) * angle + rot).fsin_cos();
+                    let (sin, cos) = (Real::usize(k) * angle + rot).fsin_cos();
                     vec![sin, cos].into()
                 })
                 .collect(),
@@ -1047,7 +1669,7 @@ impl ConcretePolytope for Concrete {
     ///
     /// # Panics
     /// This method shouldn't panic. If it does, please file a bug.
-    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<f64>) -> Result<(), Self::DualError> {
+    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<Real>) -> Result<(), Self::DualError> {
         // If we're dealing with a nullitope, the dual is itself.
         let rank = self.rank();
         if rank == 0 {
@@ -1094,19 +1716,19 @@ impl ConcretePolytope for Concrete {
     }
 
     /// Builds a pyramid with a specified apex.
-    fn pyramid_with(&self, apex: Point<f64>) -> Self {
+    fn pyramid_with(&self, apex: Point<Real>) -> Self {
         let mut poly = self.pyramid();
         *poly.vertices.last_mut().unwrap() = apex;
         poly
     }
 
     /// Builds a prism with a specified height.
-    fn prism_with(&self, height: f64) -> Self {
+    fn prism_with(&self, height: Real) -> Self {
         self.duoprism(&Self::dyad_with(height))
     }
 
     /// Builds a tegum with two specified apices.
-    fn tegum_with(&self, apex1: Point<f64>, apex2: Point<f64>) -> Self {
+    fn tegum_with(&self, apex1: Point<Real>, apex2: Point<Real>) -> Self {
         let mut poly = self.tegum();
         let v = poly.vertices.len();
         poly.vertices[v - 1] = apex1;
@@ -1120,7 +1742,7 @@ impl ConcretePolytope for Concrete {
     /// The vertices of the base should be specified in the same order as those
     /// of the original polytope. The vertices of the dual face should be
     /// specified in the same order as the facets of the original polytope.
-    fn antiprism_with_vertices<I: Iterator<Item = Point<f64>>, J: Iterator<Item = Point<f64>>>(
+    fn antiprism_with_vertices<I: Iterator<Item = Point<Real>>, J: Iterator<Item = Point<Real>>>(
         &self,
         vertices: I,
         dual_vertices: J,
@@ -1147,9 +1769,9 @@ impl ConcretePolytope for Concrete {
     fn duopyramid_with(
         &self,
         other: &Self,
-        self_offset: &Point<f64>,
-        other_offset: &Point<f64>,
-        height: f64,
+        self_offset: &Point<Real>,
+        other_offset: &Point<Real>,
+        height: Real,
     ) -> Self {
         Self::new(
             duopyramid_vertices(
@@ -1166,7 +1788,7 @@ impl ConcretePolytope for Concrete {
 
     /// Builds a [duotegum](https://polytope.miraheze.org/wiki/Tegum_product)
     /// from two polytopes.
-    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self {
+    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<Real>, q_offset: &Point<Real>) -> Self {
         Self::new(
             duopyramid_vertices(&p.vertices, &q.vertices, p_offset, q_offset, 0.0, true),
             p.abs.duotegum(&q.abs),
@@ -1182,7 +1804,7 @@ impl ConcretePolytope for Concrete {
     }
 
     /// Flattens the vertices of a polytope into a specified subspace.
-    fn flatten_into(&mut self, subspace: &Subspace<f64>) {
+    fn flatten_into(&mut self, subspace: &Subspace<Real>) {
         if !subspace.is_full_rank() {
             for v in &mut self.vertices {
                 *v = subspace.flatten(v);
@@ -1190,139 +1812,23 @@ impl ConcretePolytope for Concrete {
         }
     }
 
-    /// Takes the cross-section of a polytope through a given hyperplane.
-    ///
-    /// # Todo
-    /// We should make this function take a general [`Subspace`] instead.
-    fn cross_section(&self, slice: &Hyperplane<f64>) -> Self {
+    fn flat_cross_section(&self, slice: &Hyperplane<Real>) -> Option<Self> {
         if self.rank() < 4 {
-            unreachable!();
-        }
-
-        let mut vertices = Vec::new();
-        let mut ranks = Vec::with_capacity(self.rank());
-
-        // We map all indices of k-elements in the original polytope to the
-        // indices of the new (k-1)-elements resulting from taking their
-        // intersections with the slicing hyperplane.
-        let mut hash_element = HashMap::new();
-
-        // Determines the vertices of the cross-section.
-        for (idx, edge) in self[2].iter().enumerate() {
-            let segment = Segment(&self.vertices[edge.subs[0]], &self.vertices[edge.subs[1]]);
-
-            // If we got ourselves a new vertex:
-            if let Some(p) = slice.intersect(segment) {
-                hash_element.insert(idx, vertices.len());
-                vertices.push(p);
-            }
-        }
-
-        let vertex_count = vertices.len();
-
-        // The slice does not intersect the polytope.
-        if vertex_count == 0 {
-            return Self::nullitope();
-        }
-
-        ranks.push(SubelementList::min());
-        ranks.push(SubelementList::vertices(vertex_count));
-
-        // Takes care of building everything else.
-        for r in 3..self.rank() {
-            let mut new_hash_element = HashMap::new();
-            let mut new_els = SubelementList::new();
-
-            for (idx, el) in self[r].iter().enumerate() {
-                let mut new_subs = Subelements::new();
-                for sub in &el.subs {
-                    if let Some(&v) = hash_element.get(sub) {
-                        new_subs.push(v);
-                    }
-                }
-
-                // If we got ourselves a new edge:
-                if !new_subs.is_empty() {
-                    new_hash_element.insert(idx, new_els.len());
-                    new_els.push(new_subs);
-                }
-            }
-
-            ranks.push(new_els);
-            hash_element = new_hash_element;
-        }
-
-        // Adds a maximal element manually.
-        ranks.push(SubelementList::max(ranks.last().unwrap().len()));
-
-        // Splits compounds of dyads.
-        let (first, last) = ranks.split_at_mut(3);
-
-        if let (Some(edges), Some(faces)) = (first.last_mut(), last.first_mut()) {
-            // Keeps track of the indices of our new edges.
-            let mut edge_num = edges.len();
-            let mut new_edges = SubelementList::new();
-
-            // The superelements of all edges.
-            let mut edge_sups = Vec::new();
-            for _ in 0..edge_num {
-                edge_sups.push(Superelements::new());
-            }
-
-            for (idx, face) in faces.iter().enumerate() {
-                for &sub in face {
-                    edge_sups[sub].push(idx);
-                }
-            }
-
-            for (edge_idx, subs) in edges.iter_mut().enumerate() {
-                debug_assert_eq!(
-                    subs.len() % 2,
-                    0,
-                    "A line should always intersect a polygon an even amount of times!"
-                );
-                let comps = subs.len() / 2;
-
-                if comps > 1 {
-                    // Sorts the component's vertices lexicographically.
-                    subs.sort_unstable_by_key(|&x| PointOrd::new(vertices[x].clone()));
-
-                    // Splits the edge, adds the new split edges as subelements
-                    // to the edge's superelements.
-                    for _ in 1..comps {
-                        let v0 = subs.pop().unwrap();
-                        let v1 = subs.pop().unwrap();
-                        new_edges.push(vec![v0, v1].into());
-
-                        for &sup in &edge_sups[edge_idx] {
-                            faces[sup].push(edge_num);
-                        }
-
-                        edge_num += 1;
-                    }
-                }
-            }
-
-            // Adds the new edges.
-            edges.append(&mut new_edges);
+            return None;
         }
 
-        // Builds the polytope.
-        // TODO: no need for ranks, just use the builder directly.
-        let mut builder = AbstractBuilder::new();
-        for subelements in ranks {
-            builder.push(subelements);
-        }
+        self.cross_section(slice).flatten_section(slice)
+    }
 
-        // Safety: TODO shit, this one's complicated... I'll come back to it.
-        unsafe {
-            let mut abs = builder.build();
-            abs.untangle_faces();
-            Self::new(vertices, abs)
-        }
+    /// Takes the cross-section of a polytope through a given hyperplane.
+    ///
+    /// # Todo
+    /// We should make this function take a general [`Subspace`] instead.
+    fn cross_section(&self, slice: &Hyperplane<Real>) -> Self {
+        self.cross_section_through(slice, 0..self.el_count(2))
     }
 
-    fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<f64>) -> Self {
+    fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<Real>) -> Self {
         if truncate_type.is_empty() {
             println!("Cannot truncate with no active nodes!");
             return self.clone()
@@ -1330,9 +1836,9 @@ impl ConcretePolytope for Concrete {
         let (abs, subflags) = self.abs().truncate_and_flags(truncate_type.clone());
         let element_vertices = self.avg_vertex_map();
 
-        let mut vertex_coords = Vec::<Point<f64>>::new();
+        let mut vertex_coords = Vec::<Point<Real>>::new();
         for subflag in subflags {
-            let mut vector = Point::<f64>::from_vec(vec![0.0; self.rank() - 1]);
+            let mut vector = Point::<Real>::from_vec(vec![0.0; self.rank() - 1]);
             for (r, i) in subflag.iter().enumerate() {
                 vector += element_vertices[truncate_type[r] + 1][*i].clone() * depth[truncate_type[r]];
             }
@@ -1421,13 +1927,22 @@ impl ConcretePolytope for Concrete {
 
 #[cfg(test)]
 mod tests {
-    use super::{Concrete, ConcretePolytope};
-    use crate::{float::Float, Polytope};
+    use std::collections::BTreeSet;
+
+    use super::{section::SectionCache, Concrete, ConcretePolytope};
+    use crate::{
+        abs::Ranked,
+        float::{Float, Real},
+        geometry::{Hyperplane, Hypersphere, Matrix, Point, PointOrd, Vector},
+        group::Group,
+        Polytope,
+    };
 
     use approx::abs_diff_eq;
+    use nalgebra::dvector;
 
     /// Tests that a polytope has an expected volume.
-    fn test_volume(mut poly: Concrete, volume: Option<f64>) {
+    fn test_volume(mut poly: Concrete, volume: Option<Real>) {
         poly.element_sort();
 
         if let Some(poly_volume) = poly.volume() {
@@ -1437,7 +1952,7 @@ mod tests {
             ));
 
             assert!(
-                abs_diff_eq!(poly_volume, volume, epsilon = f64::EPS),
+                abs_diff_eq!(poly_volume, volume, epsilon = Real::EPS),
                 "Expected volume {} for {}, found volume {}.",
                 volume,
                 "TBA: name",
@@ -1466,13 +1981,13 @@ mod tests {
         test_volume(Concrete::dyad(), Some(1.0));
     }
 
-    fn polygon_area(n: usize, d: usize) -> f64 {
-        let n = n as f64;
-        let d = d as f64;
-        n * (d * f64::TAU / n).sin() / 2.0
+    fn polygon_area(n: usize, d: usize) -> Real {
+        let n = n as Real;
+        let d = d as Real;
+        n * (d * Real::TAU / n).sin() / 2.0
     }
 
-    fn test_compound(mut p: Concrete, volume: Option<f64>) {
+    fn test_compound(mut p: Concrete, volume: Option<Real>) {
         p.comp_append(p.clone());
         test_volume(p, volume)
     }
@@ -1494,7 +2009,148 @@ mod tests {
         }
     }
 
-    fn polygons_areas() -> (Vec<Concrete>, Vec<f64>) {
+    #[test]
+    fn compound_polygon() {
+        use gcd::Gcd;
+
+        for (n, d) in [(6, 2), (8, 2), (9, 3), (10, 4), (12, 3)] {
+            let gcd = n.gcd(d);
+
+            // The star polygon is a compound of gcd(n, d) polygons on distinct
+            // vertices.
+            let mut compound = Concrete::star_polygon(n, d);
+            compound.element_sort();
+            assert!(compound.abs.is_compound());
+
+            let components = compound.defiss();
+            assert_eq!(components.len(), gcd);
+            for component in &components {
+                crate::test(component, [1, n / gcd, n / gcd, 1]);
+            }
+
+            for (i, v) in compound.vertices.iter().enumerate() {
+                for w in &compound.vertices[..i] {
+                    assert!((v - w).norm() > Real::EPS);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compound_dedup() {
+        // The cubes share a square.
+        let cube = Concrete::cube();
+        let mut moved = cube.clone();
+        moved.recenter_with(&Point::from_vec(vec![1.0, 0.0, 0.0]));
+        crate::test(
+            &Concrete::compound_dedup(vec![cube.clone(), moved]),
+            [1, 12, 24, 12, 1],
+        );
+
+        // The stella octangula has no shared vertices.
+        let tet = Concrete::tetrahedron();
+        let dual = tet.dual().unwrap();
+        crate::test(&Concrete::compound_dedup(vec![tet, dual]), [1, 8, 12, 8, 1]);
+
+        crate::test(&Concrete::compound_dedup(vec![cube.clone(), cube]), [1, 8, 24, 12, 1]);
+        crate::test(&Concrete::compound_dedup(Vec::new()), [1]);
+    }
+
+    #[test]
+    fn compound_off() {
+        use crate::file::{off::OffOptions, FromFile};
+
+        let antiprism = Concrete::uniform_antiprism(6, 2);
+        let off = antiprism.to_off(OffOptions::default()).unwrap();
+        crate::test(&Concrete::from_off(&off).unwrap(), [1, 12, 24, 16, 1]);
+    }
+
+    #[test]
+    fn duoprism_elements() {
+        // Duoprisms of higher rank factors are multiprisms.
+        let (cube, pentagon) = (Concrete::cube(), Concrete::polygon(5));
+        let cube_pentagon = cube.duoprism(&pentagon);
+        crate::test(&cube_pentagon, crate::duoprism_counts(&cube, &pentagon));
+
+        let dyad = Concrete::dyad();
+        let multiprism = Concrete::multiprism([&dyad, &dyad, &dyad, &pentagon]);
+        crate::test(&cube_pentagon, multiprism.el_count_iter());
+    }
+
+    #[test]
+    fn compound_antiprism() {
+        use gcd::Gcd;
+
+        for (n, d) in [(6, 2), (8, 2), (9, 3), (10, 4), (4, 2), (8, 4)] {
+            let gcd = n.gcd(d);
+            let mut antiprism = Concrete::uniform_antiprism(n, d);
+            crate::test(&antiprism, [1, 2 * n, 4 * n, 2 * n + 2 * gcd, 1]);
+            assert!(antiprism.is_equilateral_with(1.0));
+
+            // Every component is an antiprism on its own.
+            antiprism.element_sort();
+            let components = antiprism.defiss();
+            assert_eq!(components.len(), gcd);
+            let m = n / gcd;
+            for component in &components {
+                crate::test(component, [1, 2 * m, 4 * m, 2 * m + 2, 1]);
+            }
+        }
+    }
+
+    /// Checks that antiprisms whose bases would coincide aren't built.
+    #[test]
+    #[should_panic(expected = "no uniform 3/2 antiprism exists")]
+    fn degenerate_antiprism() {
+        Concrete::uniform_antiprism(3, 2);
+    }
+
+    /// Checks the antiprisms of polytopes other than the uniform polygonal
+    /// ones.
+    #[test]
+    fn antiprism_elements() {
+        // The tetrahedral antiprism is a 16-cell.
+        let antiprism = Concrete::simplex(4).try_antiprism().unwrap();
+        crate::test(&antiprism, [1, 8, 24, 32, 16, 1]);
+
+        let antiprism = Concrete::hypercube(4).try_antiprism().unwrap();
+        crate::test(&antiprism, [1, 14, 48, 62, 28, 1]);
+
+        // A polygonal antiprism, with bases and height chosen to make it
+        // uniform, is congruent to the one built directly.
+        for n in 3..8 {
+            let angle = Real::PI / Real::usize(n);
+            let squared_radius = 1.0 / angle.fcos();
+            let mut polygon = Concrete::polygon(n);
+            let radius = polygon.circumsphere().unwrap().radius();
+            polygon.scale(squared_radius.fsqrt() / radius);
+
+            let (side, lacing) = (angle.fsin(), (angle / 2.0).fsin());
+            let height = 2.0 * (squared_radius * (side * side - lacing * lacing)).fsqrt();
+            let mut antiprism = polygon
+                .try_antiprism_with(&Hypersphere::unit(2), height)
+                .unwrap();
+            let mut uniform = Concrete::uniform_antiprism(n, 1);
+            crate::test(&antiprism, uniform.el_count_iter().collect::<Vec<_>>());
+
+            let edge_len = antiprism.edge_len(0).unwrap();
+            antiprism.scale(1.0 / edge_len);
+            assert!(antiprism.is_equilateral_with(1.0));
+            uniform.recenter();
+            assert!(abs_diff_eq!(
+                antiprism.circumsphere().unwrap().radius(),
+                uniform.circumsphere().unwrap().radius(),
+                epsilon = Real::EPS
+            ));
+        }
+
+        // Antiprisms can't be built when a facet passes through the center.
+        let mut dyad = Concrete::dyad();
+        dyad.translate(&dvector![0.5]);
+        assert!(dyad.try_antiprism().is_err());
+    }
+
+    fn polygons_areas() -> (Vec<Concrete>, Vec<Real>) {
         let mut polygons = Vec::new();
         let mut areas = Vec::new();
         for n in 2..=5 {
@@ -1549,6 +2205,222 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tegum_elements() {
+        // The square bipyramid is an octahedron.
+        let octahedron = Concrete::polygon(4).tegum();
+        crate::test(&octahedron, crate::orthoplex_counts(4));
+        test_volume(octahedron, Some(2.0 / 3.0));
+
+        // The dual of a prism is the tegum of the dual.
+        for n in 3..=6 {
+            let polygon = Concrete::polygon(n);
+            let prism_dual = polygon.prism().dual().unwrap();
+            let dual_tegum = polygon.dual().unwrap().tegum();
+            crate::test(&prism_dual, dual_tegum.el_count_iter());
+        }
+
+        let cube_tegum = Concrete::cube().tegum();
+        crate::test(
+            &cube_tegum,
+            Concrete::multitegum([&Concrete::cube(), &Concrete::dyad()]).el_count_iter(),
+        );
+    }
+
+    #[test]
+    fn equilateral_pyramid() {
+        let tetrahedron = Concrete::polygon(3).equilateral_pyramid().unwrap();
+        crate::test(&tetrahedron, Concrete::simplex(4).el_count_iter());
+        assert!(tetrahedron.is_equilateral());
+
+        for n in 3..=5 {
+            let pyramid = Concrete::star_polygon_with_edge(n, 1, 1.0)
+                .equilateral_pyramid()
+                .unwrap();
+            crate::test(&pyramid, [1, n + 1, 2 * n, n + 1, 1]);
+            assert!(pyramid.is_equilateral_with(1.0));
+        }
+
+        // The hexagon's circumradius equals its edge length.
+        assert!(Concrete::star_polygon_with_edge(6, 1, 1.0)
+            .equilateral_pyramid()
+            .is_none());
+
+        let pentachoron = Concrete::simplex(4).equilateral_pyramid().unwrap();
+        crate::test(&pentachoron, [1, 5, 10, 10, 5, 1]);
+        assert!(pentachoron.is_equilateral());
+    }
+
+    #[test]
+    fn transforms() {
+        let mut cube = Concrete::cube();
+        let radius = cube.circumsphere().unwrap().radius();
+        cube.scale(2.0);
+        assert!(abs_diff_eq!(
+            cube.circumsphere().unwrap().radius(),
+            2.0 * radius,
+            epsilon = Real::EPS
+        ));
+
+        cube.translate(&Vector::from_element(3, 1.0));
+        cube.normalize_edge_length();
+        assert!(cube.is_equilateral_with(1.0));
+        cube.recenter();
+        assert!(abs_diff_eq!(
+            cube.gravicenter().unwrap().norm(),
+            0.0,
+            epsilon = Real::EPS
+        ));
+
+        // Every symmetry of the cube permutes its vertices.
+        let vertices = |poly: &Concrete| -> BTreeSet<_> {
+            poly.vertices.iter().cloned().map(PointOrd::new).collect()
+        };
+        let cube_vertices = vertices(&cube);
+
+        let mut gens = Vec::new();
+        for i in 0..3 {
+            let mut reflection = Matrix::identity(3, 3);
+            reflection[(i, i)] = -1.0;
+            gens.push(reflection);
+        }
+        gens.push(Matrix::identity(3, 3).select_rows(&[1, 2, 0]));
+        gens.push(Matrix::identity(3, 3).select_rows(&[1, 0, 2]));
+
+        let group = Group::from_generators(3, gens);
+        let mut order = 0;
+        for m in group {
+            assert_eq!(vertices(&cube.clone().apply(&m)), cube_vertices);
+            order += 1;
+        }
+        assert_eq!(order, 48);
+    }
+
+    /// Checks that normalization centers polytopes, gives them unit
+    /// circumradius, and is idempotent.
+    #[test]
+    fn normalize() {
+        let mut cube = Concrete::cube();
+        cube.scale(7.5);
+        cube.translate(&dvector![3.0, -1.0, 20.0]);
+
+        let normalized = cube.normalized();
+        assert!(normalized.gravicenter().unwrap().norm() < Real::EPS);
+        assert!(abs_diff_eq!(
+            normalized.circumsphere().unwrap().radius(),
+            1.0,
+            epsilon = Real::EPS
+        ));
+
+        let twice = normalized.clone().normalized();
+        for (v, w) in normalized.vertices.iter().zip(&twice.vertices) {
+            assert!((v - w).norm() < Real::EPS);
+        }
+
+        // Degenerate polytopes are just recentered.
+        let mut dyad = Concrete::dyad();
+        dyad.scale(0.0);
+        dyad.translate(&dvector![2.0]);
+        dyad.normalize();
+        assert_eq!(dyad.vertices, vec![dvector![0.0], dvector![0.0]]);
+        Concrete::point().normalize();
+        Concrete::nullitope().normalize();
+    }
+
+    #[test]
+    fn cross_section() {
+        // A generic section of a cube.
+        let cube = Concrete::cube();
+        let section = cube.cross_section(&Hyperplane::new(Vector::from_element(3, 1.0), 0.1));
+        crate::test(&section, [1, 6, 6, 1]);
+
+        // Sections through vertices and edges.
+        let section = cube.cross_section(&Hyperplane::new(dvector![1.0, 1.0, 0.0], 0.0));
+        crate::test(&section, [1, 4, 4, 1]);
+        assert!(section.edge_lengths().iter().all(|&len| len > Real::EPS));
+
+        let normal = Vector::from_element(3, 1.0 / Real::SQRT_3);
+        let section = cube.cross_section(&Hyperplane::new(normal, 0.5 / Real::SQRT_3));
+        crate::test(&section, [1, 3, 3, 1]);
+        assert!(section.is_equilateral_with(Real::SQRT_2));
+
+        let octahedron = Concrete::octahedron();
+        let section = octahedron.cross_section(&Hyperplane::new(dvector![0.0, 0.0, 1.0], 0.0));
+        crate::test(&section, [1, 4, 4, 1]);
+        assert!(section.is_equilateral_with(1.0));
+
+        let tesseract = Concrete::hypercube(5);
+        let section =
+            tesseract.cross_section(&Hyperplane::new(Vector::from_element(4, 1.0), 0.0));
+        crate::test(&section, [1, 12, 24, 14, 1]);
+
+        // A section that misses the polytope.
+        let section = cube.cross_section(&Hyperplane::new(dvector![1.0, 0.0, 0.0], 2.0));
+        assert!(section.is_nullitope());
+    }
+
+    #[test]
+    /// Takes sections through hyperplanes containing whole edges.
+    fn cross_section_on_edges() {
+        // The plane x = y contains two opposite edges of the cube, which become
+        // two sides of a rectangle.
+        let cube = Concrete::cube();
+        let normal = dvector![1.0, -1.0, 0.0] / Real::SQRT_2;
+        let section = cube.cross_section(&Hyperplane::new(normal.clone(), 0.0));
+        crate::test(&section, [1, 4, 4, 1]);
+
+        let mut lengths = section.edge_lengths();
+        lengths.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        let expected = [1.0, 1.0, Real::SQRT_2, Real::SQRT_2];
+        for (len, expected) in lengths.into_iter().zip(expected) {
+            assert!(abs_diff_eq!(len, expected, epsilon = Real::EPS));
+        }
+
+        // The cached sections agree.
+        let section = SectionCache::new(&cube, normal).cross_section(&cube, 0.0);
+        crate::test(&section, [1, 4, 4, 1]);
+
+        // The plane z = w contains four edges of the tesseract, and its section
+        // is a square prism.
+        let tesseract = Concrete::hypercube(5);
+        let normal = dvector![0.0, 0.0, 1.0, -1.0] / Real::SQRT_2;
+        let section = tesseract.cross_section(&Hyperplane::new(normal, 0.0));
+        crate::test(&section, [1, 8, 12, 6, 1]);
+    }
+
+    #[test]
+    fn flat_cross_section() {
+        // A slice of a tesseract parallel to a cell is a cube.
+        let tesseract = Concrete::hypercube(5);
+        let section = tesseract
+            .flat_cross_section(&Hyperplane::new(dvector![0.0, 0.0, 0.0, 1.0], 0.2))
+            .unwrap();
+        crate::test(&section, [1, 8, 12, 6, 1]);
+        assert_eq!(section.dim(), Some(3));
+        assert!(section.is_equilateral_with(1.0));
+
+        // The projection of the origin becomes the new origin.
+        let cube = Concrete::cube();
+        let section = cube
+            .flat_cross_section(&Hyperplane::new(dvector![0.0, 0.0, 1.0], 0.1))
+            .unwrap();
+        crate::test(&section, [1, 4, 4, 1]);
+        assert_eq!(section.dim(), Some(2));
+        assert!(abs_diff_eq!(
+            section.gravicenter().unwrap(),
+            Point::zeros(2),
+            epsilon = Real::EPS
+        ));
+
+        // Empty sections and sections of polygons.
+        let miss = Hyperplane::new(dvector![1.0, 0.0, 0.0], 2.0);
+        assert!(cube.flat_cross_section(&miss).is_none());
+        let polygon = Concrete::polygon(5);
+        assert!(polygon
+            .flat_cross_section(&Hyperplane::new(dvector![1.0, 0.0], 0.0))
+            .is_none());
+    }
+
     #[test]
     fn duocomb() {
         let (polygons, _) = polygons_areas();
@@ -1568,11 +2440,20 @@ mod tests {
         for n in 1..=6 {
             test_volume(
                 Concrete::simplex(n),
-                Some((n as f64 / (1 << (n - 1)) as f64).sqrt() / crate::factorial(n - 1) as f64),
+                Some((n as Real / (1 << (n - 1)) as Real).sqrt() / crate::factorial(n - 1) as Real),
             );
         }
     }
 
+    #[test]
+    fn simplex_edges() {
+        for n in 2..=8 {
+            for len in Concrete::simplex(n).edge_lengths() {
+                assert!(abs_diff_eq!(len, 1.0, epsilon = Real::EPS));
+            }
+        }
+    }
+
     #[test]
     fn hypercube() {
         for n in 1..=6 {
@@ -1580,13 +2461,220 @@ mod tests {
         }
     }
 
+    #[test]
+    fn hypercube_duals() {
+        for n in 2..=8 {
+            let hypercube = Concrete::hypercube(n);
+            for v in &hypercube.vertices {
+                assert!(v
+                    .iter()
+                    .all(|x| abs_diff_eq!(x.abs(), 0.5, epsilon = Real::EPS)));
+            }
+
+            // The dual is the orthoplex.
+            crate::test(&hypercube.dual().unwrap(), crate::orthoplex_counts(n));
+        }
+    }
+
     #[test]
     fn orthoplex() {
         for n in 1..=6 {
             test_volume(
                 Concrete::orthoplex(n),
-                Some(((1 << (n - 1)) as f64).sqrt() / crate::factorial(n - 1) as f64),
+                Some(((1 << (n - 1)) as Real).sqrt() / crate::factorial(n - 1) as Real),
             );
         }
     }
+
+    #[test]
+    fn orthoplex_vertices() {
+        for n in 2..=8 {
+            let orthoplex = Concrete::orthoplex(n);
+            let mut dual = Concrete::hypercube(n).dual().unwrap();
+
+            // The orthoplex is the dual of the hypercube, scaled so that its
+            // vertices are the unit vectors scaled by √2 / 2.
+            dual.scale(Real::SQRT_2 / 4.0);
+
+            let vertices = |poly: &Concrete| -> BTreeSet<_> {
+                poly.vertices.iter().cloned().map(PointOrd::new).collect()
+            };
+            assert_eq!(vertices(&orthoplex), vertices(&dual));
+
+            for v in &orthoplex.vertices {
+                let nonzero: Vec<_> = v.iter().filter(|x| x.abs() > Real::EPS).collect();
+                assert_eq!(nonzero.len(), 1);
+                assert!(abs_diff_eq!(
+                    nonzero[0].abs(),
+                    Real::SQRT_2 / 2.0,
+                    epsilon = Real::EPS
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn surface_area() {
+        for n in 2..=6 {
+            let area = Concrete::hypercube(n).surface_area().unwrap();
+            assert!(abs_diff_eq!(area, 2.0 * (n - 1) as Real, epsilon = Real::EPS));
+        }
+    }
+
+    #[test]
+    fn truncate() {
+        crate::test(&Concrete::cube().truncate(0.5), [1, 12, 24, 14, 1]);
+        crate::test(&Concrete::cube().truncate(0.2), [1, 24, 36, 14, 1]);
+        crate::test(&Concrete::tetrahedron().truncate(0.0), [1, 4, 6, 4, 1]);
+        crate::test(&Concrete::hypercube(5).truncate(0.5), [1, 32, 96, 88, 24, 1]);
+
+        // Truncating at a third of the edges gives a uniform polytope.
+        let trun_tet = Concrete::tetrahedron().truncate(1.0 / 3.0);
+        crate::test(&trun_tet, [1, 12, 18, 8, 1]);
+        let lengths = trun_tet.edge_lengths();
+        for len in &lengths {
+            assert!(abs_diff_eq!(*len, lengths[0], epsilon = Real::EPS));
+        }
+    }
+
+    #[test]
+    fn schlegel() {
+        let tesseract = Concrete::hypercube(5);
+        let direction = Vector::from_vec(vec![0.0, 0.0, 0.0, 1.0]);
+        let facet = tesseract.facet_towards(&direction).unwrap();
+
+        // Every vertex is projected within the facet's circumsphere.
+        let vertices = tesseract.schlegel_vertices(facet, 1.0).unwrap();
+        assert_eq!(vertices.len(), 16);
+        for v in vertices {
+            assert_eq!(v.len(), 3);
+            assert!(v.norm() < Real::SQRT_3 / 2.0 + Real::EPS);
+        }
+
+        // The point to project from can't be within the polytope's range.
+        assert!(tesseract.schlegel_vertices(facet, -0.5).is_none());
+    }
+
+    #[test]
+    fn flat_element() {
+        let cube = Concrete::cube();
+        for idx in 0..6 {
+            let face = cube.flat_element(3, idx).unwrap();
+            crate::test(&face, [1, 4, 4, 1]);
+            assert_eq!(face.dim(), Some(2));
+            assert!(face.is_equilateral_with(1.0));
+        }
+
+        assert!(cube.flat_element(3, 6).is_none());
+        assert!(cube.flat_element(6, 0).is_none());
+    }
+
+    #[test]
+    fn vertex_figure() {
+        let octahedron = Concrete::octahedron();
+        for idx in 0..6 {
+            let square = octahedron.vertex_figure(idx).unwrap();
+            crate::test(&square, [1, 4, 4, 1]);
+            assert_eq!(square.dim(), Some(2));
+            assert!(square.is_equilateral_with(0.5));
+        }
+
+        let tesseract = Concrete::hypercube(5);
+        crate::test(&tesseract.vertex_figure(0).unwrap(), [1, 4, 6, 4, 1]);
+        crate::test(&Concrete::polygon(5).vertex_figure(0).unwrap(), [1, 2, 1]);
+
+        assert!(octahedron.vertex_figure(6).is_none());
+        assert!(Concrete::dyad().vertex_figure(0).is_none());
+    }
+
+    #[test]
+    fn edge_lengths() {
+        let lengths = Concrete::cube().edge_lengths();
+        assert_eq!(lengths.len(), 12);
+        for len in lengths {
+            assert!(abs_diff_eq!(len, 1.0, epsilon = Real::EPS));
+        }
+    }
+
+    #[test]
+    fn platonic() {
+        let icosahedron = Concrete::icosahedron();
+        let dodecahedron = Concrete::dodecahedron();
+        crate::test(&icosahedron, [1, 12, 30, 20, 1]);
+        crate::test(&dodecahedron, [1, 20, 30, 12, 1]);
+
+        for poly in [&icosahedron, &dodecahedron] {
+            for len in poly.edge_lengths() {
+                assert!(abs_diff_eq!(len, 1.0, epsilon = Real::EPS));
+            }
+        }
+
+        // The two are dual to each other.
+        crate::test(&icosahedron.dual().unwrap(), [1, 20, 30, 12, 1]);
+        crate::test(&dodecahedron.dual().unwrap(), [1, 12, 30, 20, 1]);
+    }
+
+    #[test]
+    fn cube_dual() {
+        let dual = Concrete::cube().dual().unwrap();
+        crate::test(&dual, [1, 6, 12, 8, 1]);
+    }
+
+    #[test]
+    fn double_dual() {
+        let cube = Concrete::cube();
+        let sphere = Hypersphere::unit(3);
+        let dual = cube.dual_with(&sphere).unwrap();
+        let cube2 = dual.dual_with(&sphere).unwrap();
+
+        assert_eq!(cube.vertices.len(), cube2.vertices.len());
+        for (v, w) in cube.vertices.iter().zip(&cube2.vertices) {
+            assert!(abs_diff_eq!((v - w).norm(), 0.0, epsilon = Real::EPS));
+        }
+    }
+
+    #[test]
+    fn dual_through_center() {
+        // Moves a vertex of the cube to the origin.
+        let mut cube = Concrete::cube();
+        let vertex = cube.vertices[0].clone();
+        cube.recenter_with(&vertex);
+        assert!(cube.dual().is_none());
+    }
+
+    #[test]
+    fn dual_failure_unchanged() {
+        let mut cube = Concrete::cube();
+        let vertex = cube.vertices[0].clone();
+        cube.recenter_with(&vertex);
+
+        let original = cube.clone();
+        assert!(cube.try_dual_mut_with(&Hypersphere::unit(3)).is_err());
+        assert_eq!(cube.vertices, original.vertices);
+        crate::test(&cube, [1, 8, 12, 6, 1]);
+    }
+
+    #[test]
+    fn reflected_dual() {
+        let mut cube = Concrete::cube();
+        cube.recenter_with(&Point::from_element(3, 0.1));
+
+        // A negative squared radius reflects the dual through the center.
+        let center = Point::from_element(3, 0.2);
+        let dual = cube
+            .dual_with(&Hypersphere::with_squared_radius(center.clone(), 1.0))
+            .unwrap();
+        let reflected = cube
+            .dual_with(&Hypersphere::with_squared_radius(center.clone(), -1.0))
+            .unwrap();
+
+        crate::test(&reflected, [1, 6, 12, 8, 1]);
+        for (v, w) in dual.vertices.iter().zip(&reflected.vertices) {
+            assert!(abs_diff_eq!(
+                (v + w - 2.0 * &center).norm(),
+                0.0,
+                epsilon = Real::EPS
+            ));
+        }
+    }
 }
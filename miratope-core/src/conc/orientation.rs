@@ -0,0 +1,310 @@
+//! Contains the code that orients the faces of a polyhedron consistently.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    abs::Ranked,
+    conc::{
+        cycle::{Cycle, CycleList},
+        Concrete, ConcretePolytope,
+    },
+    float::Real,
+    geometry::Point,
+};
+
+use vec_like::*;
+
+/// Returns the pairs of consecutive vertices in a cycle, including the pair
+/// that closes it.
+fn cycle_edges(cycle: &Cycle) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let len = cycle.len();
+    (0..len).map(move |i| (cycle[i], cycle[(i + 1) % len]))
+}
+
+/// Returns the sum of the cross products of consecutive vertices of a cycle of
+/// 3D points. This is twice the area of the polygon times its unit normal.
+pub fn newell_normal(cycle: &Cycle, vertices: &[Point<Real>]) -> [Real; 3] {
+    let mut normal = [0.0; 3];
+
+    for (v, w) in cycle_edges(cycle) {
+        let (p, q) = (&vertices[v], &vertices[w]);
+        normal[0] += p[1] * q[2] - p[2] * q[1];
+        normal[1] += p[2] * q[0] - p[0] * q[2];
+        normal[2] += p[0] * q[1] - p[1] * q[0];
+    }
+
+    normal
+}
+
+impl Concrete {
+    /// Returns the faces of a polyhedron as cycles of vertices, oriented so
+    /// that every edge is traversed in opposite directions by the two faces
+    /// that contain it. For a polyhedron in 3D space, each component is
+    /// oriented so that the normals of its faces point outwards. Otherwise,
+    /// the first face of each component keeps the orientation given by the
+    /// order of its edges, if any.
+    ///
+    /// Returns `None` if the polytope isn't a polyhedron, if any face is a
+    /// compound or any edge isn't shared by exactly two faces, or if the
+    /// polyhedron is non-orientable.
+    pub fn oriented_faces(&self) -> Option<Vec<Cycle>> {
+        if self.rank() != 4 {
+            return None;
+        }
+
+        // Gets each face as a single cycle.
+        let edges = &self[2];
+        let mut faces = Vec::with_capacity(self.el_count(3));
+        for face in &self[3] {
+            let face_edges: Vec<_> = face.subs.iter().map(|&e| &edges[e].subs).collect();
+            let mut cycles = CycleList::from_face_edges(&face_edges);
+            if cycles.len() != 1 {
+                return None;
+            }
+            faces.push(cycles.pop().unwrap());
+        }
+
+        // Maps every edge to the faces that traverse it, together with whether
+        // they do so from the smaller vertex index to the larger one.
+        let mut edge_faces: HashMap<_, Vec<(usize, bool)>> = HashMap::new();
+        for (idx, face) in faces.iter().enumerate() {
+            for (v, w) in cycle_edges(face) {
+                edge_faces
+                    .entry((v.min(w), v.max(w)))
+                    .or_default()
+                    .push((idx, v < w));
+            }
+        }
+        if edge_faces.values().any(|faces| faces.len() != 2) {
+            return None;
+        }
+
+        // Whether each face must be reversed, and the component it belongs to.
+        let mut reversed: Vec<Option<bool>> = vec![None; faces.len()];
+        let mut components = Vec::new();
+
+        // Propagates the orientation of a face to its neighbors.
+        for start in 0..faces.len() {
+            if reversed[start].is_some() {
+                continue;
+            }
+
+            reversed[start] = Some(false);
+            let mut component = vec![start];
+            let mut queue = VecDeque::from(vec![start]);
+
+            while let Some(idx) = queue.pop_front() {
+                let flip = reversed[idx].unwrap();
+
+                for (v, w) in cycle_edges(&faces[idx]) {
+                    let key = (v.min(w), v.max(w));
+
+                    // The direction in which this face now traverses the edge.
+                    let forward = (v < w) != flip;
+
+                    for &(other, other_forward) in &edge_faces[&key] {
+                        if other == idx {
+                            continue;
+                        }
+
+                        // The other face must traverse the edge backwards.
+                        let other_flip = other_forward == forward;
+                        match reversed[other] {
+                            Some(flip) if flip != other_flip => return None,
+                            Some(_) => {}
+                            None => {
+                                reversed[other] = Some(other_flip);
+                                component.push(other);
+                                queue.push_back(other);
+                            }
+                        }
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        for (face, flip) in faces.iter_mut().zip(reversed) {
+            if flip == Some(true) {
+                face.reverse();
+            }
+        }
+
+        // In 3D, the signed volume of each component tells us whether its
+        // normals point inwards.
+        if self.dim() == Some(3) {
+            for component in components {
+                let volume: Real = component
+                    .iter()
+                    .map(|&idx| {
+                        let face = &faces[idx];
+                        let normal = newell_normal(face, &self.vertices);
+                        let p = &self.vertices[face[0]];
+                        normal[0] * p[0] + normal[1] * p[1] + normal[2] * p[2]
+                    })
+                    .sum();
+
+                if volume < 0.0 {
+                    for idx in component {
+                        faces[idx].reverse();
+                    }
+                }
+            }
+        }
+
+        Some(faces)
+    }
+
+    /// Orients the faces of a polyhedron consistently, as in
+    /// [`Self::oriented_faces`], by listing the edges of each face in the
+    /// order in which its oriented cycle traverses them.
+    ///
+    /// Returns whether the faces could be oriented. If they couldn't, the
+    /// polytope is left untouched.
+    pub fn orient(&mut self) -> bool {
+        let faces = match self.oriented_faces() {
+            Some(faces) => faces,
+            None => return false,
+        };
+
+        // Maps every pair of vertices to the edge between them. No two edges
+        // share both vertices, or the faces couldn't have been oriented.
+        let edge_idx: HashMap<_, _> = self[2]
+            .iter()
+            .enumerate()
+            .map(|(idx, edge)| {
+                let (v, w) = (edge.subs[0], edge.subs[1]);
+                ((v.min(w), v.max(w)), idx)
+            })
+            .collect();
+
+        // Safety: changing the order of the indices in an element does not
+        // change whether the polytope is valid.
+        let ranks = unsafe { self.abs.ranks_mut() };
+        for (face, cycle) in ranks[3].iter_mut().zip(faces) {
+            face.subs = cycle_edges(&cycle)
+                .map(|(v, w)| edge_idx[&(v.min(w), v.max(w))])
+                .collect();
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::newell_normal;
+    use crate::{
+        abs::{AbstractBuilder, Ranked},
+        conc::{cycle::Cycle, Concrete, ConcretePolytope},
+        Polytope,
+    };
+
+    use nalgebra::dvector;
+    use vec_like::VecLike;
+
+    /// Checks that the faces of some polyhedra get outwards normals.
+    #[test]
+    fn outwards() {
+        let mut cube = Concrete::cube();
+        cube.translate(&dvector![2.0, 0.0, -1.0]);
+
+        for poly in [
+            cube,
+            Concrete::simplex(4),
+            Concrete::orthoplex(4),
+            Concrete::icosahedron(),
+            Concrete::uniform_antiprism(7, 1),
+        ] {
+            let faces = poly.oriented_faces().unwrap();
+            assert_eq!(faces.len(), poly.el_count(3));
+
+            let center = poly.gravicenter().unwrap();
+            for face in &faces {
+                let normal = newell_normal(face, &poly.vertices);
+                let offset = &poly.vertices[face[0]] - &center;
+                let dot = normal[0] * offset[0] + normal[1] * offset[1] + normal[2] * offset[2];
+                assert!(dot > 0.0);
+            }
+        }
+    }
+
+    /// Checks that orientability matches the one computed from the flags.
+    #[test]
+    fn orientable() {
+        let cube = Concrete::cube();
+        let mut petrial = cube.petrial().unwrap();
+        petrial.untangle_faces();
+
+        for mut poly in [cube, petrial, Concrete::dodecahedron()] {
+            assert_eq!(poly.orient(), poly.orientable_mut());
+        }
+    }
+
+    /// Checks that the hemicube, whose three square faces are the 4-cycles of
+    /// a complete graph on four vertices, can't be oriented.
+    #[test]
+    fn hemicube() {
+        let mut builder = AbstractBuilder::new();
+        builder.push_min();
+        builder.push_vertices(4);
+        builder.push(
+            vec![[0, 1], [1, 2], [2, 3], [0, 3], [1, 3], [0, 2]]
+                .into_iter()
+                .map(|edge| edge.to_vec().into())
+                .collect(),
+        );
+        builder.push(
+            vec![[0, 1, 2, 3], [0, 4, 2, 5], [1, 4, 3, 5]]
+                .into_iter()
+                .map(|face| face.to_vec().into())
+                .collect(),
+        );
+        builder.push_max();
+
+        // Safety: the hemicube is a valid polytope.
+        let abs = unsafe { builder.build() };
+        let vertices = vec![
+            dvector![1.0, 1.0, 1.0],
+            dvector![1.0, -1.0, -1.0],
+            dvector![-1.0, 1.0, -1.0],
+            dvector![-1.0, -1.0, 1.0],
+        ];
+        let mut hemicube = Concrete::new(vertices, abs);
+
+        assert!(hemicube.abs.is_valid_polytope());
+        let faces = hemicube[3].clone();
+        assert!(!hemicube.orient());
+        assert_eq!(hemicube[3], faces);
+        assert!(!hemicube.orientable_mut());
+    }
+
+    /// Checks that orienting a polyhedron stores the orientation in the order
+    /// of the edges of its faces, which is then read back.
+    #[test]
+    fn orient() {
+        let mut poly = Concrete::uniform_antiprism(5, 2);
+        let faces = poly.oriented_faces().unwrap();
+        assert!(poly.orient());
+        assert!(poly.abs.is_valid_polytope());
+
+        for (face, oriented) in poly[3].iter().zip(&faces) {
+            let edges: Vec<_> = face.subs.iter().map(|&e| &poly[(2, e)].subs).collect();
+            let cycle = Cycle::from_ordered_edges(&edges).unwrap();
+            assert_eq!(cycle.as_inner(), oriented.as_inner());
+        }
+
+        // Orienting twice doesn't change anything.
+        let oriented = poly[3].clone();
+        assert!(poly.orient());
+        assert_eq!(poly[3], oriented);
+    }
+
+    /// Checks that only polyhedra are oriented.
+    #[test]
+    fn rank() {
+        assert!(!Concrete::polygon(5).orient());
+        assert!(!Concrete::hypercube(5).orient());
+    }
+}
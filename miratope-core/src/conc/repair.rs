@@ -0,0 +1,533 @@
+//! Contains the code that cleans up a polytope after an operation that may have
+//! left coincident vertices or degenerate elements behind.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+use crate::{
+    abs::{AbstractBuilder, AbstractResult, Ranked, SubelementList, Subelements},
+    conc::{element_types::EL_NAMES, Concrete},
+    geometry::{PointOrd, Subspace},
+};
+
+use vec_like::*;
+
+/// Records the changes that [`Concrete::repair`] made to a polytope.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// The number of elements of each rank that were merged into another one.
+    pub merged: Vec<usize>,
+
+    /// The number of elements of each rank that were removed, either because
+    /// they were degenerate or because they weren't part of the polytope.
+    pub removed: Vec<usize>,
+}
+
+impl RepairReport {
+    /// Initializes a report with no changes for a polytope of a given rank.
+    fn new(rank: usize) -> Self {
+        Self {
+            merged: vec![0; rank + 1],
+            removed: vec![0; rank + 1],
+        }
+    }
+
+    /// Returns whether the repair didn't change anything.
+    pub fn is_empty(&self) -> bool {
+        self.merged
+            .iter()
+            .chain(&self.removed)
+            .all(|&count| count == 0)
+    }
+}
+
+impl Display for RepairReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "nothing to repair");
+        }
+
+        let mut changes = Vec::new();
+        for (action, counts) in [("merged", &self.merged), ("removed", &self.removed)] {
+            for (r, &count) in counts.iter().enumerate() {
+                if count != 0 {
+                    let name = match EL_NAMES.get(r) {
+                        Some(name) => name.to_lowercase(),
+                        None => format!("{}-elements", r - 1),
+                    };
+                    changes.push(format!("{} {} {}", action, count, name));
+                }
+            }
+        }
+
+        write!(f, "{}", changes.join(", "))
+    }
+}
+
+impl Concrete {
+    /// Marks every element that can be reached from the maximal element by
+    /// going down through subelements.
+    fn reachable_elements(&self) -> Vec<Vec<bool>> {
+        let rank = self.rank();
+        let mut reachable: Vec<_> = (0..=rank).map(|r| vec![false; self[r].len()]).collect();
+        reachable[rank][0] = true;
+
+        for r in (1..=rank).rev() {
+            for (idx, el) in self[r].iter().enumerate() {
+                if reachable[r][idx] {
+                    for &sub in &el.subs {
+                        reachable[r - 1][sub] = true;
+                    }
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Attempts to turn a polytope into a valid one, after an operation that
+    /// might have left garbage behind. This:
+    ///
+    /// - merges vertices that coincide up to a small epsilon,
+    /// - removes elements that became degenerate, like edges with a single
+    ///   vertex or faces whose vertices are collinear,
+    /// - merges elements with the same subelements,
+    /// - removes elements that aren't contained in the maximal element,
+    /// - merges adjacent coplanar faces, as in [`Self::merge_coplanar_faces`].
+    ///
+    /// Afterwards, the polytope is checked for validity, and a report of the
+    /// changes is returned. The polytope is modified even if the check fails.
+    pub fn repair(&mut self) -> AbstractResult<RepairReport> {
+        let rank = self.rank();
+        let mut report = RepairReport::new(rank);
+        if rank < 2 {
+            return self.abs.ranks().is_valid().map(|_| report);
+        }
+
+        let reachable = self.reachable_elements();
+
+        // Maps every reachable vertex to a new vertex, merging the ones that
+        // coincide.
+        let mut vertices = Vec::new();
+        let mut vertex_indices = BTreeMap::new();
+        let mut map: Vec<_> = self
+            .vertices
+            .iter()
+            .zip(&reachable[1])
+            .map(|(v, &reachable)| {
+                if !reachable {
+                    report.removed[1] += 1;
+                    return None;
+                }
+
+                let len = vertices.len();
+                let idx = *vertex_indices
+                    .entry(PointOrd::new(v.clone()))
+                    .or_insert_with(|| {
+                        vertices.push(v.clone());
+                        len
+                    });
+                if idx != len {
+                    report.merged[1] += 1;
+                }
+
+                Some(idx)
+            })
+            .collect();
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        // The vertices of the elements of the previous rank.
+        let mut el_vertices: Vec<Vec<usize>> = (0..vertices.len()).map(|v| vec![v]).collect();
+
+        for r in 2..rank {
+            let mut indices = HashMap::new();
+            let mut new_map = Vec::with_capacity(self[r].len());
+            let mut new_el_vertices = Vec::new();
+            let mut subelements = SubelementList::new();
+
+            for (idx, el) in self[r].iter().enumerate() {
+                if !reachable[r][idx] {
+                    report.removed[r] += 1;
+                    new_map.push(None);
+                    continue;
+                }
+
+                let mut subs: Vec<_> = el.subs.iter().filter_map(|&sub| map[sub]).collect();
+                subs.sort_unstable();
+                subs.dedup();
+
+                let mut el_verts: Vec<_> = subs
+                    .iter()
+                    .flat_map(|&sub| el_vertices[sub].iter().copied())
+                    .collect();
+                el_verts.sort_unstable();
+                el_verts.dedup();
+
+                // An element is degenerate if its vertices span a subspace of
+                // lower rank than expected.
+                if subs.len() < 2
+                    || Subspace::from_points_with(el_verts.iter().map(|&v| &vertices[v]), r - 2)
+                        .is_some()
+                {
+                    report.removed[r] += 1;
+                    new_map.push(None);
+                    continue;
+                }
+
+                let subs = Subelements::from(subs);
+                let len = indices.len();
+                let new_idx = *indices.entry(subs.clone()).or_insert_with(|| {
+                    subelements.push(subs);
+                    new_el_vertices.push(el_verts);
+                    len
+                });
+                if new_idx != len {
+                    report.merged[r] += 1;
+                }
+
+                new_map.push(Some(new_idx));
+            }
+
+            builder.push(subelements);
+            map = new_map;
+            el_vertices = new_el_vertices;
+        }
+
+        builder.push_max();
+
+        // Safety: we check the validity of the polytope immediately after.
+        *self = Self::new(vertices, unsafe { builder.build() });
+        self.merge_coplanar_faces_with(&mut report);
+        self.abs.ranks().is_valid().map(|_| report)
+    }
+
+    /// Merges every pair of faces that share an edge, lie on the same plane,
+    /// and are contained in the same elements, into a single face. The edges
+    /// between them are removed, together with any vertices left without
+    /// edges. Returns a report of the changes.
+    pub fn merge_coplanar_faces(&mut self) -> RepairReport {
+        let mut report = RepairReport::new(self.rank());
+        self.merge_coplanar_faces_with(&mut report);
+        report
+    }
+
+    /// Merges adjacent coplanar faces as in [`Self::merge_coplanar_faces`],
+    /// recording the changes into a report.
+    fn merge_coplanar_faces_with(&mut self, report: &mut RepairReport) {
+        let rank = self.rank();
+        if rank < 4 {
+            return;
+        }
+
+        // The vertices of each face.
+        let face_vertices: Vec<Vec<usize>> = self[3]
+            .iter()
+            .map(|face| {
+                let mut verts: Vec<_> = face
+                    .subs
+                    .iter()
+                    .flat_map(|&e| self[(2, e)].subs.iter().copied())
+                    .collect();
+                verts.sort_unstable();
+                verts.dedup();
+                verts
+            })
+            .collect();
+
+        // Joins the faces into classes of faces to be merged, each represented
+        // by one of its faces.
+        fn find(classes: &mut [usize], mut f: usize) -> usize {
+            while classes[f] != f {
+                classes[f] = classes[classes[f]];
+                f = classes[f];
+            }
+            f
+        }
+
+        let mut classes: Vec<_> = (0..self[3].len()).collect();
+        for edge in &self[2] {
+            if edge.sups.len() != 2 {
+                continue;
+            }
+
+            let (f, g) = (edge.sups[0], edge.sups[1]);
+            let (mut f_sups, mut g_sups) = (self[(3, f)].sups.clone(), self[(3, g)].sups.clone());
+            f_sups.sort_unstable();
+            g_sups.sort_unstable();
+            if f_sups != g_sups {
+                continue;
+            }
+
+            let subspace = Subspace::from_points(
+                face_vertices[f]
+                    .iter()
+                    .chain(&face_vertices[g])
+                    .map(|&v| &self.vertices[v]),
+            );
+            if subspace.rank() <= 2 {
+                let (f, g) = (find(&mut classes, f), find(&mut classes, g));
+                classes[f.max(g)] = f.min(g);
+            }
+        }
+
+        let classes: Vec<_> = (0..classes.len()).map(|f| find(&mut classes, f)).collect();
+        if classes.iter().enumerate().all(|(f, &class)| f == class) {
+            return;
+        }
+
+        // Removes the edges between two faces that are merged, and the
+        // vertices left without edges.
+        let mut edge_map = Vec::with_capacity(self[2].len());
+        let mut edges = SubelementList::new();
+        let mut used_vertices = vec![false; self.vertices.len()];
+        for edge in &self[2] {
+            if edge.sups.len() == 2 && classes[edge.sups[0]] == classes[edge.sups[1]] {
+                report.removed[2] += 1;
+                edge_map.push(None);
+            } else {
+                for &v in &edge.subs {
+                    used_vertices[v] = true;
+                }
+                edge_map.push(Some(edges.len()));
+                edges.push(edge.subs.clone());
+            }
+        }
+
+        let mut vertex_map = Vec::with_capacity(self.vertices.len());
+        let mut vertices = Vec::new();
+        for (v, &used) in self.vertices.iter().zip(&used_vertices) {
+            if used {
+                vertex_map.push(Some(vertices.len()));
+                vertices.push(v.clone());
+            } else {
+                report.removed[1] += 1;
+                vertex_map.push(None);
+            }
+        }
+
+        for edge in edges.iter_mut() {
+            for v in edge.iter_mut() {
+                *v = vertex_map[*v].unwrap();
+            }
+        }
+
+        // Builds each merged face out of the edges of its class.
+        let mut face_map = vec![0; classes.len()];
+        let mut faces: Vec<Vec<usize>> = Vec::new();
+        for (f, &class) in classes.iter().enumerate() {
+            if f == class {
+                face_map[f] = faces.len();
+                faces.push(Vec::new());
+            } else {
+                report.merged[3] += 1;
+                face_map[f] = face_map[class];
+            }
+
+            faces[face_map[f]].extend(self[(3, f)].subs.iter().filter_map(|&e| edge_map[e]));
+        }
+
+        let faces = faces
+            .into_iter()
+            .map(|mut subs| {
+                subs.sort_unstable();
+                subs.dedup();
+                Subelements::from(subs)
+            })
+            .collect();
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+        builder.push(edges);
+        builder.push(faces);
+
+        for r in 4..rank {
+            let mut subelements = SubelementList::new();
+            for el in &self[r] {
+                let mut subs: Vec<_> = if r == 4 {
+                    el.subs.iter().map(|&f| face_map[f]).collect()
+                } else {
+                    el.subs.iter().copied().collect()
+                };
+                subs.sort_unstable();
+                subs.dedup();
+                subelements.push(subs.into());
+            }
+            builder.push(subelements);
+        }
+
+        builder.push_max();
+
+        // Safety: merging two faces with the same superelements across an
+        // edge they alone contain keeps the polytope valid.
+        *self = Self::new(vertices, unsafe { builder.build() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        abs::{AbstractBuilder, Element, Ranked, Subelements, Superelements},
+        conc::Concrete,
+        Polytope,
+    };
+
+    use nalgebra::dvector;
+    use vec_like::*;
+
+    #[test]
+    fn cube() {
+        let mut cube = Concrete::cube();
+        let v = cube.vertices[0].clone();
+        let w = cube.vertices[1].clone();
+        cube.vertices.push(v);
+        cube.vertices.push(w);
+
+        // Safety: we deliberately corrupt the cube.
+        unsafe {
+            let ranks = cube.abs.ranks_mut();
+
+            // Makes an edge use a duplicate vertex.
+            ranks[1].push(Element::new(
+                Subelements::from(vec![0]),
+                Superelements::new(),
+            ));
+            let edge = ranks[1][0].sups[0];
+            for sub in ranks[2][edge].subs.iter_mut() {
+                if *sub == 0 {
+                    *sub = 8;
+                }
+            }
+
+            // Adds a degenerate edge into a face.
+            ranks[1].push(Element::new(
+                Subelements::from(vec![0]),
+                Superelements::new(),
+            ));
+            ranks[2].push(Element::new(
+                Subelements::from(vec![1, 9]),
+                Superelements::new(),
+            ));
+            let face = ranks[2][edge].sups[0];
+            let new_edge = ranks[2].len() - 1;
+            ranks[3][face].subs.push(new_edge);
+
+            // Adds a duplicate face.
+            let face = ranks[3][0].clone();
+            ranks[3].push(face);
+            let new_face = ranks[3].len() - 1;
+            ranks[4][0].subs.push(new_face);
+        }
+
+        assert!(cube.abs.ranks().is_valid().is_err());
+        let report = cube.repair().unwrap();
+        crate::test(&cube, [1, 8, 12, 6, 1]);
+        assert_eq!(report.merged, [0, 2, 0, 1, 0]);
+        assert_eq!(report.removed, [0, 0, 1, 0, 0]);
+    }
+
+    /// Duplicates every vertex and edge of a cube, and checks that repairing
+    /// it gives back the cube.
+    #[test]
+    fn duplicated_cube() {
+        let mut cube = Concrete::cube();
+        let vertex_count = cube.vertices.len();
+        let edge_count = cube.abs[2].len();
+        let duplicates = cube.vertices.clone();
+        cube.vertices.extend(duplicates);
+
+        // Safety: we deliberately corrupt the cube.
+        unsafe {
+            let ranks = cube.abs.ranks_mut();
+
+            for v in 0..vertex_count {
+                ranks[1].push(Element::new(
+                    Subelements::from(vec![0]),
+                    Superelements::new(),
+                ));
+                ranks[0][0].sups.push(vertex_count + v);
+            }
+
+            for e in 0..edge_count {
+                let subs: Subelements = ranks[2][e].subs.iter().map(|v| v + vertex_count).collect();
+                ranks[2].push(Element::new(subs, Superelements::new()));
+                for face in ranks[2][e].sups.clone() {
+                    ranks[3][face].subs.push(edge_count + e);
+                }
+            }
+        }
+
+        let report = cube.repair().unwrap();
+        crate::test(&cube, [1, 8, 12, 6, 1]);
+        assert_eq!(report.merged, [0, 8, 12, 0, 0]);
+        assert_eq!(report.to_string(), "merged 8 vertices, merged 12 edges");
+
+        assert!(cube.repair().unwrap().is_empty());
+    }
+
+    /// Builds a square pyramid whose base is split into two triangles, and
+    /// checks that these are merged back into a square.
+    #[test]
+    fn split_pyramid() {
+        let mut builder = AbstractBuilder::new();
+        builder.push_min();
+        builder.push_vertices(5);
+        builder.push(
+            vec![
+                vec![0, 1],
+                vec![1, 2],
+                vec![2, 3],
+                vec![0, 3],
+                vec![0, 4],
+                vec![1, 4],
+                vec![2, 4],
+                vec![3, 4],
+                vec![0, 2],
+            ]
+            .into_iter()
+            .map(Subelements::from)
+            .collect(),
+        );
+        builder.push(
+            vec![
+                vec![0, 4, 5],
+                vec![1, 5, 6],
+                vec![2, 6, 7],
+                vec![3, 4, 7],
+                vec![0, 1, 8],
+                vec![2, 3, 8],
+            ]
+            .into_iter()
+            .map(Subelements::from)
+            .collect(),
+        );
+        builder.push_max();
+
+        // Safety: the split pyramid is a valid polytope.
+        let abs = unsafe { builder.build() };
+        let vertices = vec![
+            dvector![1.0, 1.0, 0.0],
+            dvector![-1.0, 1.0, 0.0],
+            dvector![-1.0, -1.0, 0.0],
+            dvector![1.0, -1.0, 0.0],
+            dvector![0.0, 0.0, 1.0],
+        ];
+        let mut pyramid = Concrete::new(vertices, abs);
+        assert!(pyramid.abs.is_valid_polytope());
+
+        let report = pyramid.merge_coplanar_faces();
+        crate::test(&pyramid, [1, 5, 8, 5, 1]);
+        assert!(pyramid.abs.is_valid_polytope());
+        assert_eq!(report.to_string(), "merged 1 faces, removed 1 edges");
+        assert!(pyramid[3].iter().any(|face| face.subs.len() == 4));
+
+        // Nothing else can be merged.
+        assert!(pyramid.merge_coplanar_faces().is_empty());
+        assert!(Concrete::cube().merge_coplanar_faces().is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+//! Speeds up taking many cross-sections of a polytope through parallel
+//! hyperplanes, as when sweeping a slice through it.
+
+use super::Concrete;
+use crate::{
+    abs::Ranked,
+    float::{Float, Real},
+    geometry::{Hyperplane, Vector},
+};
+
+use vec_like::VecLike;
+
+/// Stores the extent of every edge of a polytope along a fixed direction, so
+/// that cross-sections perpendicular to it only need to check the edges that
+/// can actually intersect them.
+///
+/// The cache doesn't borrow the polytope, so that both can be stored side by
+/// side. It must only be used with the polytope it was built from.
+#[derive(Clone, Debug)]
+pub struct SectionCache {
+    /// The normal vector of the slicing hyperplanes.
+    normal: Vector<Real>,
+
+    /// The lowest and highest positions of each edge along the normal,
+    /// together with its index, sorted by the lowest position.
+    edges: Vec<(Real, Real, usize)>,
+}
+
+impl SectionCache {
+    /// Precomputes the extents of the edges of a polytope along a given unit
+    /// normal vector.
+    pub fn new(polytope: &Concrete, normal: Vector<Real>) -> Self {
+        let mut edges: Vec<_> = if polytope.rank() >= 3 {
+            polytope[2]
+                .iter()
+                .enumerate()
+                .map(|(idx, edge)| {
+                    let h0 = polytope.vertices[edge.subs[0]].dot(&normal);
+                    let h1 = polytope.vertices[edge.subs[1]].dot(&normal);
+                    (h0.min(h1), h0.max(h1), idx)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        edges.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        Self { normal, edges }
+    }
+
+    /// Returns the normal vector of the slicing hyperplanes.
+    pub fn normal(&self) -> &Vector<Real> {
+        &self.normal
+    }
+
+    /// Returns the indices of the edges that might intersect the hyperplane at
+    /// a given position, in increasing order. Endpoints within [`Float::EPS`]
+    /// of the hyperplane count as lying on it, just as in
+    /// [`Hyperplane::intersect`].
+    fn edges_at(&self, pos: Real) -> Vec<usize> {
+        let end = self
+            .edges
+            .partition_point(|&(min, _, _)| min < pos + Real::EPS);
+        let mut edges: Vec<_> = self.edges[..end]
+            .iter()
+            .filter(|&&(_, max, _)| max > pos - Real::EPS)
+            .map(|&(_, _, idx)| idx)
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Takes the cross-section of the polytope through the hyperplane at a
+    /// given position. This gives the same result as
+    /// [`ConcretePolytope::cross_section`](super::ConcretePolytope::cross_section).
+    ///
+    /// # Panics
+    /// Panics if the polytope is a polygon or lower.
+    pub fn cross_section(&self, polytope: &Concrete, pos: Real) -> Concrete {
+        let slice = Hyperplane::new(self.normal.clone(), pos);
+        polytope.cross_section_through(&slice, self.edges_at(pos))
+    }
+
+    /// Takes the cross-section of the polytope through the hyperplane at a
+    /// given position, and flattens it. This gives the same result as
+    /// [`ConcretePolytope::flat_cross_section`](super::ConcretePolytope::flat_cross_section).
+    pub fn flat_cross_section(&self, polytope: &Concrete, pos: Real) -> Option<Concrete> {
+        if polytope.rank() < 4 {
+            return None;
+        }
+
+        let slice = Hyperplane::new(self.normal.clone(), pos);
+        polytope
+            .cross_section_through(&slice, self.edges_at(pos))
+            .flatten_section(&slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SectionCache;
+    use crate::{
+        abs::Ranked,
+        conc::{Concrete, ConcretePolytope},
+        float::Real,
+        geometry::{Hyperplane, Vector},
+        Polytope,
+    };
+
+    use std::time::Instant;
+    use vec_like::VecLike;
+
+    /// Returns a generic unit vector, so that slices don't line up with the
+    /// elements of the test polytopes.
+    fn direction(dim: usize) -> Vector<Real> {
+        Vector::from_iterator(dim, (1..=dim).map(|i| i as Real)).normalize()
+    }
+
+    /// Checks that two polytopes have the same vertices and elements, in the
+    /// same order.
+    fn assert_same(p: &Concrete, q: &Concrete) {
+        assert_eq!(p.vertices, q.vertices);
+        assert_eq!(p.rank(), q.rank());
+        for r in 0..=p.rank() {
+            for (el, other) in p[r].iter().zip(q[r].iter()) {
+                assert_eq!(el.subs, other.subs);
+            }
+        }
+    }
+
+    /// Checks that cached cross-sections match uncached ones, including those
+    /// through vertices.
+    #[test]
+    fn matches() {
+        let mut polytopes = vec![
+            Concrete::hypercube(5),
+            Concrete::simplex(5),
+            Concrete::orthoplex(6),
+        ];
+        polytopes.push(polytopes[0].try_antiprism().unwrap());
+
+        for polytope in &polytopes {
+            let dim = polytope.dim_or();
+            let mut normals = vec![direction(dim)];
+            let mut axis = Vector::zeros(dim);
+            axis[dim - 1] = 1.0;
+            normals.push(axis);
+
+            for normal in normals {
+                let cache = SectionCache::new(polytope, normal.clone());
+                let (min, max) = polytope.minmax(normal.clone()).unwrap();
+
+                for i in 0..=20 {
+                    let pos = min + (max - min) * i as Real / 20.0;
+                    let slice = Hyperplane::new(normal.clone(), pos);
+
+                    assert_same(
+                        &cache.cross_section(polytope, pos),
+                        &polytope.cross_section(&slice),
+                    );
+
+                    match (
+                        cache.flat_cross_section(polytope, pos),
+                        polytope.flat_cross_section(&slice),
+                    ) {
+                        (Some(p), Some(q)) => assert_same(&p, &q),
+                        (None, None) => {}
+                        _ => panic!("flat cross-sections at {} differ", pos),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compares the cost of sweeping a slice through a polytope the size of
+    /// the 120-cell with and without the cache. Run with
+    /// `cargo test --release -- --ignored bench_sweep --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_sweep() {
+        // The 120-cell, with 600 vertices and 1200 edges.
+        let polytope = Concrete::parse_cd("x5o3o3o").unwrap().unwrap();
+        let normal = direction(polytope.dim_or());
+        let (min, max) = polytope.minmax(normal.clone()).unwrap();
+        const FRAMES: usize = 100;
+        let positions: Vec<_> = (1..FRAMES)
+            .map(|i| min + (max - min) * i as Real / FRAMES as Real)
+            .collect();
+
+        let now = Instant::now();
+        for &pos in &positions {
+            polytope.flat_cross_section(&Hyperplane::new(normal.clone(), pos));
+        }
+        let uncached = now.elapsed();
+
+        let now = Instant::now();
+        let cache = SectionCache::new(&polytope, normal);
+        for &pos in &positions {
+            cache.flat_cross_section(&polytope, pos);
+        }
+        let cached = now.elapsed();
+
+        println!(
+            "{} edges, {} frames: {:?} per frame uncached, {:?} per frame cached",
+            polytope.el_count(2),
+            FRAMES - 1,
+            uncached / (FRAMES - 1) as u32,
+            cached / (FRAMES - 1) as u32
+        );
+    }
+}
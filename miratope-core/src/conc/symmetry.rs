@@ -5,9 +5,9 @@ use std::{collections::{BTreeMap, HashSet}, vec, iter::FromIterator};
 use crate::{
     abs::{Ranked, flag::{FlagIter, Flag}},
     conc::Concrete,
-    float::Float,
+    float::{Float, Real},
     group::Group,
-    geometry::{Matrix, Point, PointOrd, Subspace},
+    geometry::{centroid, Matrix, Point, PointOrd, Subspace},
     Polytope,
 };
 
@@ -18,9 +18,9 @@ use super::ConcretePolytope;
 impl Flag {
     /// Outputs a sequence of vertices obtained from applying a fixed sequence of flag changes to a flag.
     /// Used for computing the elements of a symmetry group. 
-    fn vertex_sequence(&mut self, p: &Concrete) -> Matrix<f64> {
+    fn vertex_sequence(&mut self, p: &Concrete) -> Matrix<Real> {
         let rank = p.rank();
-        let mut basis = Matrix::<f64>::zeros(rank-1,rank-1);
+        let mut basis = Matrix::<Real>::zeros(rank-1,rank-1);
         let mut columns = basis.column_iter_mut();
         let vertex = &p.vertices[self[1]];
 
@@ -39,7 +39,7 @@ impl Flag {
 
 impl Concrete {
     /// Computes the symmetry group of a polytope, along with a list of vertex mappings.
-    pub fn get_symmetry_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<f64>>>, Vec<Vec<usize>>)> {
+    pub fn get_symmetry_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<Real>>>, Vec<Vec<usize>>)> {
         let mut fixed = self.clone(); // We'll relabel the facets if needed so the first facet isn't hemi.
 
         let mut facet_idx = 0;
@@ -48,7 +48,7 @@ impl Concrete {
                 let facet_space = Subspace::from_points(
                     self.abs.element_and_vertices(self.rank()-1, facet_idx).unwrap().0.iter().map(|x| &self.vertices[*x])
                 );
-                if facet_space.distance(&Point::zeros(self.dim().unwrap())) > f64::EPS {
+                if facet_space.distance(&Point::zeros(self.dim().unwrap())) > Real::EPS {
                     break;
                 }
                 facet_idx += 1;
@@ -79,7 +79,7 @@ impl Concrete {
         let flag_iter = FlagIter::new(&fixed.abs);
         let (types, types_map_back) = &fixed.element_types_common();
 
-        let mut vertices_pointord = Vec::<PointOrd<f64>>::new();
+        let mut vertices_pointord = Vec::<PointOrd<Real>>::new();
         for v in &self.vertices {
             vertices_pointord.push(PointOrd::new(v.clone()));
         }
@@ -101,7 +101,7 @@ impl Concrete {
         let base_basis = base_flag.clone().vertex_sequence(&fixed);
         let base_basis_inverse = base_basis.clone().try_inverse().unwrap();
 
-        let mut group = Vec::<Matrix<f64>>::new();
+        let mut group = Vec::<Matrix<Real>>::new();
 
         'a: for flag in flag_iter {
             if flag
@@ -152,7 +152,7 @@ impl Concrete {
     }
 
     /// Computes the rotation subgroup of a polytope, along with a list of vertex mappings.
-    pub fn get_rotation_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<f64>>>, Vec<Vec<usize>>)> {
+    pub fn get_rotation_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<Real>>>, Vec<Vec<usize>>)> {
         if let Some((full_group, full_vertex_map)) = self.get_symmetry_group() {
             let mut rotation_group = Vec::new();
             let mut vertex_map = Vec::new();
@@ -173,10 +173,143 @@ impl Concrete {
         }
     }
 
+    /// Computes the order of the symmetry group of a polytope about its
+    /// gravicenter, and whether the group contains the central inversion.
+    pub fn symmetry_order(&self) -> Option<(usize, bool)> {
+        let mut poly = self.clone();
+        poly.recenter();
+
+        let (group, _) = poly.get_symmetry_group()?;
+        let mut order = 0;
+        let mut central_inv = false;
+
+        for isometry in group {
+            order += 1;
+            let dim = isometry.nrows();
+            if (isometry + Matrix::identity(dim, dim)).amax() < Real::EPS {
+                central_inv = true;
+            }
+        }
+
+        Some((order, central_inv))
+    }
+
+    /// Computes the group of orthogonal matrices that permute the vertices of
+    /// a polytope about their centroid, up to [`Float::EPS`]. Unlike
+    /// [`Self::get_symmetry_group`], this only looks at the vertices, and
+    /// ignores how they're connected.
+    ///
+    /// A symmetry is determined by where it sends a basis of vertices, and it
+    /// must send each vertex to another with the same distances to the rest.
+    /// We use these classes of vertices to prune the candidate images of the
+    /// basis, which must also preserve the dot products between its vectors.
+    /// If the vertices don't span the whole space, the symmetries fix the
+    /// orthogonal complement of their span.
+    pub fn symmetry_group(&self) -> Group<vec::IntoIter<Matrix<Real>>> {
+        let dim = self.dim_or();
+        if self.vertices.is_empty() {
+            return Group::trivial(dim).cache();
+        }
+
+        let center = centroid(&self.vertices);
+        let vertices: Vec<_> = self.vertices.iter().map(|v| v - &center).collect();
+        let vertex_count = vertices.len();
+
+        // The sorted distances from each vertex to every other one.
+        let profiles: Vec<Vec<Real>> = vertices
+            .iter()
+            .map(|v| {
+                let mut dists: Vec<_> = vertices.iter().map(|w| (v - w).norm()).collect();
+                dists.sort_by(|a, b| a.partial_cmp(b).expect("Distance is NaN"));
+                dists.push(v.norm());
+                dists
+            })
+            .collect();
+        let same_class = |i: usize, j: usize| {
+            profiles[i]
+                .iter()
+                .zip(&profiles[j])
+                .all(|(a, b)| (a - b).fabs() < Real::EPS)
+        };
+
+        // A basis for the span of the vertices, made out of vertices.
+        let mut span = Subspace::new(Point::zeros(dim));
+        let basis: Vec<_> = (0..vertex_count)
+            .filter(|&i| span.add(&vertices[i]).is_some())
+            .collect();
+
+        // Every vertex lies on the centroid.
+        if basis.is_empty() {
+            return Group::trivial(dim).cache();
+        }
+
+        let columns = |idxs: &[usize]| {
+            Matrix::from_columns(&idxs.iter().map(|&i| vertices[i].clone()).collect::<Vec<_>>())
+        };
+        let basis_mat = columns(&basis);
+        let proj = (basis_mat.transpose() * &basis_mat)
+            .try_inverse()
+            .expect("Basis is linearly independent")
+            * basis_mat.transpose();
+        let complement = Matrix::identity(dim, dim) - &basis_mat * &proj;
+
+        let indices: BTreeMap<_, _> = vertices
+            .iter()
+            .map(|v| PointOrd::new(v.clone()))
+            .zip(0..)
+            .collect();
+
+        // Depth-first search through the possible images of the basis.
+        let mut group = Vec::new();
+        let mut images: Vec<usize> = Vec::new();
+        let mut next = 0;
+        loop {
+            // Finds the next candidate image for the current basis vertex.
+            let i = images.len();
+            let candidate = (next..vertex_count).find(|&j| {
+                same_class(basis[i], j)
+                    && images.iter().zip(&basis).all(|(&img, &b)| {
+                        (vertices[j].dot(&vertices[img]) - vertices[basis[i]].dot(&vertices[b]))
+                            .fabs()
+                            < Real::EPS
+                    })
+            });
+
+            match candidate {
+                Some(j) => {
+                    images.push(j);
+                    next = 0;
+
+                    if images.len() == basis.len() {
+                        let isometry = columns(&images) * &proj + &complement;
+
+                        if vertices
+                            .iter()
+                            .all(|v| indices.contains_key(&PointOrd::new(&isometry * v)))
+                        {
+                            group.push(isometry);
+                        }
+
+                        next = images.pop().unwrap() + 1;
+                    }
+                }
+
+                // Backtracks.
+                None => match images.pop() {
+                    Some(j) => next = j + 1,
+                    None => break,
+                },
+            }
+        }
+
+        // Safety: the symmetries of a set of points form a group.
+        unsafe { Group::new(dim, group.into_iter()) }
+    }
+
     /// Fills in the vertex map.
     /// A vertex map is an array of (group element, vertex index) with values being the index of the vertex after applying the transformation.
-    pub fn get_vertex_map(&mut self, group: Group<vec::IntoIter<Matrix<f64>>>) -> Vec<Vec<usize>> {
-        let mut vertices = Vec::<PointOrd<f64>>::new();
+    pub fn get_vertex_map(&mut self, group: Group<vec::IntoIter<Matrix<Real>>>) -> Vec<Vec<usize>> {
+        let mut vertices = Vec::<PointOrd<Real>>::new();
         for v in &self.vertices {
             vertices.push(PointOrd::new(v.clone()));
         }
@@ -204,19 +337,19 @@ impl Concrete {
 }
 
 /// A set of vertices.
-pub struct Vertices(pub Vec<Point<f64>>);
+pub struct Vertices(pub Vec<Point<Real>>);
 
 impl Vertices {
     /// Uses the provided symmetry group on the vertices, also outputs the new vertex map.
-    pub fn copy_by_symmetry(&self, group: Group<vec::IntoIter<Matrix<f64>>>) -> (Self, Vec<Vec<usize>>) {
-        let mut vertices = BTreeMap::<PointOrd<f64>, usize>::new();
+    pub fn copy_by_symmetry(&self, group: Group<vec::IntoIter<Matrix<Real>>>) -> (Self, Vec<Vec<usize>>) {
+        let mut vertices = BTreeMap::<PointOrd<Real>, usize>::new();
         let mut vertices_vec = Vec::new();
         let mut c = 0;
 
         for vertex in self.0.clone() {
             if vertices.get(&PointOrd::new(vertex.clone())).is_none() {
                 for isometry in group.clone() {
-                    let new_vertex = PointOrd::<f64>::new(isometry.clone() * vertex.clone());
+                    let new_vertex = PointOrd::<Real>::new(isometry.clone() * vertex.clone());
                     if vertices.get(&new_vertex).is_none() {
                         vertices.insert(new_vertex.clone(), c);
                         vertices_vec.push(new_vertex);
@@ -249,4 +382,49 @@ impl Vertices {
             vertex_map,
         )
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{conc::Concrete, Polytope};
+
+    /// Checks the symmetry groups of a few regular polytopes.
+    #[test]
+    fn regular() {
+        assert_eq!(Concrete::polygon(5).symmetry_order(), Some((10, false)));
+        assert_eq!(Concrete::simplex(4).symmetry_order(), Some((24, false)));
+        assert_eq!(Concrete::cube().symmetry_order(), Some((48, true)));
+        assert_eq!(Concrete::orthoplex(5).symmetry_order(), Some((384, true)));
+    }
+
+    /// Checks that the symmetry group doesn't depend on the position of the
+    /// polytope.
+    #[test]
+    fn translated() {
+        let mut cube = Concrete::cube();
+        for v in cube.vertices.iter_mut() {
+            v[0] += 1.0;
+        }
+
+        assert_eq!(cube.symmetry_order(), Some((48, true)));
+    }
+
+    /// Checks the symmetry groups of a few polytopes from their vertices.
+    #[test]
+    fn symmetry_group() {
+        assert_eq!(Concrete::cube().symmetry_group().order(), 48);
+        assert_eq!(Concrete::simplex(4).symmetry_group().order(), 24);
+        assert_eq!(Concrete::polygon(5).symmetry_group().order(), 10);
+    }
+
+    /// Checks that an irregular tetrahedron only has the trivial symmetry.
+    #[test]
+    fn irregular() {
+        let mut tet = Concrete::simplex(4);
+        tet.vertices[0] *= 1.3;
+        tet.vertices[1] *= 0.8;
+        tet.vertices[2][0] += 0.1;
+
+        assert_eq!(tet.symmetry_order(), Some((1, false)));
+    }
+}
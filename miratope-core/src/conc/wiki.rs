@@ -0,0 +1,285 @@
+//! Recognizes polytopes that have their own page on the
+//! [Polytope Wiki](https://polytope.miraheze.org).
+
+use crate::{
+    abs::Ranked,
+    conc::Concrete,
+    float::{Float, Real},
+    geometry::Subspace,
+    greek::{polygon_name, MAX_PREFIX},
+    Polytope,
+};
+
+use super::ConcretePolytope;
+use vec_like::*;
+
+/// Names of the wiki pages for the convex regular polytopes of rank 4 and
+/// higher, together with their rank, vertex count and facet count.
+const REGULAR_PAGES: [(usize, usize, usize, &str); 17] = [
+    (4, 4, 4, "Tetrahedron"),
+    (4, 8, 6, "Cube"),
+    (4, 6, 8, "Octahedron"),
+    (4, 20, 12, "Dodecahedron"),
+    (4, 12, 20, "Icosahedron"),
+    (5, 5, 5, "Pentachoron"),
+    (5, 16, 8, "Tesseract"),
+    (5, 8, 16, "Hexadecachoron"),
+    (5, 24, 24, "Icositetrachoron"),
+    (5, 600, 120, "Hecatonicosachoron"),
+    (5, 120, 600, "Hexacosichoron"),
+    (6, 6, 6, "Hexateron"),
+    (6, 32, 10, "Penteract"),
+    (6, 10, 32, "Pentacross"),
+    (7, 7, 7, "Heptapeton"),
+    (7, 64, 12, "Hexeract"),
+    (7, 12, 64, "Hexacross"),
+];
+
+/// Names of the wiki pages for the snub polyhedra, together with their vertex
+/// count and the number of faces with each number of sides.
+const SNUB_PAGES: [(usize, [(usize, usize); 2], &str); 2] = [
+    (24, [(3, 32), (4, 6)], "Snub cube"),
+    (60, [(3, 80), (5, 12)], "Snub dodecahedron"),
+];
+
+impl Concrete {
+    /// Returns whether every vertex of the polytope lies on the same side of
+    /// each of its facets, when measured from the gravicenter.
+    ///
+    /// The polytope is assumed to be full-rank in its ambient space.
+    fn facets_are_supporting(&self) -> bool {
+        let rank = self.rank();
+        let center = match self.gravicenter() {
+            Some(center) => center,
+            None => return true,
+        };
+
+        (0..self.facet_count()).all(|idx| {
+            let subspace = Subspace::from_points(
+                self.element_vertices_ref(rank - 1, idx)
+                    .unwrap()
+                    .into_iter(),
+            );
+
+            match subspace.normal(&center) {
+                Some(normal) => self
+                    .vertices
+                    .iter()
+                    .all(|v| (v - &subspace.offset).dot(&normal) > -Real::EPS),
+                None => false,
+            }
+        })
+    }
+
+    /// Returns a copy of the polytope, flattened into its own subspace,
+    /// recentered on its gravicenter, and with its elements sorted.
+    fn normalized(&self) -> Self {
+        let mut poly = self.clone();
+        poly.flatten();
+        if let Some(center) = poly.gravicenter() {
+            poly.recenter_with(&center);
+        }
+        poly.element_sort();
+        poly
+    }
+
+    /// Returns whether the polytope is a convex regular polytope. This is
+    /// checked by verifying that it's convex, equilateral, and that it has a
+    /// single element type in each rank.
+    pub fn is_convex_regular(&self) -> bool {
+        let rank = self.rank();
+        if rank <= 2 {
+            return true;
+        }
+
+        let poly = self.normalized();
+        poly.is_equilateral()
+            && poly
+                .element_types()
+                .iter()
+                .take(rank)
+                .skip(1)
+                .all(|types| types.len() == 1)
+            && poly.facets_are_supporting()
+    }
+
+    /// Returns the title of the wiki page for a convex regular polytope up to
+    /// rank 7, or `None` if the polytope isn't one.
+    fn regular_page(&self) -> Option<String> {
+        let page = match self.rank() {
+            0 => "Nullitope",
+            1 => "Point",
+            2 => "Dyad",
+            rank => {
+                if !self.is_convex_regular() {
+                    return None;
+                }
+
+                let vertex_count = self.vertex_count();
+                if rank == 3 {
+                    return (vertex_count <= MAX_PREFIX).then(|| polygon_name(vertex_count));
+                }
+
+                let facet_count = self.facet_count();
+                REGULAR_PAGES
+                    .iter()
+                    .find(|(r, v, f, _)| *r == rank && *v == vertex_count && *f == facet_count)?
+                    .3
+            }
+        };
+
+        Some(page.to_string())
+    }
+
+    /// Returns the title of the wiki page for a snub polyhedron, or `None` if
+    /// the polytope isn't one. These are recognized by their face counts, and
+    /// by being convex and equilateral.
+    fn snub_page(&self) -> Option<String> {
+        if self.rank() != 4 {
+            return None;
+        }
+
+        let vertex_count = self.vertex_count();
+        let page = SNUB_PAGES.iter().find(|(v, faces, _)| {
+            *v == vertex_count
+                && self.el_count(3) == faces.iter().map(|(_, count)| count).sum()
+                && faces.iter().all(|&(sides, count)| {
+                    self[3]
+                        .iter()
+                        .filter(|face| face.subs.len() == sides)
+                        .count()
+                        == count
+                })
+        })?;
+
+        let poly = self.normalized();
+        (poly.is_equilateral() && poly.facets_are_supporting()).then(|| page.2.to_string())
+    }
+
+    /// Returns the slug of the page on the
+    /// [Polytope Wiki](https://polytope.miraheze.org) for the polytope, like
+    /// `cube` or `snub_cube`, or `None` if it's not recognized. Currently, the
+    /// convex regular polytopes up to rank 7 and the snub polyhedra are
+    /// recognized.
+    pub fn wiki_slug(&self) -> Option<String> {
+        self.regular_page()
+            .or_else(|| self.snub_page())
+            .map(|page| page.to_lowercase().replace(' ', "_"))
+    }
+
+    /// Returns the name of the polytope if it's a convex regular polytope, or
+    /// `None` otherwise. Up to rank 7, this is the title of its wiki page.
+    /// Simplices, hypercubes and orthoplices of any higher rank are named
+    /// after their dimension, like `7-cube`.
+    pub fn regular_name(&self) -> Option<String> {
+        let rank = self.rank();
+        if rank <= 7 {
+            return self.regular_page();
+        }
+
+        if !self.is_convex_regular() {
+            return None;
+        }
+
+        let dim = rank - 1;
+        let vertex_count = self.vertex_count();
+        let facet_count = self.facet_count();
+        let power = 1usize.checked_shl(dim as u32);
+
+        let family = if vertex_count == rank && facet_count == rank {
+            "simplex"
+        } else if Some(vertex_count) == power && facet_count == 2 * dim {
+            "cube"
+        } else if vertex_count == 2 * dim && Some(facet_count) == power {
+            "orthoplex"
+        } else {
+            return None;
+        };
+
+        Some(format!("{}-{}", dim, family))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conc::{Concrete, ConcretePolytope},
+        float::Real,
+        geometry::Point,
+        group::Group,
+        Polytope,
+    };
+
+    #[test]
+    fn cube() {
+        assert_eq!(Concrete::cube().wiki_slug(), Some("cube".to_string()));
+    }
+
+    #[test]
+    fn tesseract() {
+        assert_eq!(
+            Concrete::hypercube(5).wiki_slug(),
+            Some("tesseract".to_string())
+        );
+    }
+
+    /// Builds the snub cube from the rotations of a point by the cube's
+    /// symmetry group, whose coordinates involve the tribonacci constant.
+    #[test]
+    fn snub_cube() {
+        let root: Real = 33.0;
+        let t = (1.0 + (19.0 - 3.0 * root.sqrt()).cbrt() + (19.0 + 3.0 * root.sqrt()).cbrt()) / 3.0;
+        let seed = Point::from_vec(vec![1.0, 1.0 / t, t]);
+        let snub_cube = Concrete::from_orbit(Group::hypercube(3).rotations(), &seed);
+        crate::test(&snub_cube, [1, 24, 60, 38, 1]);
+
+        assert_eq!(snub_cube.wiki_slug(), Some("snub_cube".to_string()));
+        assert_eq!(snub_cube.regular_name(), None);
+    }
+
+    /// Checks that regular polytopes are recognized regardless of their
+    /// scale, including the ones without a wiki page.
+    #[test]
+    fn regular_names() {
+        let mut icosahedron = Concrete::icosahedron();
+        icosahedron.scale(3.7);
+        assert_eq!(icosahedron.regular_name(), Some("Icosahedron".to_string()));
+
+        let octahedron = Concrete::cube().dual().unwrap();
+        assert_eq!(octahedron.regular_name(), Some("Octahedron".to_string()));
+
+        assert_eq!(
+            Concrete::simplex(9).regular_name(),
+            Some("8-simplex".to_string())
+        );
+        assert_eq!(
+            Concrete::hypercube(8).regular_name(),
+            Some("7-cube".to_string())
+        );
+        assert_eq!(
+            Concrete::orthoplex(8).regular_name(),
+            Some("7-orthoplex".to_string())
+        );
+
+        assert_eq!(Concrete::uniform_prism(5, 1).regular_name(), None);
+        assert_eq!(Concrete::simplex(8).prism().regular_name(), None);
+    }
+
+    #[test]
+    fn polygon() {
+        assert_eq!(
+            Concrete::polygon(24).wiki_slug(),
+            Some("icositetragon".to_string())
+        );
+    }
+
+    #[test]
+    fn pentagram() {
+        assert_eq!(Concrete::star_polygon(5, 2).wiki_slug(), None);
+    }
+
+    #[test]
+    fn unrecognized() {
+        assert_eq!(Concrete::uniform_prism(5, 1).wiki_slug(), None);
+    }
+}
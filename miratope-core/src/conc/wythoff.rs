@@ -0,0 +1,343 @@
+//! Builds uniform polytopes from Coxeter diagrams with ringed nodes, via the
+//! [Wythoff construction](https://polytope.miraheze.org/wiki/Wythoff_construction).
+//!
+//! Every element of a Wythoffian polytope is the image under the symmetry group
+//! of a base element, whose vertices are the orbit of the generator point under
+//! the reflections in some subset of the nodes. A subset of nodes gives an
+//! element precisely when every connected component of the subdiagram it
+//! induces contains a ringed node.
+
+use std::collections::{btree_map::Entry, BTreeMap, HashSet};
+
+use crate::{
+    abs::{AbstractBuilder, SubelementList},
+    conc::Concrete,
+    cox::{
+        cd::{Cd, CdResult, Node},
+        Cox,
+    },
+    float::{Float, Real},
+    geometry::{Matrix, Point, PointOrd, Vector},
+};
+
+use vec_like::*;
+
+/// Returns whether every connected component of the subdiagram induced by a
+/// set of nodes contains a ringed node.
+fn is_active(cox_adjacent: &[Vec<bool>], ringed: &[bool], set: &[usize]) -> bool {
+    let mut visited = vec![false; set.len()];
+
+    for start in 0..set.len() {
+        if visited[start] {
+            continue;
+        }
+
+        // Flood fills the component of the starting node.
+        visited[start] = true;
+        let mut stack = vec![start];
+        let mut has_ring = false;
+
+        while let Some(i) = stack.pop() {
+            has_ring |= ringed[set[i]];
+
+            for j in 0..set.len() {
+                if !visited[j] && cox_adjacent[set[i]][set[j]] {
+                    visited[j] = true;
+                    stack.push(j);
+                }
+            }
+        }
+
+        if !has_ring {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns the closure of a set of vertices under a set of vertex
+/// permutations, as a sorted list.
+fn closure(start: Vec<usize>, perms: &[&Vec<usize>]) -> Vec<usize> {
+    let mut set: HashSet<_> = start.iter().copied().collect();
+    let mut stack = start;
+
+    while let Some(v) = stack.pop() {
+        for perm in perms {
+            if set.insert(perm[v]) {
+                stack.push(perm[v]);
+            }
+        }
+    }
+
+    let mut set: Vec<_> = set.into_iter().collect();
+    set.sort_unstable();
+    set
+}
+
+impl Concrete {
+    /// Builds the uniform polytope described by a Coxeter diagram with ringed
+    /// nodes, via the Wythoff construction. Star polytopes, given by diagrams
+    /// with rational edges, are supported.
+    ///
+    /// Returns `None` if the diagram doesn't describe a finite group, if it has
+    /// snub nodes, if some connected component has no ringed nodes, or if the
+    /// generator point lies on the mirror of a ringed node.
+    pub fn wythoff(cd: &Cd) -> Option<Self> {
+        if cd.nodes().iter().any(|node| matches!(node, Node::Snub(_))) {
+            return None;
+        }
+
+        Self::wythoff_with(&cd.cox(), cd.node_vector() / 2.0)
+    }
+
+    /// Builds the polytope generated by the Wythoff construction from a
+    /// Coxeter matrix and the distances from the generator point to each
+    /// mirror. A node is ringed when its distance is nonzero.
+    ///
+    /// Returns `None` if the matrix doesn't describe a finite group, if some
+    /// connected component has no ringed nodes, or if the generator point lies
+    /// on the mirror of a ringed node.
+    pub fn wythoff_with(cox: &Cox<Real>, distances: Vector<Real>) -> Option<Self> {
+        let dim = cox.dim();
+        if dim == 0 || distances.len() != dim {
+            return None;
+        }
+
+        let ringed: Vec<_> = distances.iter().map(|d| d.abs() > Real::EPS).collect();
+        let normals = cox.chamber_normals()?;
+        let generator = cox.point_at_distances(distances)?;
+
+        let adjacent: Vec<Vec<_>> = (0..dim)
+            .map(|i| {
+                (0..dim)
+                    .map(|j| i != j && (cox[(i, j)] - 2.0).abs() > Real::EPS)
+                    .collect()
+            })
+            .collect();
+
+        if !is_active(&adjacent, &ringed, &(0..dim).collect::<Vec<_>>()) {
+            return None;
+        }
+
+        // The reflections through each mirror.
+        let reflections: Vec<Matrix<Real>> = normals
+            .column_iter()
+            .map(|n| Matrix::identity(dim, dim) - n * n.transpose() * 2.0)
+            .collect();
+
+        // Finds the orbit of the generator.
+        let mut vertices = vec![generator.clone()];
+        let mut indices = BTreeMap::new();
+        indices.insert(PointOrd::new(generator), 0);
+        let mut idx = 0;
+        while idx < vertices.len() {
+            for refl in &reflections {
+                let v: Point<Real> = refl * &vertices[idx];
+                if let Entry::Vacant(entry) = indices.entry(PointOrd::new(v.clone())) {
+                    entry.insert(vertices.len());
+                    vertices.push(v);
+                }
+            }
+            idx += 1;
+        }
+
+        // The permutation of the vertices induced by each reflection.
+        let perms: Vec<Vec<usize>> = reflections
+            .iter()
+            .map(|refl| {
+                vertices
+                    .iter()
+                    .map(|v| indices[&PointOrd::new(refl * v)])
+                    .collect()
+            })
+            .collect();
+        let all_perms: Vec<_> = perms.iter().collect();
+
+        // The elements of each rank from 2 up to the facets, given by their
+        // vertices and the set of nodes that generates them.
+        let mut elements: Vec<Vec<(Vec<usize>, u32)>> = vec![Vec::new(); dim + 1];
+
+        for mask in 1u32..(1 << dim) - 1 {
+            let set: Vec<_> = (0..dim).filter(|&i| mask & (1 << i) != 0).collect();
+            if !is_active(&adjacent, &ringed, &set) {
+                continue;
+            }
+
+            let set_perms: Vec<_> = set.iter().map(|&i| &perms[i]).collect();
+            let base = closure(vec![0], &set_perms);
+            if base.len() < 2 {
+                return None;
+            }
+
+            // Finds every image of the base element.
+            let mut found = HashSet::new();
+            found.insert(base.clone());
+            let mut stack = vec![base];
+            while let Some(el) = stack.pop() {
+                for perm in &all_perms {
+                    let mut image: Vec<_> = el.iter().map(|&v| perm[v]).collect();
+                    image.sort_unstable();
+
+                    if found.insert(image.clone()) {
+                        stack.push(image);
+                    }
+                }
+
+                elements[set.len() + 1].push((el, mask));
+            }
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(dim + 2);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        // Maps every vertex to the elements of the previous rank containing it.
+        let mut containing: Vec<Vec<usize>> = (0..vertices.len()).map(|v| vec![v]).collect();
+        let mut prev: Vec<(Vec<usize>, u32)> = (0..vertices.len()).map(|v| (vec![v], 0)).collect();
+
+        for els in elements.into_iter().skip(2) {
+            let mut subelements = SubelementList::with_capacity(els.len());
+            let mut new_containing = vec![Vec::new(); vertices.len()];
+
+            for (idx, (el, mask)) in els.iter().enumerate() {
+                // The subelements are the elements of the previous rank whose
+                // vertices and generating nodes are contained in this one's.
+                let mut subs: Vec<_> = el
+                    .iter()
+                    .flat_map(|&v| containing[v].iter().copied())
+                    .filter(|&sub| {
+                        let (sub_el, sub_mask) = &prev[sub];
+                        sub_mask & !mask == 0 && sub_el.iter().all(|v| el.binary_search(v).is_ok())
+                    })
+                    .collect();
+                subs.sort_unstable();
+                subs.dedup();
+                subelements.push(subs.into());
+
+                for &v in el {
+                    new_containing[v].push(idx);
+                }
+            }
+
+            builder.push(subelements);
+            containing = new_containing;
+            prev = els;
+        }
+
+        builder.push_max();
+
+        // Safety: the Wythoff construction always gives a valid polytope.
+        Some(Self::new(vertices, unsafe { builder.build() }))
+    }
+
+    /// Parses a Coxeter diagram and builds the uniform polytope it describes.
+    /// See [`Self::wythoff`].
+    pub fn parse_cd(input: &str) -> CdResult<Option<Self>> {
+        Cd::parse(input).map(|cd| Self::wythoff(&cd))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conc::{Concrete, ConcretePolytope},
+        cox::{cd::Cd, Cox},
+        float::{Float, Real},
+        geometry::Vector,
+    };
+
+    /// Builds the polytope from a diagram, and tests its element counts and
+    /// that it has unit edge length.
+    fn test_cd<I: IntoIterator<Item = usize> + Clone>(input: &str, element_counts: I) {
+        let poly = Concrete::parse_cd(input).unwrap().unwrap();
+        crate::test(&poly, element_counts);
+
+        for len in poly.edge_lengths() {
+            assert!(
+                (len - 1.0).abs() < Real::EPS,
+                "{} has an edge of length {}",
+                input,
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn dyad() {
+        test_cd("x", [1, 2, 1]);
+    }
+
+    #[test]
+    fn hexagon() {
+        test_cd("x3x", [1, 6, 6, 1]);
+    }
+
+    #[test]
+    fn tetrahedron() {
+        test_cd("x3o3o", [1, 4, 6, 4, 1]);
+    }
+
+    #[test]
+    fn cube() {
+        test_cd("x4o3o", [1, 8, 12, 6, 1]);
+    }
+
+    #[test]
+    fn dodecahedron() {
+        test_cd("x5o3o", [1, 20, 30, 12, 1]);
+    }
+
+    #[test]
+    fn truncated_cube() {
+        test_cd("x3x4o", [1, 24, 36, 14, 1]);
+    }
+
+    #[test]
+    fn great_stellated_dodecahedron() {
+        test_cd("x5/2o3o", [1, 20, 30, 12, 1]);
+    }
+
+    #[test]
+    fn pentachoron() {
+        test_cd("x3o3o3o", [1, 5, 10, 10, 5, 1]);
+    }
+
+    #[test]
+    fn triangular_prism() {
+        test_cd("x3o x", [1, 6, 9, 5, 1]);
+    }
+
+    #[test]
+    fn distances() {
+        let cox = Cd::parse("o5o3o").unwrap().cox();
+
+        let dodecahedron = Concrete::wythoff_with(&cox, Vector::from_vec(vec![0.5, 0.0, 0.0]));
+        crate::test(&dodecahedron.unwrap(), [1, 20, 30, 12, 1]);
+
+        // A non-uniform truncated dodecahedron.
+        let truncated = Concrete::wythoff_with(&cox, Vector::from_vec(vec![0.5, 0.2, 0.0]));
+        let truncated = truncated.unwrap();
+        crate::test(&truncated, [1, 60, 90, 32, 1]);
+
+        let lengths = truncated.edge_lengths();
+        assert!(lengths.iter().any(|&len| (len - 1.0).abs() < Real::EPS));
+        assert!(lengths.iter().any(|&len| (len - 0.4).abs() < Real::EPS));
+
+        // A duoprism with different edge lengths in each component.
+        let cox = Cd::parse("o4o o3o").unwrap().cox();
+        let duoprism = Concrete::wythoff_with(&cox, Vector::from_vec(vec![1.0, 0.0, 0.5, 0.0]));
+        crate::test(&duoprism.unwrap(), [1, 12, 24, 19, 7, 1]);
+
+        // Some component isn't ringed.
+        let cox = Cox::h(3);
+        assert!(Concrete::wythoff_with(&cox, Vector::zeros(3)).is_none());
+        assert!(Concrete::wythoff_with(&cox, Vector::zeros(2)).is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Concrete::parse_cd("o3o3o").unwrap().is_none());
+        assert!(Concrete::parse_cd("s3s3s").unwrap().is_none());
+    }
+}
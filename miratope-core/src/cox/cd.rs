@@ -5,7 +5,7 @@ use std::fmt::Display;
 use petgraph::graph::{Edge as GraphEdge, Node as GraphNode, NodeIndex, UnGraph};
 
 use crate::{
-    float::Float,
+    float::{Float, Real},
     geometry::{Matrix, Point, Vector},
 };
 
@@ -61,6 +61,18 @@ pub enum CdError {
         /// The second node in the duplicated edge.
         b: usize,
     },
+
+    /// A virtual node referred to a node that doesn't exist.
+    InvalidVirtualNode {
+        /// The letter of the virtual node.
+        name: char,
+
+        /// Whether the virtual node counts from the end of the diagram.
+        neg: bool,
+
+        /// The position at which the reader found the error.
+        pos: usize,
+    },
 }
 
 impl Display for CdError {
@@ -93,6 +105,15 @@ impl Display for CdError {
             Self::RepeatEdge { a, b } => {
                 write!(f, "repeat edge between {} and {}", a, b)
             }
+
+            // A virtual node referred to a node that doesn't exist.
+            Self::InvalidVirtualNode { name, neg, pos } => write!(
+                f,
+                "virtual node *{}{} at position {} refers to no node",
+                if neg { "-" } else { "" },
+                name,
+                pos
+            ),
         }
     }
 }
@@ -111,19 +132,19 @@ pub enum Node {
 
     /// A ringed node. Represents a mirror at (half) a certain distance from the
     /// generator. Reflecting the generator through this mirror creates an edge.
-    Ringed(f64),
+    Ringed(Real),
 
     /// A snub node. Represents a mirror at (half) a certain distance from the
     /// generator. In contrast to [`Self::Ringed`] nodes, the generator point
     /// and its reflection through this mirror can't simultaneously be in the
     /// polytope.
-    Snub(f64),
+    Snub(Real),
 }
 
 impl Node {
     /// Returns twice the distance from the generator point to the hyperplane
     /// corresponding to this node.
-    pub fn value(&self) -> f64 {
+    pub fn value(&self) -> Real {
         match self {
             Self::Unringed => 0.0,
             Self::Ringed(val) | Self::Snub(val) => *val,
@@ -131,12 +152,12 @@ impl Node {
     }
 
     /// Shorthand for `NodeVal::Ringed(x)`.
-    pub fn ringed(x: f64) -> Self {
+    pub fn ringed(x: Real) -> Self {
         Self::Ringed(x)
     }
 
     /// Shorthand for `NodeVal::Snub(x)`.
-    pub fn snub(x: f64) -> Self {
+    pub fn snub(x: Real) -> Self {
         Self::Snub(x)
     }
 
@@ -153,24 +174,24 @@ impl Node {
     pub fn from_char(c: char) -> Option<Self> {
         Some(Node::ringed(match c {
             'o' => return Some(Node::Unringed),
-            's' => return Some(Node::snub(f64::ONE)),
-            'v' => (f64::SQRT_5 - f64::ONE) / f64::TWO,
-            'x' => f64::ONE,
-            'q' => f64::SQRT_2,
-            'f' => (f64::SQRT_5 + f64::ONE) / f64::TWO,
-            'h' => f64::SQRT_3,
-            'k' => (f64::SQRT_2 + f64::TWO).fsqrt(),
-            'u' => f64::TWO,
-            'w' => f64::SQRT_2 + f64::ONE,
-            'F' => (f64::SQRT_5 + f64::THREE) / f64::TWO,
-            'e' => f64::SQRT_3 + f64::ONE,
-            'Q' => f64::SQRT_2 * f64::TWO,
-            'd' => f64::THREE,
-            'V' => f64::SQRT_5 + f64::ONE,
-            'U' => f64::SQRT_2 + f64::TWO,
-            'A' => (f64::SQRT_5 + f64::ONE) / f64::FOUR + f64::ONE,
-            'X' => f64::SQRT_2 * f64::TWO + f64::ONE,
-            'B' => f64::SQRT_5 + f64::TWO,
+            's' => return Some(Node::snub(Real::ONE)),
+            'v' => (Real::SQRT_5 - Real::ONE) / Real::TWO,
+            'x' => Real::ONE,
+            'q' => Real::SQRT_2,
+            'f' => (Real::SQRT_5 + Real::ONE) / Real::TWO,
+            'h' => Real::SQRT_3,
+            'k' => (Real::SQRT_2 + Real::TWO).fsqrt(),
+            'u' => Real::TWO,
+            'w' => Real::SQRT_2 + Real::ONE,
+            'F' => (Real::SQRT_5 + Real::THREE) / Real::TWO,
+            'e' => Real::SQRT_3 + Real::ONE,
+            'Q' => Real::SQRT_2 * Real::TWO,
+            'd' => Real::THREE,
+            'V' => Real::SQRT_5 + Real::ONE,
+            'U' => Real::SQRT_2 + Real::TWO,
+            'A' => (Real::SQRT_5 + Real::ONE) / Real::FOUR + Real::ONE,
+            'X' => Real::SQRT_2 * Real::TWO + Real::ONE,
+            'B' => Real::SQRT_5 + Real::TWO,
             _ => return None,
         }))
     }
@@ -222,8 +243,8 @@ impl Edge {
     }
 
     /// Returns the numerical value of the edge.
-    pub fn value(&self) -> f64 {
-        f64::u32(self.num) / f64::u32(self.den)
+    pub fn value(&self) -> Real {
+        Real::u32(self.num) / Real::u32(self.den)
     }
 
     /// Returns `true` if the edge stores any value equivalent to 2.
@@ -269,13 +290,16 @@ impl NodeRef {
         }
     }
 
-    /// Returns the index in the graph that the node reference represents.
-    /// Requires knowing the number of nodes in the graph.
-    pub fn index(&self, len: usize) -> NodeIndex {
-        NodeIndex::new(match *self {
+    /// Returns the index in the graph that the node reference represents, or
+    /// `None` if there's no such node. Requires knowing the number of nodes in
+    /// the graph.
+    pub fn index(&self, len: usize) -> Option<NodeIndex> {
+        let idx = match *self {
             Self::Absolute(idx) => idx,
-            Self::Negative(idx) => len - 1 - idx,
-        })
+            Self::Negative(idx) => len.checked_sub(idx + 1)?,
+        };
+
+        (idx < len).then(|| NodeIndex::new(idx))
     }
 }
 
@@ -298,10 +322,10 @@ impl EdgeRef {
         Self { first, other, edge }
     }
 
-    /// Returns the index in the graph of both node references. Requires knowing
-    /// the number of nodes in the graph.
-    pub fn indices(&self, len: usize) -> [NodeIndex; 2] {
-        [self.first.index(len), self.other.index(len)]
+    /// Returns the index in the graph of both node references, or `None` if
+    /// either doesn't exist. Requires knowing the number of nodes in the graph.
+    pub fn indices(&self, len: usize) -> Option<[NodeIndex; 2]> {
+        Some([self.first.index(len)?, self.other.index(len)?])
     }
 }
 
@@ -394,7 +418,7 @@ impl Cd {
     }
 
     /// Returns the vector whose values represent the node values.
-    pub fn node_vector(&self) -> Vector<f64> {
+    pub fn node_vector(&self) -> Vector<Real> {
         Vector::from_iterator(self.dim(), self.node_iter().map(|node| node.value()))
     }
 
@@ -415,7 +439,7 @@ impl Cd {
     }
 
     /// Creates a [`Cox`] from a Coxeter diagram.
-    pub fn cox(&self) -> Cox<f64> {
+    pub fn cox(&self) -> Cox<Real> {
         let dim = self.dim();
         let graph = &self.0;
 
@@ -441,23 +465,20 @@ impl Cd {
     /// Returns the circumradius of the polytope specified by the matrix, or
     /// `None` if this doesn't apply. This is just
     /// calling [`Self::generator`] and taking the norm.
-    pub fn circumradius(&self) -> Option<f64> {
+    pub fn circumradius(&self) -> Option<Real> {
         self.generator().as_ref().map(Point::norm)
     }
 
     /// Returns a point in the position specified by the Coxeter diagram,
-    /// using the set of mirrors generated by [`Cox::normals`].    
-    pub fn generator(&self) -> Option<Point<f64>> {
-        let mut vector = self.node_vector();
-
-        self.cox()
-            .normals()?
-            .solve_upper_triangular_mut(&mut vector)
-            .then(|| vector)
+    /// using the set of mirrors generated by [`Cox::chamber_normals`]. That
+    /// is, the point whose distance to each mirror is half the value of its
+    /// node.
+    pub fn generator(&self) -> Option<Point<Real>> {
+        self.cox().point_at_distances(self.node_vector() / 2.0)
     }
 }
 
-impl From<Cd> for Cox<f64> {
+impl From<Cd> for Cox<Real> {
     fn from(cd: Cd) -> Self {
         cd.cox()
     }
@@ -5,6 +5,7 @@ use std::{collections::VecDeque, iter, str::FromStr};
 use petgraph::graph::NodeIndex;
 
 use super::cd::{Cd, CdError, CdResult, Edge, EdgeRef, Node, NodeRef};
+use crate::float::Real;
 
 /// Helper struct that parses a [`Cd`] based on a textual notation, adapted from
 /// [Krieger (year)](https://bendwavy.org/klitzing/pdf/Stott_v8.pdf).
@@ -31,10 +32,17 @@ use super::cd::{Cd, CdError, CdResult, Edge, EdgeRef, Node, NodeRef};
 /// * Parenthesized lengths, líke `(1.0)` or `(-3.5)`.
 /// * Virtual nodes, like `*a` or `*-c`.
 ///
+/// A virtual node refers to a node found elsewhere in the diagram: `*a` is the
+/// first node, `*b` the second, and so on, while `*-a` is the last node, `*-b`
+/// the one before it, and so on. These can be used to write branched diagrams
+/// like `o3o3o3o3o *c3o`, or loops like `o3o3o3*a`. A virtual node that refers
+/// to no node gives a [`CdError::InvalidVirtualNode`].
+///
 /// Edges come in two different types:
 ///
 /// * A single integer, like `3` or `15`.
-/// * Two integers separated by a backslash, like `5/2` or `7/3`.
+/// * Two integers separated by a slash, like `5/2` or `7/3`. These represent
+///   an angle of π · *d* / *n* between the mirrors.
 pub struct CdBuilder<'a> {
     /// The Coxeter diagram in inline ASCII notation.
     diagram: &'a str,
@@ -57,6 +65,11 @@ pub struct CdBuilder<'a> {
 
     /// The value of the next edge.
     next_edge: Option<Edge>,
+
+    /// The virtual nodes found so far, together with their letters and the
+    /// positions where they were found. These are checked once we know how
+    /// many nodes the diagram has.
+    virtual_nodes: Vec<(NodeRef, char, usize)>,
 }
 
 /// Operations that are commonly done to parse CDs.
@@ -75,6 +88,7 @@ impl<'a> CdBuilder<'a> {
             // The previous and next node to be built.
             prev_node: None,
             next_edge: None,
+            virtual_nodes: Vec::new(),
         }
     }
 
@@ -148,13 +162,15 @@ impl<'a> CdBuilder<'a> {
     /// By the time this method is called, we've already skipped the opening
     /// parenthesis.
     fn parse_node(&mut self) -> CdResult<Node> {
-        let (init_idx, _) = self.peek().expect("Node can't be empty!");
+        let (init_idx, _) = self
+            .peek()
+            .ok_or(CdError::MismatchedParenthesis { pos: self.len() })?;
         let mut end_idx = init_idx;
 
         // We read the number until we find the closing parenthesis.
         while let Some((idx, c)) = self.next() {
             if c == ')' {
-                let val: f64 = self.parse_slice(init_idx, end_idx)?;
+                let val: Real = self.parse_slice(init_idx, end_idx)?;
 
                 // In case the user tries to literally write "NaN" (real funny).
                 return if val.is_nan() {
@@ -192,6 +208,8 @@ impl<'a> CdBuilder<'a> {
 
             // If the node is a virtual node.
             '*' => {
+                let star_idx = idx;
+
                 // Reads the index the virtual node refers to.
                 let (mut idx, mut c) = self.next_or()?;
 
@@ -206,7 +224,10 @@ impl<'a> CdBuilder<'a> {
 
                 match c {
                     // A virtual node, from *a to *z.
-                    'a'..='z' => new_node = NodeRef::new(neg, c as usize - 'a' as usize),
+                    'a'..='z' => {
+                        new_node = NodeRef::new(neg, c as usize - 'a' as usize);
+                        self.virtual_nodes.push((new_node, c, star_idx));
+                    }
 
                     // Any other character is invalid.
                     _ => return Err(CdError::InvalidSymbol { pos: idx }),
@@ -244,7 +265,7 @@ impl<'a> CdBuilder<'a> {
     /// [`CdError::InvalidEdge`] if the edge is something invalid like `1/0`.
     fn parse_edge(&mut self) -> CdResult<Option<Edge>> {
         let mut numerator = None;
-        let (mut init_idx, c) = self.peek().expect("Slice can't be empty!");
+        let (mut init_idx, c) = self.peek_or()?;
 
         // If the next character is not numeric, this means this isn't an edge
         // at all, and we return None.
@@ -303,11 +324,14 @@ impl<'a> CdBuilder<'a> {
         Ok(())
     }
 
+    /// Reads through the entire diagram, adding its nodes to the graph and
+    /// queuing up its edges.
     fn read(&mut self) -> CdResult<()> {
         loop {
             self.create_node()?;
 
             // We continue until we find that there's no further edges.
+            self.skip_whitespace();
             if self.peek().is_none() {
                 return Ok(());
             }
@@ -322,8 +346,21 @@ impl<'a> CdBuilder<'a> {
         self.read()?;
         let len = self.cd.node_count();
 
+        // Checks that every virtual node refers to an actual node.
+        for &(node_ref, name, pos) in &self.virtual_nodes {
+            if node_ref.index(len).is_none() {
+                return Err(CdError::InvalidVirtualNode {
+                    name,
+                    neg: matches!(node_ref, NodeRef::Negative(_)),
+                    pos,
+                });
+            }
+        }
+
         for edge_ref in self.edge_queue.into_iter() {
-            let [a, b] = edge_ref.indices(len);
+            let [a, b] = edge_ref
+                .indices(len)
+                .expect("virtual nodes were checked");
             self.cd.add_edge(a, b, edge_ref.edge)?;
         }
 
@@ -355,7 +392,7 @@ mod tests {
 
     /// Tests that a parsed diagram's nodes and Coxeter matrix match expected
     /// values.
-    fn test(diagram: &str, nodes: Vec<Node>, matrix: Matrix<f64>) {
+    fn test(diagram: &str, nodes: Vec<Node>, matrix: Matrix<Real>) {
         let cd = Cd::parse(diagram).unwrap();
         assert_eq!(cd.nodes(), nodes, "Node mismatch!");
         assert_eq!(cd.cox(), Cox::new(matrix), "Coxeter matrix mismatch!");
@@ -365,7 +402,7 @@ mod tests {
     /// Tests some of the I2 symmetry groups.
     fn i2() {
         for n in 2..10 {
-            let nf = n as f64;
+            let nf = n as Real;
 
             test(
                 &format!("x{}x", n),
@@ -425,6 +462,34 @@ mod tests {
         )
     }
 
+    #[test]
+    /// Tests a loop, closed by a virtual node.
+    fn cycle() {
+        test(
+            "x3o3o3*a",
+            vec![x(), o(), o()],
+            dmatrix![
+                1.0, 3.0, 3.0;
+                3.0, 1.0, 3.0;
+                3.0, 3.0, 1.0
+            ],
+        )
+    }
+
+    #[test]
+    /// Tests fractional edges.
+    fn fractions() {
+        test(
+            "o5/2o5o3/2*a",
+            vec![o(), o(), o()],
+            dmatrix![
+                1.0, 2.5, 1.5;
+                2.5, 1.0, 5.0;
+                1.5, 5.0, 1.0
+            ],
+        )
+    }
+
     #[test]
     /// Tests snub nodes.
     fn snubs() {
@@ -486,6 +551,15 @@ mod tests {
                 3.0, 1.0, 2.0;
                 2.0, 2.0, 1.0
             ],
+        );
+
+        test(
+            "x3o ",
+            vec![x(), o()],
+            dmatrix![
+                1.0, 3.0;
+                3.0, 1.0
+            ],
         )
     }
 
@@ -509,6 +583,12 @@ mod tests {
         Cd::parse("x(1.0x").unwrap();
     }
 
+    #[test]
+    #[should_panic(expected = "MismatchedParenthesis { pos: 2 }")]
+    fn empty_parenthesis() {
+        Cd::parse("x(").unwrap();
+    }
+
     #[test]
     #[should_panic(expected = "UnexpectedEnding { pos: 6 }")]
     fn unexpected_ending() {
@@ -538,4 +618,24 @@ mod tests {
     fn repeat_edge() {
         Cd::parse("x3x xx *c3*d *a3*b").unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "InvalidVirtualNode { name: 'z', neg: false, pos: 2 }")]
+    fn invalid_virtual_node() {
+        Cd::parse("o3*z3o").unwrap();
+    }
+
+    #[test]
+    /// Tests that errors name the virtual node that refers to no node.
+    fn invalid_virtual_node_message() {
+        for &(diagram, message) in &[
+            ("o3*z3o", "virtual node *z at position 2 refers to no node"),
+            ("x3o *-d3x", "virtual node *-d at position 4 refers to no node"),
+        ] {
+            match Cd::parse(diagram) {
+                Err(err) => assert_eq!(err.to_string(), message),
+                Ok(_) => panic!("{} should not parse", diagram),
+            }
+        }
+    }
 }
@@ -4,11 +4,22 @@
 #![allow(dead_code)]
 #![allow(clippy::collapsible_match)]
 
-use std::io::Result as IoResult;
+use std::{
+    fmt::Write as _,
+    io::{Result as IoResult, Seek, Write},
+    path::Path,
+};
 
-use crate::{conc::Concrete, geometry::Point};
+use super::IoError;
+use crate::{
+    abs::Ranked,
+    conc::{cycle::CycleList, Concrete, ConcretePolytope},
+    float::Real,
+    geometry::Point,
+};
 
 use nalgebra::dvector;
+use vec_like::*;
 use xml::{
     attribute::OwnedAttribute,
     reader::{EventReader, XmlEvent},
@@ -82,7 +93,7 @@ impl<'a> XmlReader<'a> {
     /// ```xml
     /// <element type="point3d" label="A">
     /// ```
-    fn read_point(&mut self, attributes: &[OwnedAttribute]) -> GgbResult<Vertex<f64>> {
+    fn read_point(&mut self, attributes: &[OwnedAttribute]) -> GgbResult<Vertex<Real>> {
         let label = attribute(attributes, "label").unwrap_or_default();
         let coord_attributes = self.read_until("coords")?;
 
@@ -90,7 +101,7 @@ impl<'a> XmlReader<'a> {
         /// the same name.
         macro_rules! read_coord {
             ($x:ident) => {
-                let $x: f64;
+                let $x: Real;
 
                 if let Some(c) = attribute(&coord_attributes, stringify!($x)) {
                     if let Ok(c) = c.parse() {
@@ -140,6 +151,12 @@ pub enum GgbError {
 
     /// Some number could not be parsed.
     ParseError,
+
+    /// Some generic I/O error occured while writing the file.
+    IoError(IoError),
+
+    /// The polytope lives in more than 3 dimensions, so it can't be exported.
+    InvalidDimension(usize),
 }
 
 impl std::fmt::Display for GgbError {
@@ -151,6 +168,12 @@ impl std::fmt::Display for GgbError {
             Self::InvalidGgb => write!(f, "invalid GGB"),
             Self::ZipError(err) => write!(f, "ZIP error: {}", err),
             Self::ParseError => write!(f, "parse error"),
+            Self::IoError(err) => write!(f, "IO error: {}", err),
+            Self::InvalidDimension(dim) => write!(
+                f,
+                "cannot export a polytope in {} dimensions, GeoGebra only supports up to 3",
+                dim
+            ),
         }
     }
 }
@@ -161,6 +184,12 @@ impl From<ZipError> for GgbError {
     }
 }
 
+impl From<IoError> for GgbError {
+    fn from(err: IoError) -> Self {
+        Self::IoError(err)
+    }
+}
+
 /// The result of trying to read a GGB file.
 pub type GgbResult<T> = Result<T, GgbError>;
 
@@ -210,7 +239,7 @@ fn read_face() -> Face {
 
 /// Parses the `geogebra.xml` file to produce a polytope.
 pub(super) fn parse_xml(xml: &str) -> GgbResult<Concrete> {
-    let mut vertices: Vec<Vertex<f64>> = Vec::new();
+    let mut vertices: Vec<Vertex<Real>> = Vec::new();
     let mut edges = Vec::new();
     let mut xml = XmlReader::new(xml);
 
@@ -255,3 +284,232 @@ pub(super) fn parse_xml(xml: &str) -> GgbResult<Concrete> {
         }
     }
 }
+
+/// A set of options to be used when saving a GGB file.
+#[derive(Clone, Copy)]
+pub struct GgbOptions {
+    /// The number of decimals the coordinates are rounded to.
+    pub decimals: usize,
+}
+
+impl Default for GgbOptions {
+    fn default() -> Self {
+        Self { decimals: 6 }
+    }
+}
+
+/// Returns the label of the vertex with a given index, following GeoGebra's
+/// conventions: `A`, `B`, ..., `Z`, `AA`, `AB`, ...
+pub fn vertex_label(mut idx: usize) -> String {
+    let mut label = Vec::new();
+
+    loop {
+        label.push(b'A' + (idx % 26) as u8);
+        if idx < 26 {
+            break;
+        }
+        idx = idx / 26 - 1;
+    }
+
+    label.reverse();
+    String::from_utf8(label).unwrap()
+}
+
+/// Returns the label of the edge with a given index.
+fn edge_label(idx: usize) -> String {
+    format!("e_{{{}}}", idx + 1)
+}
+
+/// Returns the label of the face with a given index.
+fn face_label(idx: usize) -> String {
+    format!("f_{{{}}}", idx + 1)
+}
+
+/// Writes a GeoGebra command with a list of inputs and a single output.
+fn write_command<I: IntoIterator<Item = String>>(
+    xml: &mut String,
+    name: &str,
+    inputs: I,
+    output: &str,
+) {
+    let _ = write!(xml, "<command name=\"{}\">\n\t<input", name);
+    for (idx, input) in inputs.into_iter().enumerate() {
+        let _ = write!(xml, " a{}=\"{}\"", idx, input);
+    }
+    let _ = writeln!(xml, "/>\n\t<output a0=\"{}\"/>\n</command>", output);
+}
+
+impl Concrete {
+    /// Converts a polytope into the `geogebra.xml` file of a GGB file, with a
+    /// point for every vertex, a segment for every edge, and a polygon for
+    /// every component of every face. Fails if the polytope lives in more than
+    /// 3 dimensions.
+    pub fn to_ggb_xml(&self, options: GgbOptions) -> GgbResult<String> {
+        let dim = self.dim_or();
+        if dim > 3 {
+            return Err(GgbError::InvalidDimension(dim));
+        }
+
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<geogebra format=\"5.0\">\n<construction>\n",
+        );
+
+        // Writes the vertices, padding their coordinates with zeros.
+        for (idx, v) in self.vertices.iter().enumerate() {
+            let coord = |i: usize| v.get(i).copied().unwrap_or(0.0);
+            let _ = writeln!(
+                xml,
+                "<element type=\"point3d\" label=\"{}\">\n\t<coords x=\"{:.*}\" y=\"{:.*}\" z=\"{:.*}\" w=\"1\"/>\n</element>",
+                vertex_label(idx),
+                options.decimals,
+                coord(0),
+                options.decimals,
+                coord(1),
+                options.decimals,
+                coord(2)
+            );
+        }
+
+        let rank = self.rank();
+        if rank < 3 {
+            xml.push_str("</construction>\n</geogebra>\n");
+            return Ok(xml);
+        }
+
+        // Writes the edges.
+        for (idx, edge) in self[2].iter().enumerate() {
+            let label = edge_label(idx);
+            write_command(
+                &mut xml,
+                "Segment",
+                edge.subs.iter().map(|&v| vertex_label(v)),
+                &label,
+            );
+            let _ = writeln!(xml, "<element type=\"segment3d\" label=\"{}\"/>", label);
+        }
+
+        // Writes every component of every face as a polygon.
+        let cycles: Vec<_> = if rank == 3 {
+            CycleList::from_edges(self[2].iter().map(|edge| &edge.subs))
+                .into_iter()
+                .collect()
+        } else {
+            self[3]
+                .iter()
+                .flat_map(|face| {
+                    CycleList::from_edges(face.subs.iter().map(|&i| &self[(2, i)].subs))
+                })
+                .collect()
+        };
+
+        for (idx, cycle) in cycles.into_iter().enumerate() {
+            let label = face_label(idx);
+            write_command(
+                &mut xml,
+                "Polygon",
+                cycle.into_iter().map(vertex_label),
+                &label,
+            );
+            let _ = writeln!(xml, "<element type=\"polygon3d\" label=\"{}\"/>", label);
+        }
+
+        xml.push_str("</construction>\n</geogebra>\n");
+        Ok(xml)
+    }
+
+    /// Writes a polytope's GGB file into a writer.
+    pub fn write_ggb<W: Write + Seek>(&self, writer: W, options: GgbOptions) -> GgbResult<()> {
+        let xml = self.to_ggb_xml(options)?;
+        let mut zip = zip::ZipWriter::new(writer);
+        zip.start_file("geogebra.xml", zip::write::FileOptions::default())?;
+        zip.write_all(xml.as_bytes())?;
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// Writes a polytope's GGB file in a specified file path.
+    pub fn to_ggb<P: AsRef<Path>>(&self, fp: P, options: GgbOptions) -> GgbResult<()> {
+        self.write_ggb(std::fs::File::create(fp)?, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{Cursor, Read},
+    };
+
+    use super::*;
+    use crate::Polytope;
+
+    #[test]
+    fn labels() {
+        assert_eq!(vertex_label(0), "A");
+        assert_eq!(vertex_label(25), "Z");
+        assert_eq!(vertex_label(26), "AA");
+        assert_eq!(vertex_label(27), "AB");
+        assert_eq!(vertex_label(26 + 26 * 26), "AAA");
+    }
+
+    /// Counts the GeoGebra elements of each type in an XML file.
+    fn element_types(xml: &str) -> HashMap<String, usize> {
+        let mut types = HashMap::new();
+
+        for event in EventReader::from_str(xml) {
+            if let XmlEvent::StartElement {
+                name, attributes, ..
+            } = event.expect("Invalid XML.")
+            {
+                if name.local_name == "element" {
+                    let el_type = attribute(&attributes, "type").unwrap();
+                    *types.entry(el_type.to_string()).or_default() += 1;
+                }
+            }
+        }
+
+        types
+    }
+
+    #[test]
+    fn cube() {
+        let mut ggb = Cursor::new(Vec::new());
+        Concrete::cube()
+            .write_ggb(&mut ggb, Default::default())
+            .unwrap();
+
+        let mut xml = String::new();
+        zip::read::ZipArchive::new(ggb)
+            .unwrap()
+            .by_name("geogebra.xml")
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+
+        let types = element_types(&xml);
+        assert_eq!(types["point3d"], 8);
+        assert_eq!(types["segment3d"], 12);
+        assert_eq!(types["polygon3d"], 6);
+        assert!(xml.contains("<coords x=\"-0.500000\" y=\"-0.500000\" z=\"-0.500000\" w=\"1\"/>"));
+    }
+
+    #[test]
+    fn polygon() {
+        let xml = Concrete::polygon(5)
+            .to_ggb_xml(GgbOptions { decimals: 2 })
+            .unwrap();
+
+        let types = element_types(&xml);
+        assert_eq!(types["point3d"], 5);
+        assert_eq!(types["segment3d"], 5);
+        assert_eq!(types["polygon3d"], 1);
+    }
+
+    #[test]
+    fn tesseract() {
+        assert!(matches!(
+            Concrete::hypercube(5).to_ggb_xml(Default::default()),
+            Err(GgbError::InvalidDimension(4))
+        ));
+    }
+}
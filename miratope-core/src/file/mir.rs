@@ -0,0 +1,504 @@
+//! Reading from and writing to MIR files, Miratope's own binary format.
+//!
+//! These are much faster to load than OFF files, so they're used to cache
+//! large polytopes. All values are stored in little endian, in the following
+//! layout:
+//!
+//! - The magic bytes `MIR`, followed by the [version](VERSION) of the format.
+//! - The number of bytes in each coordinate, either 4 or 8.
+//! - The rank of the polytope, its number of dimensions, and its number of
+//!   vertices, as `u32`s.
+//! - The coordinates of each vertex.
+//! - For every rank from the edges up to the maximal element, the number of
+//!   elements, and the number of subelements of each element followed by their
+//!   indices, all as `u32`s.
+//!
+//! Superelements aren't stored, since they can be recovered from the
+//! subelements.
+
+use std::{
+    convert::TryFrom,
+    fmt::Display,
+    fs,
+    io::Error as IoError,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    abs::{AbstractBuilder, AbstractError, Ranked, SubelementList, Subelements},
+    conc::{Concrete, ConcretePolytope},
+    float::{Float, Real},
+    geometry::Point,
+};
+
+use super::{FileError, FileResult, FromFile};
+
+use vec_like::VecLike;
+
+/// The magic bytes at the start of every MIR file.
+const MAGIC: &[u8; 3] = b"MIR";
+
+/// The current version of the format. Files of any other version are
+/// rejected.
+pub const VERSION: u8 = 1;
+
+/// Any error encountered while reading or writing a MIR file.
+#[derive(Debug)]
+pub enum MirError {
+    /// Didn't find the MIR magic bytes.
+    MagicBytes,
+
+    /// The file was written with another version of the format.
+    Version(u8),
+
+    /// The coordinates have an unsupported size.
+    FloatSize(u8),
+
+    /// The file ended unexpectedly.
+    UnexpectedEnding,
+
+    /// The file has data left over after the polytope.
+    TrailingData,
+
+    /// The polytope has no vertices despite not being the nullitope, or the
+    /// other way around.
+    VertexCount {
+        /// The rank of the polytope.
+        rank: usize,
+
+        /// The number of vertices in the file.
+        count: usize,
+    },
+
+    /// An element has no subelements.
+    EmptyElement {
+        /// The rank of the element.
+        rank: usize,
+
+        /// The index of the element.
+        index: usize,
+    },
+
+    /// An element references a subelement that doesn't exist.
+    IndexOutOfBounds {
+        /// The rank of the referenced subelement.
+        rank: usize,
+
+        /// The index of the referenced subelement.
+        index: usize,
+
+        /// The number of elements of that rank.
+        len: usize,
+    },
+
+    /// The polytope has too many elements to be stored.
+    TooLarge,
+
+    /// The elements read don't form a valid polytope.
+    Invalid(AbstractError),
+
+    /// There was a problem reading or saving the file.
+    IoError(IoError),
+}
+
+impl Display for MirError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MagicBytes => write!(f, "no \"MIR\" detected"),
+            Self::Version(version) => write!(
+                f,
+                "file has version {}, expected version {}",
+                version, VERSION
+            ),
+            Self::FloatSize(size) => write!(f, "coordinates can't have {} bytes", size),
+            Self::UnexpectedEnding => write!(f, "file ended unexpectedly"),
+            Self::TrailingData => write!(f, "file has trailing data"),
+            Self::VertexCount { rank, count } => write!(
+                f,
+                "polytope of rank {} can't have {} vertices",
+                rank, count
+            ),
+            Self::EmptyElement { rank, index } => write!(
+                f,
+                "element {} of rank {} has no subelements",
+                index, rank
+            ),
+            Self::IndexOutOfBounds { rank, index, len } => write!(
+                f,
+                "index {} of rank {} is out of bounds: only {} elements exist",
+                index, rank, len
+            ),
+            Self::TooLarge => write!(f, "polytope is too large"),
+            Self::Invalid(err) => write!(f, "invalid polytope: {}", err),
+            Self::IoError(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for MirError {}
+
+impl From<IoError> for MirError {
+    fn from(err: IoError) -> Self {
+        Self::IoError(err)
+    }
+}
+
+/// The result of reading or writing a MIR file.
+pub type MirResult<T> = Result<T, MirError>;
+
+/// Appends a count or an index to a MIR file.
+fn write_u32(bytes: &mut Vec<u8>, n: usize) -> MirResult<()> {
+    let n = u32::try_from(n).map_err(|_| MirError::TooLarge)?;
+    bytes.extend_from_slice(&n.to_le_bytes());
+    Ok(())
+}
+
+/// Reads the values in a MIR file one by one.
+struct MirReader<'a> {
+    /// The bytes left to read.
+    src: &'a [u8],
+}
+
+impl<'a> MirReader<'a> {
+    /// Reads the next few bytes of the file.
+    fn read_bytes(&mut self, len: usize) -> MirResult<&'a [u8]> {
+        if self.src.len() < len {
+            return Err(MirError::UnexpectedEnding);
+        }
+
+        let (bytes, src) = self.src.split_at(len);
+        self.src = src;
+        Ok(bytes)
+    }
+
+    /// Reads a byte.
+    fn read_u8(&mut self) -> MirResult<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    /// Reads a count or an index.
+    fn read_u32(&mut self) -> MirResult<usize> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+        Ok(u32::from_le_bytes(bytes) as usize)
+    }
+
+    /// Reads a coordinate with a given number of bytes.
+    fn read_float(&mut self, size: u8) -> MirResult<Real> {
+        Ok(if size == 4 {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(self.read_bytes(4)?);
+            Real::f64(f64::from(f32::from_le_bytes(bytes)))
+        } else {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(self.read_bytes(8)?);
+            Real::f64(f64::from_le_bytes(bytes))
+        })
+    }
+
+    /// Returns a capacity for a list of `len` values, each of which takes up
+    /// at least `size` bytes. This is never more than what the rest of the
+    /// file could hold, so that corrupt counts can't exhaust the memory.
+    fn capacity(&self, len: usize, size: usize) -> usize {
+        len.min(self.src.len() / size)
+    }
+
+    /// Reads the next list of elements, given the number of elements of the
+    /// previous rank.
+    fn read_els(&mut self, rank: usize, num_subs: usize) -> MirResult<SubelementList> {
+        let num_el = self.read_u32()?;
+        let mut els_subs = SubelementList::with_capacity(self.capacity(num_el, 4));
+
+        for index in 0..num_el {
+            let el_sub_num = self.read_u32()?;
+            if el_sub_num == 0 {
+                return Err(MirError::EmptyElement { rank, index });
+            }
+
+            let mut subs = Subelements::with_capacity(self.capacity(el_sub_num, 4));
+            for _ in 0..el_sub_num {
+                let sub = self.read_u32()?;
+                if sub >= num_subs {
+                    return Err(MirError::IndexOutOfBounds {
+                        rank: rank - 1,
+                        index: sub,
+                        len: num_subs,
+                    });
+                }
+
+                subs.push(sub);
+            }
+
+            els_subs.push(subs);
+        }
+
+        Ok(els_subs)
+    }
+
+    /// Reads a polytope from the file.
+    fn build(mut self) -> MirResult<Concrete> {
+        if self.read_bytes(MAGIC.len())? != MAGIC {
+            return Err(MirError::MagicBytes);
+        }
+
+        let version = self.read_u8()?;
+        if version != VERSION {
+            return Err(MirError::Version(version));
+        }
+
+        let size = self.read_u8()?;
+        if size != 4 && size != 8 {
+            return Err(MirError::FloatSize(size));
+        }
+
+        let rank = self.read_u32()?;
+        let dim = self.read_u32()?;
+        let vertex_count = self.read_u32()?;
+
+        // Only the nullitope has no vertices.
+        if (rank == 0) != (vertex_count == 0) {
+            return Err(MirError::VertexCount {
+                rank,
+                count: vertex_count,
+            });
+        }
+
+        let capacity = self.capacity(vertex_count, dim.max(1) * size as usize);
+        let mut vertices = Vec::with_capacity(capacity);
+        for _ in 0..vertex_count {
+            let mut coords = Vec::with_capacity(self.capacity(dim, size as usize));
+            for _ in 0..dim {
+                coords.push(self.read_float(size)?);
+            }
+
+            vertices.push(Point::from_vec(coords));
+        }
+
+        // Every rank from the edges up takes at least the four bytes of its
+        // element count, so that a corrupt rank can't exhaust the memory.
+        if rank.saturating_sub(1) > self.src.len() / 4 {
+            return Err(MirError::UnexpectedEnding);
+        }
+
+        let mut abs = AbstractBuilder::with_rank_capacity(rank);
+        abs.push_min();
+        if rank != 0 {
+            abs.push_vertices(vertex_count);
+        }
+
+        let mut num_subs = vertex_count;
+        for r in 2..=rank {
+            let subelements = self.read_els(r, num_subs)?;
+            num_subs = subelements.len();
+            abs.push(subelements);
+        }
+
+        if !self.src.is_empty() {
+            return Err(MirError::TrailingData);
+        }
+
+        abs.ranks().is_valid().map_err(MirError::Invalid)?;
+
+        // Safety: we've just checked that the ranks form a valid polytope.
+        Ok(Concrete::new(vertices, unsafe { abs.build() }))
+    }
+}
+
+impl Concrete {
+    /// Converts a polytope into the bytes of a MIR file.
+    pub fn to_bin(&self) -> MirResult<Vec<u8>> {
+        let rank = self.rank();
+        let dim = self.dim().unwrap_or(0);
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(std::mem::size_of::<Real>() as u8);
+        write_u32(&mut bytes, rank)?;
+        write_u32(&mut bytes, dim)?;
+        write_u32(&mut bytes, self.vertices.len())?;
+
+        for v in &self.vertices {
+            for x in v.iter() {
+                bytes.extend_from_slice(&x.to_le_bytes());
+            }
+        }
+
+        for r in 2..=rank {
+            write_u32(&mut bytes, self.el_count(r))?;
+            for el in self[r].iter() {
+                write_u32(&mut bytes, el.subs.len())?;
+                for &sub in &el.subs {
+                    write_u32(&mut bytes, sub)?;
+                }
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    /// Reads a polytope from the bytes of a MIR file.
+    pub fn from_bin(src: &[u8]) -> MirResult<Self> {
+        MirReader { src }.build()
+    }
+
+    /// Writes a polytope's MIR file in a specified file path.
+    pub fn save_bin<P: AsRef<Path>>(&self, fp: P) -> MirResult<()> {
+        fs::write(fp, self.to_bin()?)?;
+        Ok(())
+    }
+
+    /// Reads a polytope from a MIR file in a specified file path.
+    pub fn load_bin<P: AsRef<Path>>(fp: P) -> MirResult<Self> {
+        Self::from_bin(&fs::read(fp)?)
+    }
+
+    /// Returns the path in which the MIR file caching an OFF file is stored,
+    /// which is the same path with the extension changed.
+    pub fn cache_path<P: AsRef<Path>>(fp: P) -> PathBuf {
+        fp.as_ref().with_extension("mir")
+    }
+
+    /// Loads a polytope from an OFF file, preferring its cached MIR file if
+    /// it's newer than the OFF file. Otherwise, the OFF file is read, and its
+    /// MIR file is written for next time.
+    ///
+    /// The cache is only an optimization: if it can't be read for any reason,
+    /// such as a corrupt file or an older version of the format, the OFF file
+    /// is read instead, and if it can't be written, it's just skipped.
+    pub fn from_off_cached<P: AsRef<Path>>(fp: &P) -> FileResult<'_, Self> {
+        let cache = Self::cache_path(fp);
+
+        // Whether the cache was modified after the OFF file.
+        let modified = |p: &Path| fs::metadata(p).and_then(|meta| meta.modified()).ok();
+        let fresh = match (modified(fp.as_ref()), modified(&cache)) {
+            (Some(off), Some(mir)) => mir >= off,
+            _ => false,
+        };
+
+        if fresh {
+            if let Ok(poly) = Self::load_bin(&cache) {
+                return Ok(poly);
+            }
+        }
+
+        let src = String::from_utf8(fs::read(fp)?)?;
+        let poly = Self::from_off(&src)?;
+        poly.save_bin(&cache).ok();
+        Ok(poly)
+    }
+}
+
+/// [`MirError`] is a type of [`FileError`].
+impl<'a> From<MirError> for FileError<'a> {
+    fn from(err: MirError) -> Self {
+        Self::MirError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    use std::time::Instant;
+
+    /// Checks that MIR files written from polytopes read back into the same
+    /// polytopes.
+    #[test]
+    fn round_trip() {
+        for poly in [
+            Concrete::nullitope(),
+            Concrete::point(),
+            Concrete::dyad(),
+            Concrete::polygon(7),
+            Concrete::star_polygon(7, 2),
+            Concrete::cube(),
+            Concrete::simplex(5),
+            Concrete::orthoplex(6),
+            Concrete::polygon(5).try_antiprism().unwrap(),
+            Concrete::polygon(4).pyramid(),
+            Concrete::polygon(3).duoprism(&Concrete::polygon(5)),
+        ] {
+            let read = Concrete::from_bin(&poly.to_bin().unwrap()).unwrap();
+            test(&read, poly.el_count_iter());
+            assert_eq!(read.vertices, poly.vertices);
+
+            for r in 0..=poly.rank() {
+                for (el, read_el) in poly[r].iter().zip(read[r].iter()) {
+                    assert_eq!(el.subs, read_el.subs);
+                }
+            }
+        }
+    }
+
+    /// Compares the size and loading time of the MIR and OFF files of a large
+    /// polytope. Run with
+    /// `cargo test --release -- --ignored hexeract --nocapture`.
+    #[test]
+    #[ignore]
+    fn hexeract() {
+        let poly = Concrete::hypercube(7);
+        let bin = poly.to_bin().unwrap();
+        let off = poly.to_off(Default::default()).unwrap();
+
+        let now = Instant::now();
+        let read = Concrete::from_bin(&bin).unwrap();
+        let bin_time = now.elapsed();
+        test(&read, poly.el_count_iter());
+
+        let now = Instant::now();
+        Concrete::from_off(&off).unwrap();
+        let off_time = now.elapsed();
+
+        println!(
+            "6-cube: MIR is {} bytes, read in {:?}; OFF is {} bytes, read in {:?}",
+            bin.len(),
+            bin_time,
+            off.len(),
+            off_time
+        );
+    }
+
+    /// Checks that corrupt files are rejected instead of crashing.
+    #[test]
+    fn corrupt() {
+        let bin = Concrete::cube().to_bin().unwrap();
+
+        // Every truncation of the file is an error.
+        for len in 0..bin.len() {
+            assert!(Concrete::from_bin(&bin[..len]).is_err());
+        }
+
+        let mut wrong = bin.clone();
+        wrong[0] = b'O';
+        assert!(matches!(Concrete::from_bin(&wrong), Err(MirError::MagicBytes)));
+
+        let mut wrong = bin.clone();
+        wrong[3] = VERSION + 1;
+        assert!(matches!(Concrete::from_bin(&wrong), Err(MirError::Version(_))));
+
+        let mut wrong = bin.clone();
+        wrong.push(0);
+        assert!(matches!(Concrete::from_bin(&wrong), Err(MirError::TrailingData)));
+
+        // Changes the first vertex of the first edge to a nonexistent one.
+        let mut wrong = bin.clone();
+        let edge = 5 + 12 + 8 * 3 * std::mem::size_of::<Real>() + 4 + 4;
+        wrong[edge..edge + 4].copy_from_slice(&8u32.to_le_bytes());
+        assert!(matches!(
+            Concrete::from_bin(&wrong),
+            Err(MirError::IndexOutOfBounds { rank: 1, index: 8, .. })
+        ));
+
+        // Claims there's a huge rank.
+        let mut wrong = bin.clone();
+        wrong[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(Concrete::from_bin(&wrong), Err(MirError::UnexpectedEnding)));
+
+        // Claims there's a huge number of vertices.
+        let mut wrong = bin;
+        wrong[13..17].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Concrete::from_bin(&wrong).is_err());
+    }
+}
@@ -1,10 +1,12 @@
 //! Reading from and writing to files in various different formats.
 
 pub mod ggb;
+pub mod mir;
 pub mod off;
 
 use self::{
     ggb::{GgbError, GgbResult},
+    mir::{MirError, MirResult},
     off::{OffParseResult, OffReader},
 };
 use crate::conc::Concrete;
@@ -24,6 +26,9 @@ pub enum FileError<'a> {
     /// An error while reading a GGB file.
     GgbError(GgbError),
 
+    /// An error while reading a MIR file.
+    MirError(MirError),
+
     /// Some generic I/O error occured.
     IoError(IoError),
 
@@ -43,6 +48,7 @@ impl<'a> Display for FileError<'a> {
         match self {
             Self::OffError(err) => write!(f, "OFF error: {}", err),
             Self::GgbError(err) => write!(f, "GGB error: {}", err),
+            Self::MirError(err) => write!(f, "MIR error: {}", err),
             Self::IoError(err) => write!(f, "IO error: {}", err),
             Self::ZipError(err) => write!(f, "ZIP error while opening GGB: {}", err),
             Self::InvalidFile(err) => write!(f, "invalid file: {}", err),
@@ -103,6 +109,9 @@ pub trait FromFile: Sized {
     /// 3D.
     fn from_ggb(file: File) -> GgbResult<Self>;
 
+    /// Reads a polytope from the bytes of a MIR file.
+    fn from_mir(src: &[u8]) -> MirResult<Self>;
+
     /// Loads a polytope from a file path.
     fn from_path<U: AsRef<std::path::Path>>(fp: &U) -> FileResult<'_, Self> {
         use std::{ffi::OsStr, fs};
@@ -123,6 +132,9 @@ pub trait FromFile: Sized {
             // Reads the file as a GGB file.
             "ggb" => Ok(Self::from_ggb(fs::File::open(fp)?)?),
 
+            // Reads the file as a MIR file.
+            "mir" => Ok(Self::from_mir(&fs::read(fp)?)?),
+
             // Could not recognize the file extension.
             ext => Err(FileError::InvalidExtension(ext)),
         }
@@ -151,6 +163,10 @@ impl FromFile for Concrete {
             Err(GgbError::InvalidGgb)
         }
     }
+
+    fn from_mir(src: &[u8]) -> MirResult<Self> {
+        Self::from_bin(src)
+    }
 }
 
 /// A position in a file.
@@ -6,8 +6,9 @@ use std::{collections::{HashMap, HashSet}, fmt::Display, io::Error as IoError, p
 use super::Position;
 
 use crate::{
-    abs::{AbstractBuilder, Ranked, SubelementList, Subelements},
+    abs::{AbstractBuilder, AbstractError, Ranked, SubelementList, Subelements},
     conc::{cycle::CycleList, Concrete},
+    float::Real,
     geometry::Point,
     Polytope, COMPONENTS, ELEMENT_NAMES,
 };
@@ -38,6 +39,27 @@ pub enum OffParseError {
 
     /// Didn't find the OFF magic word.
     MagicWord(Position),
+
+    /// An element has no subelements.
+    EmptyElement(Position),
+
+    /// An element references a subelement that doesn't exist.
+    IndexOutOfBounds {
+        /// The position of the reference.
+        pos: Position,
+
+        /// The rank of the referenced subelement.
+        rank: usize,
+
+        /// The index of the referenced subelement.
+        index: usize,
+
+        /// The number of elements of that rank.
+        len: usize,
+    },
+
+    /// The elements read don't form a valid polytope.
+    Invalid(AbstractError),
 }
 
 impl Display for OffParseError {
@@ -48,6 +70,21 @@ impl Display for OffParseError {
             Self::Parsing(pos) => write!(f, "could not parse number at {}", pos),
             Self::Rank(pos) => write!(f, "could not read rank at {}", pos),
             Self::MagicWord(pos) => write!(f, "no \"OFF\" detected at {}", pos),
+            Self::EmptyElement(pos) => write!(f, "element with no subelements at {}", pos),
+            Self::IndexOutOfBounds {
+                pos,
+                rank,
+                index,
+                len,
+            } => write!(
+                f,
+                "index {} at {} is out of bounds: only {} {} exist",
+                index,
+                pos,
+                len,
+                element_name(*rank).to_lowercase()
+            ),
+            Self::Invalid(err) => write!(f, "invalid polytope: {}", err),
         }
     }
 }
@@ -57,6 +94,69 @@ impl std::error::Error for OffParseError {}
 /// The result of parsing an OFF file.
 pub type OffParseResult<T> = Result<T, OffParseError>;
 
+/// A color stored in an OFF file, as its red, green, blue, and alpha
+/// components, each between 0 and 1.
+pub type OffColor = [Real; 4];
+
+/// The colors stored in an OFF file, if any.
+#[derive(Clone, Debug, Default)]
+pub struct OffColors {
+    /// The color of each vertex, read when the file has a `COFF` header.
+    pub vertices: Vec<Option<OffColor>>,
+
+    /// The color of each face, read from the values after its vertex indices.
+    /// In the polygonal case, these are the colors of the components.
+    pub faces: Vec<Option<OffColor>>,
+}
+
+/// Attempts to read a color from the values after the indices of an element,
+/// or after the coordinates of a vertex. Following the Geomview convention,
+/// colors may be given as three or four components, either as floats between
+/// 0 and 1, or as integers between 0 and 255.
+fn parse_color(tokens: &[Token<'_>]) -> Option<OffColor> {
+    if tokens.len() != 3 && tokens.len() != 4 {
+        return None;
+    }
+
+    let mut color = [1.0; 4];
+    for (c, token) in color.iter_mut().zip(tokens) {
+        *c = token.parse().ok()?;
+    }
+
+    // Integer components are scaled into the unit interval.
+    if tokens.iter().all(|token| !token.slice.contains('.'))
+        && color.iter().take(tokens.len()).any(|&c| c > 1.0)
+    {
+        for c in color.iter_mut().take(tokens.len()) {
+            *c /= 255.0;
+        }
+    }
+
+    Some(color)
+}
+
+/// The information stored in the magic word of an OFF file, which has the form
+/// `(ST)?C?N?(4?n|-?\d+)?OFF`.
+#[derive(Clone, Copy, Debug, Default)]
+struct Header {
+    /// The dimension of the polytope, or `None` if it's stored in the next
+    /// token, as in the `nOFF` variant.
+    dim: Option<isize>,
+
+    /// Whether the dimension in the next token should be incremented by one,
+    /// as in the `4nOFF` variant.
+    homogeneous: bool,
+
+    /// Whether every vertex is followed by a normal vector.
+    normals: bool,
+
+    /// Whether every vertex is followed by a color.
+    colors: bool,
+
+    /// Whether every vertex is followed by texture coordinates.
+    texture: bool,
+}
+
 /// Gets the name for an element with a given rank.
 fn element_name(rank: usize) -> String {
     match ELEMENT_NAMES.get(rank) {
@@ -94,22 +194,39 @@ impl<'a> Token<'a> {
             .map_err(|_| OffParseError::Parsing(self.pos))
     }
 
-    /// Reads the rank from a token of the form `(-?\d+)?OFF`. If the rank is
-    /// omitted, we use a default value of 4.
-    fn rank(&self) -> OffParseResult<usize> {
-        let rank = self
+    /// Reads the header from a token of the form `(ST)?C?N?(4?n|-?\d+)?OFF`.
+    /// If the dimension is omitted, we use a default value of 3.
+    fn header(&self) -> OffParseResult<Header> {
+        let mut dim = self
             .slice
             .strip_suffix("OFF")
             .ok_or(OffParseError::MagicWord(self.pos))?;
+        let mut header = Header::default();
 
-        if rank.is_empty() {
-            Ok(4)
-        } else {
-            match rank.parse::<isize>() {
-                Ok(r) => Ok((r + 1) as usize),
-                Err(_) => Err(OffParseError::Rank(self.pos)),
-            }
+        if let Some(rest) = dim.strip_prefix("ST") {
+            header.texture = true;
+            dim = rest;
+        }
+        if let Some(rest) = dim.strip_prefix('C') {
+            header.colors = true;
+            dim = rest;
         }
+        if let Some(rest) = dim.strip_prefix('N') {
+            header.normals = true;
+            dim = rest;
+        }
+
+        match dim {
+            "" => header.dim = Some(3),
+            "n" => {}
+            "4n" => header.homogeneous = true,
+            _ => match dim.parse::<isize>() {
+                Ok(d) => header.dim = Some(d),
+                Err(_) => return Err(OffParseError::Rank(self.pos)),
+            },
+        }
+
+        Ok(header)
     }
 }
 
@@ -193,12 +310,64 @@ impl<'a> TokenIter<'a> {
         })
     }
 
+    /// Reads all remaining tokens on the current line, which usually contain
+    /// data we don't need, like colors.
+    fn rest_of_line(&mut self) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+
+        while self.position.column != 0 {
+            let pos = self.position;
+            match self.try_next() {
+                Some(OffNext::Token(slice)) => tokens.push(Token { slice, pos }),
+                Some(OffNext::Garbage) => {}
+                None => break,
+            }
+        }
+
+        tokens
+    }
+
     /// Reads and parses the next token from the OFF file.
     pub fn parse_next<U: FromStr>(&mut self) -> OffParseResult<U> {
         self.next()
             .ok_or(OffParseError::UnexpectedEnding(self.position))?
             .parse()
     }
+
+    /// Reads and parses the next token from the OFF file as the index of an
+    /// element of a given rank, and checks that it's in bounds.
+    fn parse_index(&mut self, rank: usize, len: usize) -> OffParseResult<usize> {
+        let token = self
+            .next()
+            .ok_or(OffParseError::UnexpectedEnding(self.position))?;
+        let index = token.parse()?;
+
+        if index < len {
+            Ok(index)
+        } else {
+            Err(OffParseError::IndexOutOfBounds {
+                pos: token.pos,
+                rank,
+                index,
+                len,
+            })
+        }
+    }
+
+    /// Reads and parses the number of subelements of an element, which must
+    /// be positive.
+    fn parse_sub_count(&mut self) -> OffParseResult<usize> {
+        let token = self
+            .next()
+            .ok_or(OffParseError::UnexpectedEnding(self.position))?;
+        let count = token.parse()?;
+
+        if count == 0 {
+            Err(OffParseError::EmptyElement(token.pos))
+        } else {
+            Ok(count)
+        }
+    }
 }
 
 impl<'a> Iterator for TokenIter<'a> {
@@ -222,14 +391,32 @@ pub struct OffReader<'a> {
 
     /// The underlying abstract polytope.
     abs: AbstractBuilder,
+
+    /// The information in the header of the file.
+    header: Header,
+
+    /// The colors read from the file.
+    colors: OffColors,
+
+    /// The options used while reading the file.
+    options: OffOptions,
 }
 
 impl<'a> OffReader<'a> {
     /// Initializes a new reader from a source OFF file.
     pub fn new(src: &'a str) -> Self {
+        Self::with_options(src, Default::default())
+    }
+
+    /// Initializes a new reader from a source OFF file, with a given set of
+    /// options.
+    pub fn with_options(src: &'a str, options: OffOptions) -> Self {
         Self {
             iter: TokenIter::new(src),
             abs: AbstractBuilder::new(),
+            header: Header::default(),
+            colors: OffColors::default(),
+            options,
         }
     }
 
@@ -243,9 +430,31 @@ impl<'a> OffReader<'a> {
         self.iter.next()
     }
 
-    /// Reads the first token from the OFF file, returns the polytope's rank.
+    /// Reads the header from the OFF file, returns the polytope's rank.
     fn rank(&mut self) -> OffParseResult<usize> {
-        self.next().ok_or(OffParseError::Empty)?.rank()
+        self.header = self.next().ok_or(OffParseError::Empty)?.header()?;
+
+        let dim = match self.header.dim {
+            Some(dim) => dim,
+            None => {
+                let token = self
+                    .next()
+                    .ok_or(OffParseError::UnexpectedEnding(self.iter.position))?;
+                let dim: isize = token.parse().map_err(|_| OffParseError::Rank(token.pos))?;
+
+                if self.header.homogeneous {
+                    dim + 1
+                } else {
+                    dim
+                }
+            }
+        };
+
+        if dim < -1 {
+            return Err(OffParseError::Rank(self.iter.position));
+        }
+
+        Ok((dim + 1) as usize)
     }
 
     /// Gets the number of elements from the OFF file from rank 1 up to rank
@@ -278,7 +487,7 @@ impl<'a> OffReader<'a> {
         &mut self,
         count: usize,
         dim: usize,
-    ) -> OffParseResult<Vec<Point<f64>>> {
+    ) -> OffParseResult<Vec<Point<Real>>> {
         // Reads all vertices.
         let mut vertices = Vec::with_capacity(count);
 
@@ -287,9 +496,30 @@ impl<'a> OffReader<'a> {
             let mut v = Vec::with_capacity(dim);
 
             for _ in 0..dim {
-                v.push(self.iter.parse_next()?);
+                let c: Real = self.iter.parse_next()?;
+                v.push(match self.options.snap_eps {
+                    Some(eps) => snap(c, eps),
+                    None => c,
+                });
             }
 
+            // Reads the color of the vertex, skipping normals and texture
+            // coordinates.
+            let color = if self.header.normals || self.header.colors || self.header.texture {
+                let extra = self.iter.rest_of_line();
+                let start = if self.header.normals { dim } else { 0 };
+                let end = extra.len().saturating_sub(if self.header.texture { 2 } else { 0 });
+
+                if self.header.colors && start < end {
+                    parse_color(&extra[start..end])
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            self.colors.vertices.push(color);
             vertices.push(v.into());
         }
 
@@ -302,6 +532,7 @@ impl<'a> OffReader<'a> {
     fn parse_edges_and_faces(
         &mut self,
         rank: usize,
+        num_vertices: usize,
         num_edges: usize,
         num_faces: usize,
     ) -> OffParseResult<(SubelementList, SubelementList)> {
@@ -311,13 +542,13 @@ impl<'a> OffReader<'a> {
 
         // Add each face to the element list.
         for _ in 0..num_faces {
-            let face_sub_num = self.iter.parse_next()?;
+            let face_sub_num = self.iter.parse_sub_count()?;
             let mut face = Subelements::new();
             let mut face_verts = Vec::with_capacity(face_sub_num + 1);
 
             // Reads all vertices of the face.
             for _ in 0..face_sub_num {
-                face_verts.push(self.iter.parse_next()?);
+                face_verts.push(self.iter.parse_index(1, num_vertices)?);
             }
 
             // We add the first vertex to the end for simplicity.
@@ -349,10 +580,9 @@ impl<'a> OffReader<'a> {
                 faces.push(face);
             }
 
-            // Goes to the end of the line in order to ignore things like colour info.
-            if self.iter.position.column != 0 {
-                self.iter.comment = true;
-            }
+            // Reads the colour info at the end of the line, if any.
+            let extra = self.iter.rest_of_line();
+            self.colors.faces.push(parse_color(&extra));
         }
 
         // If this is a polygon, we add a single maximal element as a face.
@@ -369,26 +599,30 @@ impl<'a> OffReader<'a> {
         Ok((edges, faces))
     }
 
-    /// Parses the next set of d-elements from the OFF file.
-    fn parse_els(&mut self, num_el: usize) -> OffParseResult<SubelementList> {
+    /// Parses the next set of d-elements from the OFF file, given the number
+    /// of elements of the previous rank.
+    fn parse_els(
+        &mut self,
+        rank: usize,
+        num_el: usize,
+        num_subs: usize,
+    ) -> OffParseResult<SubelementList> {
         let mut els_subs = SubelementList::with_capacity(num_el);
 
         // Adds every d-element to the element list.
         for _ in 0..num_el {
-            let el_sub_num = self.iter.parse_next()?;
+            let el_sub_num = self.iter.parse_sub_count()?;
             let mut subs = Subelements::with_capacity(el_sub_num);
 
             // Reads all sub-elements of the d-element.
             for _ in 0..el_sub_num {
-                subs.push(self.iter.parse_next()?);
+                subs.push(self.iter.parse_index(rank - 1, num_subs)?);
             }
 
             els_subs.push(subs);
 
             // Goes to the end of the line in order to ignore things like colour info.
-            if self.iter.position.column != 0 {
-                self.iter.comment = true;
-            }
+            self.iter.rest_of_line();
         }
 
         Ok(els_subs)
@@ -405,15 +639,21 @@ impl<'a> OffReader<'a> {
     }*/
 
     /// Builds a concrete polytope from the OFF reader.
-    pub fn build(mut self) -> OffParseResult<Concrete> {
+    pub fn build(self) -> OffParseResult<Concrete> {
+        self.build_with_colors().map(|(poly, _)| poly)
+    }
+
+    /// Builds a concrete polytope from the OFF reader, together with the
+    /// vertex and face colors stored in the file.
+    pub fn build_with_colors(mut self) -> OffParseResult<(Concrete, OffColors)> {
         // Reads the rank of the polytope.
         let rank = self.rank()?;
 
         // Deals with dumb degenerate cases.
         match rank {
-            0 => return Ok(Concrete::nullitope()),
-            1 => return Ok(Concrete::point()),
-            2 => return Ok(Concrete::dyad()),
+            0 => return Ok((Concrete::nullitope(), self.colors)),
+            1 => return Ok((Concrete::point(), self.colors)),
+            2 => return Ok((Concrete::dyad(), self.colors)),
             _ => {}
         }
 
@@ -427,15 +667,19 @@ impl<'a> OffReader<'a> {
         self.abs.push_vertices(vertices.len());
 
         // Reads edges and faces.
+        let mut num_subs = vertices.len();
         if rank >= 3 {
-            let (edges, faces) = self.parse_edges_and_faces(rank, num_elems[1], num_elems[2])?;
+            let (edges, faces) =
+                self.parse_edges_and_faces(rank, num_subs, num_elems[1], num_elems[2])?;
+            num_subs = faces.len();
             self.abs.push(edges);
             self.abs.push(faces);
         }
 
         // Adds all higher elements.
-        for &num_el in num_elems.iter().take(rank - 1).skip(3) {
-            let subelements = self.parse_els(num_el)?;
+        for (r, &num_el) in num_elems.iter().enumerate().take(rank - 1).skip(3) {
+            let subelements = self.parse_els(r + 1, num_el, num_subs)?;
+            num_subs = subelements.len();
             self.abs.push(subelements);
         }
 
@@ -445,9 +689,16 @@ impl<'a> OffReader<'a> {
         }
 
         // Builds the concrete polytope.
-
-        // Safety: TODO this isn't actually safe. We need to do some checking.
-        Ok(Concrete::new(vertices, unsafe { self.abs.build() }))
+        self.abs
+            .ranks()
+            .is_valid()
+            .map_err(OffParseError::Invalid)?;
+
+        // Safety: we've just checked that the ranks form a valid polytope.
+        Ok((
+            Concrete::new(vertices, unsafe { self.abs.build() }),
+            self.colors,
+        ))
     }
 }
 
@@ -479,19 +730,39 @@ impl Concrete {
     }
 }*/
 
-/// A set of options to be used when saving the OFF file.
+/// A set of options to be used when reading or saving an OFF file.
 #[derive(Clone, Copy)]
 pub struct OffOptions {
     /// Whether the OFF file should have comments specifying each face type.
     pub comments: bool,
+
+    /// The number of decimal places with which coordinates are written, or
+    /// `None` to write them at full precision.
+    pub decimals: Option<usize>,
+
+    /// The spacing of the grid that coordinates are rounded to when read, or
+    /// `None` to read them exactly.
+    pub snap_eps: Option<Real>,
 }
 
 impl Default for OffOptions {
     fn default() -> Self {
-        OffOptions { comments: true }
+        OffOptions {
+            comments: true,
+            decimals: None,
+            snap_eps: None,
+        }
     }
 }
 
+/// Rounds a coordinate to the nearest multiple of `eps`.
+fn snap(c: Real, eps: Real) -> Real {
+    // Dividing by the reciprocal avoids errors like 0.49999999999999994 for
+    // 500000 times 1e-6.
+    let scale = eps.recip();
+    (c * scale).round() / scale
+}
+
 /// An error while writing an OFF file.
 #[derive(Clone, Copy, Debug)]
 pub enum OffWriteError {
@@ -691,7 +962,10 @@ impl<'a> OffWriter<'a> {
         // Adds the coordinates.
         for v in &self.poly.vertices {
             for c in v {
-                self.push_to_str(c);
+                match self.options.decimals {
+                    Some(decimals) => self.push_str(format!("{:.*}", decimals, c)),
+                    None => self.push_to_str(c),
+                }
                 self.push(' ');
             }
             self.push('\n');
@@ -726,8 +1000,8 @@ impl<'a> OffWriter<'a> {
         } else {
             for (idx, face) in self.poly[3].iter().enumerate() {
                 self.push_to_str(face.subs.len());
-                let mut cycles =
-                    CycleList::from_edges(face.subs.iter().map(|&i| &self.poly[(2, i)].subs));
+                let edges: Vec<_> = face.subs.iter().map(|&i| &self.poly[(2, i)].subs).collect();
+                let mut cycles = CycleList::from_face_edges(&edges);
 
                 if cycles.len() > 1 {
                     return Err(OffWriteError::CompoundFace { idx });
@@ -850,12 +1124,20 @@ type OffSaveResult<T> = Result<T, OffSaveError>;
 
 //todo: put this in its own trait
 impl Concrete {
+    /// Reads a polytope from an OFF file, with a given set of options.
+    pub fn from_off_with(src: &str, options: OffOptions) -> OffParseResult<Self> {
+        OffReader::with_options(src, options).build()
+    }
+
     /// Converts a polytope into an OFF file.
     pub fn to_off(&self, options: OffOptions) -> OffWriteResult<String> {
         let mut fixed = self.clone();
         fixed.untangle_faces();
         fixed.element_sort();
 
+        // Orientable polyhedra are written with consistently wound faces.
+        fixed.orient();
+
         OffWriter::new(&fixed, options).build()
     }
 
@@ -870,8 +1152,11 @@ impl Concrete {
 mod tests {
     use super::*;
     use crate::file::FromFile;
+    use crate::float::Float;
     use crate::test;
 
+    use approx::abs_diff_eq;
+
     /// Tests a particular OFF file.
     fn test_off_file<I: IntoIterator<Item = usize> + Clone>(src: &str, element_counts: I) {
         // Checks that element counts match up.
@@ -941,6 +1226,65 @@ mod tests {
         test_off!("comments", [1, 4, 6, 4, 1])
     }
 
+    /// Checks that blank lines, trailing comments, and face colors are
+    /// correctly parsed.
+    #[test]
+    fn stella_nums() {
+        test_off!("stella", [1, 8, 12, 6, 1])
+    }
+
+    /// Checks that the `COFF` header is supported.
+    #[test]
+    fn coff_nums() {
+        test_off!("coff", [1, 4, 6, 4, 1])
+    }
+
+    /// Checks that the `STNOFF` header is supported.
+    #[test]
+    fn stoff_nums() {
+        test_off!("stoff", [1, 4, 6, 4, 1])
+    }
+
+    /// Checks that the `nOFF` header is supported.
+    #[test]
+    fn noff_nums() {
+        test_off!("noff", [1, 5, 10, 10, 5, 1])
+    }
+
+    /// Checks that the `4nOFF` header is supported.
+    #[test]
+    fn homogeneous() {
+        let poly = Concrete::from_off("4nOFF 1\n3 1\n0 0\n1 0\n0 1\n3 0 1 2").unwrap();
+        test(&poly, [1, 3, 3, 1]);
+    }
+
+    /// Checks that face colors are read.
+    #[test]
+    fn face_colors() {
+        let (_, colors) = OffReader::new(include_str!("stella.off"))
+            .build_with_colors()
+            .unwrap();
+
+        assert_eq!(colors.faces.len(), 6);
+        assert_eq!(colors.faces[0], Some([0.8, 0.2, 0.2, 1.0]));
+        assert_eq!(colors.faces[1], Some([0.8, 0.2, 0.2, 1.0]));
+        assert_eq!(colors.faces[2], Some([0.8, 0.2, 0.2, 1.0]));
+        assert_eq!(colors.faces[3], None);
+        assert_eq!(colors.faces[4], None);
+        assert_eq!(colors.faces[5], Some([0.2, 0.2, 0.8, 1.0]));
+    }
+
+    /// Checks that vertex colors are read.
+    #[test]
+    fn vertex_colors() {
+        let (_, colors) = OffReader::new(include_str!("coff.off"))
+            .build_with_colors()
+            .unwrap();
+
+        assert_eq!(colors.vertices.len(), 4);
+        assert_eq!(colors.vertices[1], Some([0.0, 1.0, 0.0, 1.0]));
+    }
+
     /// Attempts to parse an OFF file, unwraps it.
     fn unwrap_off(src: &str) {
         Concrete::from_off(src).unwrap();
@@ -973,4 +1317,122 @@ mod tests {
     fn parse() {
         unwrap_off("OFF\n10 foo bar")
     }
+
+    /// A file that ends too early should fail.
+    #[test]
+    #[should_panic(expected = "UnexpectedEnding")]
+    fn unexpected_ending() {
+        unwrap_off("OFF\n4 4 6\n1 1 1\n1 -1 -1")
+    }
+
+    /// A file with a face without vertices should fail.
+    #[test]
+    #[should_panic(expected = "EmptyElement(Position { row: 3, column: 0 })")]
+    fn empty_element() {
+        unwrap_off("2OFF\n3 1\n0 0 1 0 0 1\n0")
+    }
+
+    /// A file with a face referencing a nonexistent vertex should fail.
+    #[test]
+    #[should_panic(
+        expected = "IndexOutOfBounds { pos: Position { row: 6, column: 6 }, rank: 1, index: 4, len: 4 }"
+    )]
+    fn vertex_out_of_bounds() {
+        unwrap_off("OFF\n4 4 6\n1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1\n3 0 1 4\n3 3 0 2\n3 0 1 3\n3 3 1 2")
+    }
+
+    /// A file with a cell referencing a nonexistent face should fail.
+    #[test]
+    #[should_panic(expected = "IndexOutOfBounds")]
+    fn face_out_of_bounds() {
+        let src = include_str!("pen.off").replace("4 3 6 8 9", "4 3 6 8 10");
+        unwrap_off(&src)
+    }
+
+    /// A file where an edge belongs to a single face should fail.
+    #[test]
+    #[should_panic(expected = "Invalid(Dyadic")]
+    fn open_surface() {
+        unwrap_off("OFF\n4 3 6\n1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1\n3 0 1 2\n3 3 0 2\n3 0 1 3")
+    }
+
+    /// Checks that OFF files written from polytopes read back into the same
+    /// polytopes.
+    #[test]
+    fn round_trip() {
+        for poly in [
+            Concrete::polygon(7),
+            Concrete::cube(),
+            Concrete::simplex(5),
+            Concrete::orthoplex(6),
+            Concrete::polygon(3).duoprism(&Concrete::polygon(5)),
+        ] {
+            let src = poly.to_off(Default::default()).unwrap();
+            let read = Concrete::from_off(&src).unwrap();
+            test(&read, poly.el_count_iter());
+            assert_eq!(read.vertices, poly.vertices);
+            assert!(read.is_valid_polytope());
+        }
+    }
+
+    /// Checks that coordinates can be written with a fixed number of
+    /// decimals, and that they're read back up to rounding.
+    #[test]
+    fn decimals() {
+        let options = OffOptions {
+            comments: false,
+            decimals: Some(3),
+            ..Default::default()
+        };
+
+        let cube = Concrete::cube();
+        let src = cube.to_off(options).unwrap();
+        assert!(src.lines().any(|line| line == "0.500 0.500 0.500 "));
+        assert_eq!(Concrete::from_off(&src).unwrap().vertices, cube.vertices);
+
+        let tet = Concrete::simplex(4);
+        let read = Concrete::from_off(&tet.to_off(options).unwrap()).unwrap();
+        test(&read, [1, 4, 6, 4, 1]);
+        for (v, w) in read.vertices.iter().zip(&tet.vertices) {
+            assert!((v - w).amax() <= 5e-4);
+        }
+    }
+
+    /// Checks that slightly perturbed coordinates are snapped back onto the
+    /// grid, and that they're kept as they are by default.
+    #[test]
+    fn snap_eps() {
+        let src = "OFF\n8 6 12\n\
+            0.4999995 0.5 0.5000004\n0.5 0.5 -0.5\n0.5 -0.4999996 0.5\n\
+            0.5 -0.5 -0.5\n-0.5 0.5 0.5\n-0.5000003 0.5 -0.5\n-0.5 -0.5 0.5\n\
+            -0.5 -0.5 -0.5\n\
+            4 0 1 3 2\n4 4 5 7 6\n4 0 1 5 4\n4 2 3 7 6\n4 0 2 6 4\n4 1 3 7 5";
+
+        let options = OffOptions {
+            snap_eps: Some(1e-6),
+            ..Default::default()
+        };
+        let cube = Concrete::from_off_with(src, options).unwrap();
+        test(&cube, [1, 8, 12, 6, 1]);
+        for v in &cube.vertices {
+            assert!(v.iter().all(|&c| c == 0.5 || c == -0.5), "{}", v);
+        }
+
+        let cube = Concrete::from_off(src).unwrap();
+        assert!(abs_diff_eq!(
+            cube.vertices[0][0],
+            0.4999995,
+            epsilon = Real::EPS
+        ));
+    }
+
+    /// Checks the error message for indices out of bounds.
+    #[test]
+    fn out_of_bounds_message() {
+        let err = Concrete::from_off("2OFF\n3 1\n0 0 1 0 0 1\n3 0 1 12").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "index 12 at row 4, column 7 is out of bounds: only 3 vertices exist"
+        );
+    }
 }
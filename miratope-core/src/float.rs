@@ -1,6 +1,18 @@
 //! Defines a [`Float`] trait, which allows Miratope to be generic over `f32` or
 //! `f64`.
 
+/// The floating point type used by [`Concrete`](crate::conc::Concrete)
+/// polytopes and everything built from them. This is `f64`, unless the `f32`
+/// feature is enabled.
+#[cfg(not(feature = "f32"))]
+pub type Real = f64;
+
+/// The floating point type used by [`Concrete`](crate::conc::Concrete)
+/// polytopes and everything built from them. This is `f32`, since the `f32`
+/// feature is enabled.
+#[cfg(feature = "f32")]
+pub type Real = f32;
+
 /// A trait containing the constants associated to each floating point type.
 ///
 /// This trait is only meant to be implemented for `f32` and `f64`.
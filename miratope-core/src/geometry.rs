@@ -1,16 +1,20 @@
 //! Contains structs and methods to faciliate geometry in *n*-dimensional space.
+//!
+//! Everything here is generic over the [`Float`] type of its coordinates, so
+//! that precision may be traded for speed. The type defaults to [`Real`], the
+//! type used by [`Concrete`] polytopes.
 
 /// A point in *n*-dimensional space.
-pub type Point<T> = nalgebra::DVector<T>;
+pub type Point<T = Real> = nalgebra::DVector<T>;
 
 /// A vector in *n*-dimensional space.
-pub type Vector<T> = Point<T>;
+pub type Vector<T = Real> = Point<T>;
 
 /// A non-owned form of [`Vector`].
-pub type VectorSlice<'a, T> = nalgebra::DVectorSlice<'a, T>;
+pub type VectorSlice<'a, T = Real> = nalgebra::DVectorSlice<'a, T>;
 
 /// An *n* by *n* matrix.
-pub type Matrix<T> = nalgebra::DMatrix<T>;
+pub type Matrix<T = Real> = nalgebra::DMatrix<T>;
 
 use std::{
     borrow::Cow,
@@ -18,7 +22,7 @@ use std::{
 };
 
 use crate::{
-    float::Float,
+    float::{Float, Real},
     ElementMap, conc::Concrete, abs::Ranked, Polytope,
 };
 
@@ -32,7 +36,7 @@ use vec_like::VecLike;
 /// where the hypersphere is used to reciprocate polytopes. For convenience, we
 /// allow the hypersphere to have a negative squared radius, which results in
 /// the dualized polytope being reflected about its center.
-pub struct Hypersphere<T: Float> {
+pub struct Hypersphere<T: Float = Real> {
     /// The center of the hypersphere.
     pub center: Point<T>,
 
@@ -91,6 +95,32 @@ impl<T: Float> Hypersphere<T> {
     pub fn reciprocate(&self, mut p: Point<T>) -> Option<Point<T>> {
         self.reciprocate_mut(&mut p).then(|| p)
     }
+
+    /// Intersects the hypersphere with a hyperplane. The result is a
+    /// hypersphere of one dimension less, living in the same ambient space.
+    /// Returns `None` if the hyperplane misses the hypersphere, or if the
+    /// hypersphere has a negative squared radius.
+    pub fn intersect(&self, plane: &Hyperplane<T>) -> Option<Self> {
+        if self.squared_radius < T::ZERO {
+            return None;
+        }
+
+        let distance = plane.distance(&self.center);
+        let squared_radius = self.squared_radius - distance * distance;
+
+        if squared_radius < -T::EPS {
+            None
+        } else {
+            Some(Self::with_squared_radius(
+                plane.project(&self.center),
+                if squared_radius < T::ZERO {
+                    T::ZERO
+                } else {
+                    squared_radius
+                },
+            ))
+        }
+    }
 }
 
 /// Represents an (affine) subspace, passing through a given point and generated
@@ -99,7 +129,7 @@ impl<T: Float> Hypersphere<T> {
 /// TODO: Use asserts to guarantee that the basis is an orthogonal basis of unit
 /// vectors.
 #[derive(Clone,)]
-pub struct Subspace<T: Float> {
+pub struct Subspace<T: Float = Real> {
     /// An orthogonal basis for the subspace, defined by unit vectors.
     pub basis: Vec<Vector<T>>,
 
@@ -252,6 +282,11 @@ impl<T: Float> Subspace<T> {
         abs_diff_eq!(self.distance(p), T::ZERO, epsilon = T::EPS)
     }
 
+    /// Reflects a point across the subspace.
+    pub fn reflect(&self, p: &Point<T>) -> Point<T> {
+        self.project(p) * T::TWO - p
+    }
+
     /// Computes a normal vector to the subspace, so that the specified point is
     /// left out of it. Returns `None` if the point given lies on the subspace.
     pub fn normal(&self, p: &Point<T>) -> Option<Vector<T>> {
@@ -267,14 +302,14 @@ impl<T: Float> Subspace<T> {
 
 impl Concrete {
     /// Computes the affine hull of an element.
-    pub fn affine_hull(&self, rank: usize, idx: usize) -> Subspace<f64> {
+    pub fn affine_hull(&self, rank: usize, idx: usize) -> Subspace<Real> {
         Subspace::from_points(
             &mut self.element(rank, idx).unwrap().vertices.iter(),
         )
     }
 
     /// Computes the affine hulls of all elements and puts them in an `ElementMap`.
-    pub fn element_map_affine_hulls(&self) -> ElementMap<Subspace<f64>> {
+    pub fn element_map_affine_hulls(&self) -> ElementMap<Subspace<Real>> {
         let mut element_map = ElementMap::new();
         for r in 1..self.rank() {
             element_map.push(Vec::new());
@@ -287,7 +322,7 @@ impl Concrete {
 } 
 
 /// Represents an (oriented) hyperplane together with a normal vector.
-pub struct Hyperplane<T: Float> {
+pub struct Hyperplane<T: Float = Real> {
     /// The underlying subspace associated to the hyperplane.
     pub subspace: Subspace<T>,
 
@@ -312,6 +347,23 @@ impl<T: Float> Hyperplane<T> {
         Self { subspace, normal }
     }
 
+    /// Generates an oriented hyperplane through a set of points, so that a
+    /// given point outside of it has positive distance. Returns `None` if the
+    /// points don't span a hyperplane, or if the outer point lies on it.
+    pub fn from_points(points: &[Point<T>], outer: &Point<T>) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+
+        let subspace = Subspace::from_points(points.iter());
+        if !subspace.is_hyperplane() {
+            return None;
+        }
+
+        let normal = subspace.normal(outer)?;
+        Some(Self { subspace, normal })
+    }
+
     /// Projects a point onto the hyperplane.
     pub fn project(&self, p: &Point<T>) -> Point<T> {
         self.subspace.project(p)
@@ -334,21 +386,109 @@ impl<T: Float> Hyperplane<T> {
         abs_diff_eq!(self.distance(p), T::ZERO, epsilon = T::EPS)
     }
 
-    /// Returns the intersection of itself and a line segment, or `None` if it
-    /// doesn't exist.
-    pub fn intersect(&self, line: Segment<'_, T>) -> Option<Point<T>> {
+    /// Reflects a point across the hyperplane. The signed distance of the
+    /// reflected point is the negative of the original's.
+    pub fn reflect(&self, p: &Point<T>) -> Point<T> {
+        p - &self.normal * (self.distance(p) * T::TWO)
+    }
+
+    /// Returns the intersection of itself and a line segment. Endpoints within
+    /// [`Float::EPS`] of the hyperplane are considered to lie on it.
+    pub fn intersect(&self, line: Segment<'_, T>) -> SegmentIntersection<T> {
         let d0 = self.distance(line.0);
         let d1 = self.distance(line.1);
+        let on0 = d0.fabs() < T::EPS;
+        let on1 = d1.fabs() < T::EPS;
 
         // This right here is some really sensitive code. If we screw up
         // handling the edge cases, cross-sections through elements will crash.
-        (abs_diff_ne!(d0, d1, epsilon = T::EPS) && (d0 < -T::EPS) != (d1 < -T::EPS))
-            .then(|| line.at(d1 / (d1 - d0)))
+        // An endpoint on the hyperplane is returned as is, so that every edge
+        // through a vertex gives the exact same point, and the cross-section
+        // stays watertight.
+        match (on0, on1) {
+            (true, true) => SegmentIntersection::Whole,
+            (true, false) => SegmentIntersection::Point(line.0.clone()),
+            (false, true) => SegmentIntersection::Point(line.1.clone()),
+            (false, false) => {
+                if (d0 < T::ZERO) == (d1 < T::ZERO) {
+                    SegmentIntersection::None
+                } else {
+                    // Both distances are at least EPS in absolute value and
+                    // have opposite signs, so this can't divide by zero, and
+                    // the parameter is always between 0 and 1.
+                    SegmentIntersection::Point(line.at(d1 / (d1 - d0)))
+                }
+            }
+        }
+    }
+}
+
+/// The intersection of a [`Hyperplane`] and a [`Segment`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SegmentIntersection<T: Float = Real> {
+    /// The segment lies strictly on one side of the hyperplane.
+    None,
+
+    /// The segment crosses or touches the hyperplane at a single point.
+    Point(Point<T>),
+
+    /// The segment lies on the hyperplane.
+    Whole,
+}
+
+/// Returns the arithmetic mean of a set of points.
+///
+/// # Panics
+/// This function will panic if the set of points is empty, or if the points
+/// have different dimensions.
+pub fn centroid<T: Float>(points: &[Point<T>]) -> Point<T> {
+    let dim = points
+        .first()
+        .expect("The centroid of an empty point array is undefined!")
+        .len();
+    let mut sum = Point::zeros(dim);
+
+    for p in points {
+        assert_eq!(p.len(), dim, "All points must have the same dimension!");
+        sum += p;
+    }
+
+    sum / T::usize(points.len())
+}
+
+/// Returns the least and greatest corners of the axis-aligned box containing a
+/// set of points.
+///
+/// # Panics
+/// This function will panic if the set of points is empty, or if the points
+/// have different dimensions.
+pub fn bounding_box<T: Float>(points: &[Point<T>]) -> (Point<T>, Point<T>) {
+    let first = points
+        .first()
+        .expect("The bounding box of an empty point array is undefined!");
+    let dim = first.len();
+    let mut min = first.clone();
+    let mut max = first.clone();
+
+    for p in points {
+        assert_eq!(p.len(), dim, "All points must have the same dimension!");
+
+        for i in 0..dim {
+            if p[i] < min[i] {
+                min[i] = p[i];
+            }
+            if p[i] > max[i] {
+                max[i] = p[i];
+            }
+        }
     }
+
+    (min, max)
 }
 
 /// Represents a line segment between two points.
-pub struct Segment<'a, T: Float>(pub &'a Point<T>, pub &'a Point<T>);
+#[derive(Clone, Copy)]
+pub struct Segment<'a, T: Float = Real>(pub &'a Point<T>, pub &'a Point<T>);
 
 impl<'a, T: Float> Segment<'a, T> {
     /// Returns the point at a certain position along the line. If `t` is
@@ -356,6 +496,102 @@ impl<'a, T: Float> Segment<'a, T> {
     pub fn at(&self, t: T) -> Point<T> {
         self.0 * t + self.1 * (T::ONE - t)
     }
+
+    /// Returns the point on the line segment closest to a given point.
+    pub fn closest(&self, p: &Point<T>) -> Point<T> {
+        let u = self.1 - self.0;
+        let len = u.dot(&u);
+
+        if len < T::EPS * T::EPS {
+            self.0.clone()
+        } else {
+            let t = (p - self.0).dot(&u) / len;
+            if t < T::ZERO {
+                self.0.clone()
+            } else if t > T::ONE {
+                self.1.clone()
+            } else {
+                self.0 + u * t
+            }
+        }
+    }
+
+    /// Returns the distance from a point to the line segment.
+    pub fn distance(&self, p: &Point<T>) -> T {
+        (p - self.closest(p)).norm()
+    }
+
+    /// Returns the tolerance for comparing points on the line segment. This is
+    /// [`Float::EPS`] times its length, so that it works at any scale.
+    fn eps(&self) -> T {
+        T::EPS * (self.1 - self.0).norm()
+    }
+
+    /// Returns whether a point lies on the line segment, up to a tolerance
+    /// proportional to its length.
+    pub fn contains(&self, p: &Point<T>) -> bool {
+        self.distance(p) <= self.eps()
+    }
+
+    /// Returns the intersection point of two line segments, or `None` if they
+    /// don't intersect. Parallel segments only intersect when they share a
+    /// single point, in which case it's returned.
+    pub fn intersect(&self, other: &Segment<'_, T>) -> Option<Point<T>> {
+        let u = self.1 - self.0;
+        let v = other.1 - other.0;
+        let w = self.0 - other.0;
+
+        let a = u.dot(&u);
+        let b = u.dot(&v);
+        let c = v.dot(&v);
+        let d = u.dot(&w);
+        let e = v.dot(&w);
+        let denom = a * c - b * b;
+
+        // Points are compared up to a tolerance proportional to the length of
+        // the longest segment.
+        let max_sq = if a > c { a } else { c };
+        let eps = T::EPS * max_sq.fsqrt();
+
+        // Parallel segments. The denominator is the product of both squared
+        // lengths and the squared sine of their angle, so we compare the
+        // latter against the epsilon, whatever the scale of the segments.
+        if denom.fabs() <= T::EPS * a * c {
+            let mut touching: Vec<&Point<T>> = Vec::new();
+            for p in [self.0, self.1] {
+                if other.distance(p) <= eps {
+                    touching.push(p);
+                }
+            }
+            for p in [other.0, other.1] {
+                if self.distance(p) <= eps {
+                    touching.push(p);
+                }
+            }
+
+            let first = *touching.first()?;
+            return touching
+                .iter()
+                .all(|p| (*p - first).norm() <= eps)
+                .then(|| first.clone());
+        }
+
+        // The parameters of the closest points between both lines.
+        let s = (b * e - c * d) / denom;
+        let t = (a * e - b * d) / denom;
+        let range = -T::EPS..=T::ONE + T::EPS;
+        if !range.contains(&s) || !range.contains(&t) {
+            return None;
+        }
+
+        let p = self.0 + u * s;
+        let q = other.0 + v * t;
+        if (&p - q).norm() <= eps {
+            Some(p)
+        } else {
+            None
+        }
+    }
 }
 
 /// A matrix ordered by fuzzy lexicographic ordering. That is, lexicographic
@@ -474,11 +710,11 @@ where
 
 /// A matrix ordered by fuzzy lexicographic ordering. For more info, see
 /// [`MatrixOrdMxN`].
-pub type MatrixOrd<T> = MatrixOrdMxN<T, Dynamic, Dynamic>;
+pub type MatrixOrd<T = Real> = MatrixOrdMxN<T, Dynamic, Dynamic>;
 
 /// A point ordered by fuzzy lexicographic ordering. For more info, see
 /// [`MatrixOrdMxN`].
-pub type PointOrd<T> = MatrixOrdMxN<T, Dynamic, U1>;
+pub type PointOrd<T = Real> = MatrixOrdMxN<T, Dynamic, U1>;
 
 #[cfg(test)]
 mod tests {
@@ -518,4 +754,241 @@ mod tests {
             dvector![4.0 / 3.0, 4.0 / 3.0, 4.0 / 3.0, 4.0 / 3.0],
         );
     }
+
+    #[test]
+    /// Intersects line segments.
+    pub fn segment_intersect() {
+        let (p0, p1) = (dvector![0.0, 0.0, 0.0], dvector![2.0, 2.0, 0.0]);
+        let (q0, q1) = (dvector![0.0, 2.0, 0.0], dvector![2.0, 0.0, 0.0]);
+        assert_eq(
+            Segment(&p0, &p1).intersect(&Segment(&q0, &q1)).unwrap(),
+            dvector![1.0, 1.0, 0.0],
+        );
+
+        // Skew lines.
+        let (r0, r1) = (dvector![0.0, 2.0, 1.0], dvector![2.0, 0.0, 1.0]);
+        assert!(Segment(&p0, &p1).intersect(&Segment(&r0, &r1)).is_none());
+
+        // Lines intersecting outside of the segments.
+        let (r0, r1) = (dvector![0.0, 4.0, 0.0], dvector![1.0, 3.0, 0.0]);
+        assert!(Segment(&p0, &p1).intersect(&Segment(&r0, &r1)).is_none());
+
+        // Collinear segments touching at an endpoint.
+        let r1 = dvector![3.0, 3.0, 0.0];
+        assert_eq(
+            Segment(&p0, &p1).intersect(&Segment(&p1, &r1)).unwrap(),
+            p1.clone(),
+        );
+
+        // Overlapping collinear segments.
+        let r0 = dvector![1.0, 1.0, 0.0];
+        assert!(Segment(&p0, &p1).intersect(&Segment(&r0, &r1)).is_none());
+    }
+
+    #[test]
+    /// Intersects line segments at very different scales.
+    pub fn segment_intersect_scale() {
+        for scale in [1e3, 1.0, 1e-3] {
+            let p = |x: f32, y: f32| dvector![x * scale, y * scale];
+
+            // A nearly parallel pair, touching at an endpoint.
+            let (p0, p1, q1) = (p(0.0, 0.0), p(2.0, 0.0), p(4.0, 1e-3));
+            assert_eq(
+                Segment(&p0, &p1).intersect(&Segment(&p1, &q1)).unwrap(),
+                p1.clone(),
+            );
+
+            // A nearly parallel pair that doesn't touch.
+            let (q0, q1) = (p(0.0, 1.0), p(2.0, 1.001));
+            assert!(Segment(&p0, &p1).intersect(&Segment(&q0, &q1)).is_none());
+
+            // Points off the segment by a fixed fraction of its length.
+            let segment = Segment(&p0, &p1);
+            assert!(segment.contains(&p(1.0, 1e-6)));
+            assert!(!segment.contains(&p(1.0, 1e-3)));
+        }
+
+        // Small crossing segments aren't mistaken for parallel ones.
+        let p = |x: f32, y: f32| dvector![x * 1e-3, y * 1e-3];
+        let (p0, p1, q0, q1) = (p(0.0, 0.0), p(2.0, 2.0), p(0.0, 2.0), p(2.0, 0.0));
+        assert_eq(
+            Segment(&p0, &p1).intersect(&Segment(&q0, &q1)).unwrap(),
+            p(1.0, 1.0),
+        );
+    }
+
+    #[test]
+    /// Computes the distance from some points to a segment.
+    pub fn segment_distance() {
+        let (p0, p1) = (dvector![0.0, 0.0], dvector![2.0, 0.0]);
+        let segment = Segment(&p0, &p1);
+
+        // A point over the middle of the segment.
+        assert_abs_diff_eq!(segment.distance(&dvector![1.0, 3.0]), 3.0);
+
+        // Points past either endpoint are measured to that endpoint.
+        assert_abs_diff_eq!(segment.distance(&dvector![-3.0, 4.0]), 5.0);
+        assert_eq(segment.closest(&dvector![5.0, -1.0]), p1.clone());
+
+        // A degenerate segment is a single point.
+        assert_abs_diff_eq!(Segment(&p0, &p0).distance(&dvector![0.0, 2.0]), 2.0);
+    }
+
+    #[test]
+    /// Computes the centroid and bounding box of some points.
+    pub fn centroid_bounding_box() {
+        let points = vec![
+            dvector![1.0, 0.0, -2.0],
+            dvector![-1.0, 3.0, 0.0],
+            dvector![3.0, 0.0, 2.0],
+        ];
+
+        assert_eq(centroid(&points), dvector![1.0, 1.0, 0.0]);
+
+        let (min, max) = bounding_box(&points);
+        assert_eq(min, dvector![-1.0, 0.0, -2.0]);
+        assert_eq(max, dvector![3.0, 3.0, 2.0]);
+
+        // A single point is its own centroid and bounding box.
+        let point = vec![dvector![0.5, 0.25]];
+        assert_eq(centroid(&point), point[0].clone());
+        let (min, max) = bounding_box(&point);
+        assert_eq(min, point[0].clone());
+        assert_eq(max, point[0].clone());
+    }
+
+    #[test]
+    /// Intersects hyperspheres with hyperplanes.
+    pub fn sphere_intersect() {
+        let sphere = Hypersphere::with_radius(dvector![1.0, 0.0, 0.0], 5.0);
+        let plane = Hyperplane::new(dvector![1.0, 0.0, 0.0], 4.0);
+
+        let circle = sphere.intersect(&plane).unwrap();
+        assert_eq(circle.center, dvector![4.0, 0.0, 0.0]);
+        assert_abs_diff_eq!(circle.squared_radius, 16.0, epsilon = f32::EPS);
+
+        // Tangent hyperplanes give a single point.
+        let plane = Hyperplane::new(dvector![1.0, 0.0, 0.0], 6.0);
+        let point = sphere.intersect(&plane).unwrap();
+        assert_abs_diff_eq!(point.squared_radius, 0.0, epsilon = f32::EPS);
+
+        // Faraway hyperplanes miss the hypersphere.
+        let plane = Hyperplane::new(dvector![0.0, 1.0, 0.0], 7.0);
+        assert!(sphere.intersect(&plane).is_none());
+
+        // Imaginary hyperspheres don't intersect anything.
+        let sphere = Hypersphere::with_squared_radius(dvector![0.0, 0.0, 0.0], -4.0);
+        let plane = Hyperplane::new(dvector![0.0, 0.0, 1.0], 0.0);
+        assert!(sphere.intersect(&plane).is_none());
+    }
+
+    #[test]
+    /// Intersects hyperplanes with segments in every configuration.
+    pub fn plane_intersect() {
+        let plane = Hyperplane::new(dvector![0.0, 0.0, 1.0], 1.0);
+
+        // A segment crossing the hyperplane.
+        let (p, q) = (dvector![1.0, 0.0, 0.0], dvector![1.0, 0.0, 4.0]);
+        match plane.intersect(Segment(&p, &q)) {
+            SegmentIntersection::Point(r) => assert_eq(r, dvector![1.0, 0.0, 1.0]),
+            _ => panic!("expected a point"),
+        }
+
+        // A segment touching the hyperplane at an endpoint, from either side,
+        // even if slightly off.
+        let (p, q) = (
+            dvector![1.0, 2.0, 1.0 + f32::EPS / 2.0],
+            dvector![0.0, 0.0, 3.0],
+        );
+        assert_eq!(
+            plane.intersect(Segment(&p, &q)),
+            SegmentIntersection::Point(p.clone())
+        );
+        let q = dvector![0.0, 0.0, -3.0];
+        assert_eq!(
+            plane.intersect(Segment(&q, &p)),
+            SegmentIntersection::Point(p.clone())
+        );
+
+        // A segment on the hyperplane.
+        let q = dvector![5.0, -1.0, 1.0];
+        assert_eq!(plane.intersect(Segment(&p, &q)), SegmentIntersection::Whole);
+
+        // Segments on one side of the hyperplane, parallel or not.
+        let (p, q) = (dvector![1.0, 0.0, 2.0], dvector![-1.0, 0.0, 2.0]);
+        assert_eq!(plane.intersect(Segment(&p, &q)), SegmentIntersection::None);
+        let (p, q) = (dvector![1.0, 0.0, -2.0], dvector![-1.0, 0.0, 0.5]);
+        assert_eq!(plane.intersect(Segment(&p, &q)), SegmentIntersection::None);
+    }
+
+    #[test]
+    /// Builds hyperplanes through sets of points.
+    pub fn plane_from_points() {
+        let points = [
+            dvector![1.0, 0.0, 0.0],
+            dvector![0.0, 1.0, 0.0],
+            dvector![0.0, 0.0, 1.0],
+        ];
+
+        // The outer point is on the positive side, whichever side it is.
+        let origin = Point::zeros(3);
+        let plane = Hyperplane::from_points(&points, &origin).unwrap();
+        assert_abs_diff_eq!(
+            plane.distance(&origin),
+            1.0 / f32::SQRT_3,
+            epsilon = f32::EPS
+        );
+        let outer = Point::from_element(3, 1.0);
+        let plane = Hyperplane::from_points(&points, &outer).unwrap();
+        assert_abs_diff_eq!(
+            plane.distance(&outer),
+            2.0 / f32::SQRT_3,
+            epsilon = f32::EPS
+        );
+        assert_abs_diff_eq!(plane.distance(&points[0]), 0.0, epsilon = f32::EPS);
+
+        // The outer point can't lie on the hyperplane.
+        assert!(Hyperplane::from_points(&points, &dvector![1.0, 1.0, -1.0]).is_none());
+
+        // Collinear points don't span a hyperplane.
+        let line = [
+            dvector![0.0, 0.0, 0.0],
+            dvector![1.0, 1.0, 1.0],
+            dvector![2.0, 2.0, 2.0],
+        ];
+        assert!(Hyperplane::from_points(&line, &outer).is_none());
+        assert!(Hyperplane::<f32>::from_points(&[], &outer).is_none());
+    }
+
+    #[test]
+    /// Reflects points across subspaces and hyperplanes.
+    pub fn reflect() {
+        let p = dvector![1.0, 2.0, 3.0];
+
+        // Reflection across the x axis.
+        let line = Subspace::from_points([dvector![0.0, 0.0, 0.0], dvector![1.0, 0.0, 0.0]].iter());
+        let q = line.reflect(&p);
+        assert_eq(q.clone(), dvector![1.0, -2.0, -3.0]);
+        assert_eq(line.reflect(&q), p.clone());
+
+        let plane = Hyperplane::new(dvector![0.6, 0.8, 0.0], 1.0);
+        let q = plane.reflect(&p);
+        assert_abs_diff_eq!(plane.distance(&q), -plane.distance(&p), epsilon = f32::EPS);
+        assert_eq(plane.reflect(&q), p);
+    }
+
+    #[test]
+    /// Measures the same distances in both precisions. The annotated values use
+    /// the default float type.
+    pub fn precision() {
+        let points: [Point; 2] = [dvector![0.0, 0.0, 0.0], dvector![1.0, 1.0, 0.0]];
+        let line: Subspace = Subspace::from_points(points.iter());
+        let dist = line.distance(&dvector![1.0, 0.0, 0.0]);
+        assert_abs_diff_eq!(dist, Real::HALF_SQRT_2, epsilon = Real::EPS);
+
+        let points: [Point<f32>; 2] = [dvector![0.0, 0.0, 0.0], dvector![1.0, 1.0, 0.0]];
+        let line = Subspace::from_points(points.iter());
+        let dist = line.distance(&dvector![1.0, 0.0, 0.0]);
+        assert_abs_diff_eq!(dist, f32::HALF_SQRT_2, epsilon = f32::EPS);
+    }
 }
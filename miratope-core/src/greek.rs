@@ -0,0 +1,299 @@
+//! Contains the Greek prefixes used to name polytopes after their element
+//! counts, like the `icositetra` in `icositetragon`.
+
+/// The Greek prefixes for the units, in the form used within longer prefixes.
+const UNIT_PREFIXES: [&str; 10] = [
+    "", "hena", "di", "tri", "tetra", "penta", "hexa", "hepta", "octa", "ennea",
+];
+
+/// The Greek prefixes for the tens from 30 onwards.
+const TEN_PREFIXES: [&str; 10] = [
+    "",
+    "",
+    "",
+    "triaconta",
+    "tetraconta",
+    "pentaconta",
+    "hexaconta",
+    "heptaconta",
+    "octaconta",
+    "enneaconta",
+];
+
+/// The Greek prefixes for the hundreds from 200 onwards.
+const HUNDRED_PREFIXES: [&str; 10] = [
+    "",
+    "",
+    "diacosi",
+    "triacosi",
+    "tetracosi",
+    "pentacosi",
+    "hexacosi",
+    "heptacosi",
+    "octacosi",
+    "enneacosi",
+];
+
+/// The Greek prefixes for the thousands.
+const THOUSAND_PREFIXES: [&str; 10] = [
+    "",
+    "chilia",
+    "dischilia",
+    "trischilia",
+    "tetrakischilia",
+    "pentakischilia",
+    "hexakischilia",
+    "heptakischilia",
+    "octakischilia",
+    "enneakischilia",
+];
+
+/// The largest number with a Greek prefix.
+pub const MAX_PREFIX: usize = 10000;
+
+/// Builds the Greek prefix for a number less than [`MAX_PREFIX`], as used
+/// within longer prefixes. Zero gives an empty prefix.
+fn compound_prefix(n: usize) -> String {
+    match n {
+        0..=9 => UNIT_PREFIXES[n].to_string(),
+        10 => "deca".to_string(),
+        11 => "hendeca".to_string(),
+        12 => "dodeca".to_string(),
+        13..=19 => format!("{}deca", UNIT_PREFIXES[n - 10]),
+        20 => "icosa".to_string(),
+        21..=29 => format!("icosi{}", UNIT_PREFIXES[n - 20]),
+        30..=99 => format!("{}{}", TEN_PREFIXES[n / 10], UNIT_PREFIXES[n % 10]),
+        100 => "hecto".to_string(),
+        101..=199 => format!("hecaton{}", compound_prefix(n - 100)),
+        200..=999 => format!("{}{}", HUNDRED_PREFIXES[n / 100], compound_prefix(n % 100)),
+        _ => format!(
+            "{}{}",
+            THOUSAND_PREFIXES[n / 1000],
+            compound_prefix(n % 1000)
+        ),
+    }
+}
+
+/// Returns the Greek prefix for a number, like `icositetra` for 24 or
+/// `hexacosi` for 600. Returns `None` for zero and for numbers larger than
+/// [`MAX_PREFIX`].
+pub fn greek_prefix(n: usize) -> Option<String> {
+    match n {
+        0 => None,
+        1 => Some("mono".to_string()),
+        MAX_PREFIX => Some("myria".to_string()),
+        _ if n > MAX_PREFIX => None,
+        _ => Some(compound_prefix(n)),
+    }
+}
+
+/// Returns the name of a polygon with a given number of sides, like
+/// `Pentagon` or `Hecatonicosagon`. Polygons with too many sides for a Greek
+/// prefix are named like `10001-gon`.
+pub fn polygon_name(n: usize) -> String {
+    match n {
+        3 => "Triangle".to_string(),
+        4 => "Square".to_string(),
+        _ => match greek_prefix(n) {
+            Some(prefix) => format!("{}{}gon", prefix[..1].to_uppercase(), &prefix[1..]),
+            None => format!("{}-gon", n),
+        },
+    }
+}
+
+/// Returns the single unit whose prefix is a given string, or 0 for the empty
+/// string.
+fn parse_unit(prefix: &str) -> Option<usize> {
+    UNIT_PREFIXES.iter().position(|&unit| unit == prefix)
+}
+
+/// Returns the number less than 100 with a given prefix, as built by
+/// [`compound_prefix`].
+fn parse_tens(prefix: &str) -> Option<usize> {
+    match prefix {
+        "deca" => return Some(10),
+        "hendeca" => return Some(11),
+        "dodeca" => return Some(12),
+        "icosa" => return Some(20),
+        _ => {}
+    }
+
+    if let Some(unit) = prefix.strip_suffix("deca") {
+        return parse_unit(unit).filter(|&u| u >= 3).map(|u| 10 + u);
+    }
+    if let Some(unit) = prefix.strip_prefix("icosi") {
+        return parse_unit(unit).filter(|&u| u >= 1).map(|u| 20 + u);
+    }
+
+    for (t, ten) in TEN_PREFIXES.iter().enumerate().skip(3) {
+        if let Some(unit) = prefix.strip_prefix(ten) {
+            return parse_unit(unit).map(|u| 10 * t + u);
+        }
+    }
+
+    parse_unit(prefix)
+}
+
+/// Returns the number less than 1000 with a given prefix, as built by
+/// [`compound_prefix`].
+fn parse_hundreds(prefix: &str) -> Option<usize> {
+    if prefix == "hecto" {
+        return Some(100);
+    }
+    if let Some(rest) = prefix.strip_prefix("hecaton") {
+        return parse_tens(rest).filter(|&n| n != 0).map(|n| 100 + n);
+    }
+
+    for (h, hundred) in HUNDRED_PREFIXES.iter().enumerate().skip(2) {
+        if let Some(rest) = prefix.strip_prefix(hundred) {
+            return parse_tens(rest).map(|n| 100 * h + n);
+        }
+    }
+
+    parse_tens(prefix)
+}
+
+/// Returns the number with a given Greek prefix, as returned by
+/// [`greek_prefix`], or `None` if the string isn't one.
+pub fn parse_greek_prefix(prefix: &str) -> Option<usize> {
+    match prefix {
+        "mono" => return Some(1),
+        "myria" => return Some(MAX_PREFIX),
+        _ => {}
+    }
+
+    // The thousands are tried from the longest prefix down, since "chilia"
+    // ends every other one.
+    let n = THOUSAND_PREFIXES
+        .iter()
+        .enumerate()
+        .skip(1)
+        .rev()
+        .find_map(|(k, thousand)| {
+            let rest = prefix.strip_prefix(thousand)?;
+            parse_hundreds(rest).map(|n| 1000 * k + n)
+        })
+        .or_else(|| parse_hundreds(prefix))?;
+
+    // Rejects the spellings that parse, but aren't the ones we'd write.
+    (greek_prefix(n).as_deref() == Some(prefix)).then(|| n)
+}
+
+/// Returns the number of sides of a polygon with a given name, as returned by
+/// [`polygon_name`]. This is case insensitive. Returns `None` if the name
+/// isn't that of a polygon.
+pub fn polygon_sides(name: &str) -> Option<usize> {
+    let name = name.trim().to_lowercase();
+    if let Some(n) = name.strip_suffix("-gon") {
+        return n.parse().ok().filter(|&n| n >= 3);
+    }
+
+    match name.as_str() {
+        "triangle" => Some(3),
+        "square" => Some(4),
+        _ => name
+            .strip_suffix("gon")
+            .and_then(parse_greek_prefix)
+            .filter(|&n| n >= 5),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{greek_prefix, parse_greek_prefix, polygon_name, polygon_sides, MAX_PREFIX};
+
+    /// Checks the Greek prefixes against a table of known values.
+    #[test]
+    fn greek_prefixes() {
+        for &(n, prefix) in &[
+            (1, "mono"),
+            (2, "di"),
+            (3, "tri"),
+            (4, "tetra"),
+            (5, "penta"),
+            (6, "hexa"),
+            (7, "hepta"),
+            (8, "octa"),
+            (9, "ennea"),
+            (10, "deca"),
+            (11, "hendeca"),
+            (12, "dodeca"),
+            (13, "trideca"),
+            (16, "hexadeca"),
+            (19, "enneadeca"),
+            (20, "icosa"),
+            (21, "icosihena"),
+            (24, "icositetra"),
+            (30, "triaconta"),
+            (32, "triacontadi"),
+            (48, "tetracontaocta"),
+            (99, "enneacontaennea"),
+            (100, "hecto"),
+            (101, "hecatonhena"),
+            (110, "hecatondeca"),
+            (120, "hecatonicosa"),
+            (200, "diacosi"),
+            (256, "diacosipentacontahexa"),
+            (600, "hexacosi"),
+            (720, "heptacosiicosa"),
+            (1000, "chilia"),
+            (1200, "chiliadiacosi"),
+            (2000, "dischilia"),
+            (4096, "tetrakischiliaenneacontahexa"),
+            (10000, "myria"),
+        ] {
+            assert_eq!(greek_prefix(n).as_deref(), Some(prefix), "prefix for {}", n);
+        }
+
+        assert_eq!(greek_prefix(0), None);
+        assert_eq!(greek_prefix(10001), None);
+    }
+
+    /// Checks the names of some polygons.
+    #[test]
+    fn polygon_names() {
+        assert_eq!(polygon_name(3), "Triangle");
+        assert_eq!(polygon_name(4), "Square");
+        assert_eq!(polygon_name(5), "Pentagon");
+        assert_eq!(polygon_name(24), "Icositetragon");
+        assert_eq!(polygon_name(100), "Hectogon");
+        assert_eq!(polygon_name(120), "Hecatonicosagon");
+        assert_eq!(polygon_name(10000), "Myriagon");
+        assert_eq!(polygon_name(10001), "10001-gon");
+    }
+
+    /// Checks that the names of polygons can be read back.
+    #[test]
+    fn polygon_round_trip() {
+        for n in (3..=120).chain([600, 1000, 4096, 10000, 10001, 123456]) {
+            assert_eq!(polygon_sides(&polygon_name(n)), Some(n), "sides of {}", n);
+        }
+
+        assert_eq!(polygon_sides("pentagon"), Some(5));
+        assert_eq!(polygon_sides("7-gon"), Some(7));
+        assert_eq!(polygon_sides("2-gon"), None);
+        assert_eq!(polygon_sides("Cube"), None);
+        assert_eq!(polygon_sides("Octagonal prism"), None);
+        assert_eq!(polygon_sides("Digon"), None);
+        assert_eq!(polygon_sides("Icosadigon"), None);
+        assert_eq!(polygon_sides("Hecatonhectogon"), None);
+    }
+
+    /// Checks that every Greek prefix is read back.
+    #[test]
+    fn prefix_round_trip() {
+        for n in 1..=MAX_PREFIX {
+            let prefix = greek_prefix(n).unwrap();
+            assert_eq!(
+                parse_greek_prefix(&prefix),
+                Some(n),
+                "number for {}",
+                prefix
+            );
+        }
+
+        assert_eq!(parse_greek_prefix(""), None);
+        assert_eq!(parse_greek_prefix("hena"), None);
+        assert_eq!(parse_greek_prefix("decadi"), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Defines a group whose elements have all been generated and indexed.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter::Cloned,
+    slice,
+};
+
+use super::{
+    group_item::{GroupItem, Wrapper},
+    permutation::DPermutation,
+    Group,
+};
+
+use nalgebra::Dynamic;
+
+/// A group whose elements have been generated and stored, together with a
+/// lookup table from each element to its index.
+///
+/// Unlike [`Group`], which is a lazy iterator, this allows for querying the
+/// order of the group or testing membership of an element without having to
+/// generate the group again. The elements are compared using the
+/// [`GroupItem::FuzzyOrd`] wrapper, so that small floating point errors don't
+/// matter.
+pub struct CachedGroup<T: GroupItem> {
+    /// The "dimension" of the elements of the group.
+    dim: T::Dim,
+
+    /// The elements of the group, in the order they were generated.
+    elements: Vec<T>,
+
+    /// Maps every element of the group to its index in `elements`.
+    indices: BTreeMap<T::FuzzyOrd, usize>,
+}
+
+impl<T: GroupItem + Clone, I: Iterator<Item = T>> From<Group<I>> for CachedGroup<T> {
+    fn from(group: Group<I>) -> Self {
+        let dim = group.dim;
+        let mut elements = Vec::new();
+        let mut indices = BTreeMap::new();
+
+        for el in group {
+            indices.insert(T::FuzzyOrd::from_inner(el.clone()), elements.len());
+            elements.push(el);
+        }
+
+        Self {
+            dim,
+            elements,
+            indices,
+        }
+    }
+}
+
+impl<T: GroupItem> CachedGroup<T> {
+    /// Returns the "dimension" of the elements of the group.
+    pub fn dim(&self) -> T::Dim {
+        self.dim
+    }
+
+    /// Returns the order of the group.
+    pub fn order(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns the elements of the group, in the order they were generated.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the index of an element in the group, or `None` if it doesn't
+    /// belong to it.
+    pub fn index_of(&self, el: &T) -> Option<usize> {
+        self.indices.get(T::FuzzyOrd::as_wrapper(el)).copied()
+    }
+
+    /// Returns whether an element belongs to the group.
+    pub fn contains(&self, el: &T) -> bool {
+        self.indices.contains_key(T::FuzzyOrd::as_wrapper(el))
+    }
+
+    /// Returns the multiplication table of the group. The entry at `(i, j)` is
+    /// the index of the product of the elements with indices `i` and `j`.
+    ///
+    /// # Panics
+    /// This method will panic if the elements aren't closed under
+    /// multiplication, which can only happen if the group was built unsafely.
+    pub fn mul_table(&self) -> Vec<Vec<usize>> {
+        self.elements
+            .iter()
+            .map(|a| {
+                self.elements
+                    .iter()
+                    .map(|b| {
+                        self.index_of(&a.mul(b))
+                            .expect("Group is not closed under multiplication!")
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Partitions the elements of the group into the left cosets of a
+    /// subgroup, given as lists of indices. The subgroup itself always comes
+    /// first.
+    ///
+    /// # Panics
+    /// This method will panic if the subgroup isn't contained in the group.
+    pub fn cosets(&self, subgroup: &Self) -> Vec<Vec<usize>> {
+        let mut coset_of = vec![None; self.order()];
+        let mut cosets = Vec::new();
+
+        for (i, g) in self.elements.iter().enumerate() {
+            if coset_of[i].is_some() {
+                continue;
+            }
+
+            let coset: Vec<_> = subgroup
+                .elements
+                .iter()
+                .map(|h| {
+                    self.index_of(&g.mul(h))
+                        .expect("Subgroup is not contained in the group!")
+                })
+                .collect();
+
+            for &j in &coset {
+                coset_of[j] = Some(cosets.len());
+            }
+            cosets.push(coset);
+        }
+
+        // We make sure the subgroup comes first, in case the identity isn't
+        // the first element.
+        if let Some(id) = self.index_of(&T::id(self.dim)) {
+            let idx = cosets.iter().position(|coset| coset.contains(&id)).unwrap();
+            cosets.swap(0, idx);
+        }
+
+        cosets
+    }
+
+    /// Returns whether a subgroup is normal, that is, whether it's closed under
+    /// conjugation by every element of the group.
+    pub fn is_normal(&self, subgroup: &Self) -> bool {
+        self.elements.iter().all(|g| {
+            let g_inv = g.inv();
+            subgroup
+                .elements
+                .iter()
+                .all(|h| subgroup.contains(&g.mul(h).mul(&g_inv)))
+        })
+    }
+
+    /// Returns the quotient of the group by a normal subgroup, as the group of
+    /// permutations it induces on the cosets listed by [`Self::cosets`].
+    /// Returns `None` if the subgroup isn't normal.
+    ///
+    /// # Panics
+    /// This method will panic if the subgroup isn't contained in the group.
+    pub fn quotient(&self, subgroup: &Self) -> Option<CachedGroup<DPermutation>> {
+        if !self.is_normal(subgroup) {
+            return None;
+        }
+
+        let cosets = self.cosets(subgroup);
+        let mut coset_of = vec![0; self.order()];
+        for (i, coset) in cosets.iter().enumerate() {
+            for &j in coset {
+                coset_of[j] = i;
+            }
+        }
+
+        // Every element acts on the cosets by left multiplication. The elements
+        // of each coset induce the same permutation.
+        let perms: BTreeSet<_> = self
+            .elements
+            .iter()
+            .map(|g| {
+                let perm = cosets.iter().map(|coset| {
+                    coset_of[self
+                        .index_of(&g.mul(&self.elements[coset[0]]))
+                        .expect("Group is not closed under multiplication!")]
+                });
+
+                // Safety: left multiplication permutes the cosets.
+                unsafe { DPermutation::from_iterator(perm, cosets.len()) }
+            })
+            .collect();
+
+        // Safety: the permutations induced by a group form a group.
+        Some(unsafe { Group::new(Dynamic::new(cosets.len()), perms.into_iter()) }.into())
+    }
+}
+
+impl<T: GroupItem + Clone> CachedGroup<T> {
+    /// Returns a lazy [`Group`] that iterates over the stored elements. This
+    /// can be passed to any of the constructions that take a [`Group`] without
+    /// having to generate the group again.
+    pub fn group(&self) -> Group<Cloned<slice::Iter<'_, T>>> {
+        // Safety: the elements were taken from a group.
+        unsafe { Group::new(self.dim, self.elements.iter().cloned()) }
+    }
+}
@@ -1,34 +1,55 @@
 //! Contains the code for a group generated by a set of elements.
 
-use std::borrow::Cow;
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
 
 use crate::cox::cd::CdResult;
 use crate::cox::Cox;
+use crate::float::Real;
 use crate::geometry::Matrix;
 use crate::group::Group;
 
 use super::group_item::Wrapper;
 use super::GroupItem;
 
-/// The result of trying to get the next element in a group.
-pub enum GroupNext<T> {
-    /// We've already found all elements of the group.
-    None,
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 
-    /// We found an element we had found previously.
-    Repeat,
+/// The number of queued elements that are multiplied by the generators at
+/// once. The products within a batch are computed in parallel. Larger batches
+/// make lookups slower, as the products no longer fit in the cache.
+const BATCH_SIZE: usize = 256;
 
-    /// We found a new element.
-    New(T),
+/// The threads in which a [`GenIter`] multiplies its elements.
+#[derive(Clone)]
+enum Threads {
+    /// All products are computed in the current thread.
+    Single,
+
+    /// The products are computed in the global thread pool.
+    Global,
+
+    /// The products are computed in a dedicated thread pool.
+    Pool(Arc<ThreadPool>),
 }
 
 /// An iterator for a `Group` [generated](https://en.wikipedia.org/wiki/Generator_(mathematics))
 /// by a set of floating point matrices. Its elements are built in a BFS order.
 /// It contains a lookup table, used to figure out whether an element has
 /// already been found or not, as well as a queue to store the next elements.
+///
+/// The queued elements are multiplied by the generators in batches, which are
+/// split among several threads. The products are then looked up in the order
+/// in which they would have been found one by one, so the elements are always
+/// returned in the same order, regardless of the number of threads.
+///
+/// Floating point drift can move a product far enough from an element found
+/// before that a fuzzy comparison no longer recognizes it. Elements with
+/// floating point entries are instead looked up by their
+/// [rounded](GroupItem::rounded) entries, so that drifted copies count towards
+/// the element they're a copy of.
 #[derive(Clone)]
-pub struct GenIter<T: GroupItem + Clone> {
+pub struct GenIter<T: GroupItem + Clone + Send + Sync> {
     /// The number of dimensions the group acts on.
     pub dim: T::Dim,
 
@@ -40,17 +61,21 @@ pub struct GenIter<T: GroupItem + Clone> {
     /// duplicate group elements will just keep generating forever.
     elements: BTreeMap<T::FuzzyOrd, usize>,
 
+    /// The lookup table for elements with floating point entries, indexed by
+    /// their rounded entries. It's pruned in the same way.
+    rounded: HashMap<Vec<i64>, usize>,
+
     /// Stores the elements that haven't yet been processed.
     queue: VecDeque<T>,
 
-    /// Stores the index in (`generators`)[GenGroup.generators] of the generator
-    /// that's being checked. All previous once will have already been
-    /// multiplied to the right of the current element. Quirk of the current
-    /// data structure, subject to change.
-    gen_idx: usize,
+    /// Stores the new elements that have been found, but not yet returned.
+    found: VecDeque<T>,
+
+    /// The threads in which the products of each batch are computed.
+    threads: Threads,
 }
 
-impl<T: GroupItem + Clone> GenIter<T> {
+impl<T: GroupItem + Clone + Send + Sync> GenIter<T> {
     /// Builds a new group from a set of generators.
     pub fn new(dim: T::Dim, gens: Vec<T>) -> Self {
         // Initializes the queue with only the identity matrix.
@@ -61,93 +86,144 @@ impl<T: GroupItem + Clone> GenIter<T> {
         // case that ensures that neither the identity is queued nor found
         // twice.
         let mut elements = BTreeMap::new();
-        elements.insert(Wrapper::from_inner(T::id(dim)), 0);
+        let mut rounded = HashMap::new();
+        let key = T::id(dim).rounded().key;
+        if key.is_empty() {
+            elements.insert(Wrapper::from_inner(T::id(dim)), 0);
+        } else {
+            rounded.insert(key, 0);
+        }
 
         Self {
             dim,
             gens,
             elements,
+            rounded,
             queue,
-            gen_idx: 0,
+            found: VecDeque::new(),
+            threads: Threads::Global,
         }
     }
 
+    /// Sets the number of threads used to multiply the elements. By default,
+    /// or if `n` is 0, the global thread pool is used. If `n` is 1, everything
+    /// runs on the current thread.
+    pub fn with_threads(mut self, n: usize) -> Self {
+        self.threads = match n {
+            0 => Threads::Global,
+            1 => Threads::Single,
+            n => match ThreadPoolBuilder::new().num_threads(n).build() {
+                Ok(pool) => Threads::Pool(Arc::new(pool)),
+                Err(_) => Threads::Global,
+            },
+        };
+
+        self
+    }
+
     /// Inserts a new element into the group. Returns whether the element is new.
     fn insert(&mut self, el: T) -> bool {
-        use std::collections::btree_map::Entry::*;
+        let last = self.gens.len() - 1;
+        let rounded = el.rounded();
+
+        // Types without floating point entries can't drift, so they're looked
+        // up as they are.
+        if rounded.key.is_empty() {
+            use std::collections::btree_map::Entry::*;
+
+            return match self.elements.entry(Wrapper::from_inner(el.clone())) {
+                // If the element is new, we add it to the queue as well.
+                Vacant(entry) => {
+                    entry.insert(1);
+                    self.queue.push_back(el);
+                    true
+                }
 
-        match self.elements.entry(Wrapper::from_inner(el.clone())) {
-            // If the element is new, we add it to the queue as well.
-            Vacant(entry) => {
-                entry.insert(1);
+                // Bumps the value by 1, or removes the element if this is the
+                // last time we'll find the element.
+                Occupied(mut entry) => {
+                    let value = *entry.get();
+                    if value != last {
+                        entry.insert(value + 1);
+                    } else {
+                        entry.remove_entry();
+                    }
+
+                    // The element is a repeat, except in the special case of
+                    // the identity.
+                    value == 0
+                }
+            };
+        }
+
+        // Otherwise, we look up every key the element could have been rounded
+        // to, and do the same.
+        let found = &mut self.rounded;
+        let key = rounded.keys().find(|key| found.contains_key(key));
+        match key {
+            None => {
+                found.insert(rounded.key, 1);
                 self.queue.push_back(el);
                 true
             }
 
-            // Bumps the value by 1, or removes the element if this is the last
-            // time we'll find the element.
-            Occupied(mut entry) => {
-                let value = *entry.get();
-                if value != self.gens.len() - 1 {
-                    entry.insert(value + 1);
+            Some(key) => {
+                let value = found[&key];
+                if value != last {
+                    found.insert(key, value + 1);
                 } else {
-                    entry.remove_entry();
+                    found.remove(&key);
                 }
 
-                // The element is a repeat, except in the special case of the
-                // identity.
                 value == 0
             }
         }
     }
 
-    /// Gets the next element and the next generator to attempt to multiply
-    /// with. Advances the iterator.
-    fn next_el_gen(&mut self) -> Option<(Cow<'_, T>, &T)> {
-        if self.queue.is_empty() {
-            return None;
+    /// Multiplies every element in a batch by every generator. The products
+    /// are returned element by element, and generator by generator.
+    fn products(&self, batch: &[T]) -> Vec<T> {
+        /// Multiplies an element by every generator.
+        fn mul<'a, T: GroupItem>(gens: &'a [T], el: &'a T) -> impl Iterator<Item = T> + 'a {
+            gens.iter().map(move |gen| T::mul(el, gen))
         }
 
-        let el;
-        let gen = &self.gens[self.gen_idx];
-
-        // Advances the indices.
-        self.gen_idx += 1;
-        if self.gen_idx == self.gens.len() {
-            self.gen_idx = 0;
-            el = Cow::Owned(self.queue.front().unwrap().clone());
-            self.queue.pop_front();
-        } else {
-            el = Cow::Borrowed(self.queue.front().unwrap());
+        let gens = &self.gens;
+        match &self.threads {
+            Threads::Global if rayon::current_num_threads() > 1 => {
+                batch.par_iter().flat_map_iter(|el| mul(gens, el)).collect()
+            }
+            Threads::Single | Threads::Global => {
+                batch.iter().flat_map(|el| mul(gens, el)).collect()
+            }
+            Threads::Pool(pool) => {
+                pool.install(|| batch.par_iter().flat_map_iter(|el| mul(gens, el)).collect())
+            }
         }
-
-        Some((el, gen))
     }
 
-    /// Multiplies the current element times the current generator, determines
-    /// if it is a new element. Advances the iterator.
-    fn try_next(&mut self) -> GroupNext<T> {
-        // If there's a next element and generator.
-        if let Some((el, gen)) = self.next_el_gen() {
-            let new_el = T::mul(el.as_ref(), gen);
+    /// Multiplies the next batch of queued elements by the generators, and
+    /// stores the new elements that are found. Returns `false` if there were
+    /// no more elements to process.
+    fn next_batch(&mut self) -> bool {
+        if self.queue.is_empty() || self.gens.is_empty() {
+            return false;
+        }
+
+        let len = self.queue.len().min(BATCH_SIZE);
+        let batch: Vec<_> = self.queue.drain(..len).collect();
 
-            // If the group element is new.
-            if self.insert(new_el.clone()) {
-                GroupNext::New(new_el)
-            }
-            // If we found a repeat.
-            else {
-                GroupNext::Repeat
+        for el in self.products(&batch) {
+            if self.insert(el.clone()) {
+                self.found.push_back(el);
             }
         }
-        // If we already went through the entire group.
-        else {
-            GroupNext::None
-        }
+
+        true
     }
 }
 
-impl GenIter<Matrix<f64>> {
+impl GenIter<Matrix<Real>> {
     /// Parses a diagram and turns it into a GenIter.
     pub fn parse(input: &str) -> CdResult<Option<Self>> {
         Cox::parse(input).map(|cox| cox.gen_iter())
@@ -159,21 +235,23 @@ impl GenIter<Matrix<f64>> {
     }
 }
 
-impl<T: GroupItem + Clone> Iterator for GenIter<T> {
+impl<T: GroupItem + Clone + Send + Sync> Iterator for GenIter<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            match self.try_next() {
-                GroupNext::None => return None,
-                GroupNext::Repeat => {}
-                GroupNext::New(el) => return Some(el),
-            };
+            if let Some(el) = self.found.pop_front() {
+                return Some(el);
+            }
+
+            if !self.next_batch() {
+                return None;
+            }
         }
     }
 }
 
-impl<T: GroupItem + Clone> From<GenIter<T>> for Group<GenIter<T>> {
+impl<T: GroupItem + Clone + Send + Sync> From<GenIter<T>> for Group<GenIter<T>> {
     fn from(gen: GenIter<T>) -> Self {
         // The elements of a GenIter always form a group (that's the point!)
         unsafe { Self::new(gen.dim, gen) }
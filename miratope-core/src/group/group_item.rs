@@ -99,6 +99,73 @@ unsafe impl<T: Float> Wrapper<Quaternion<T>> for MatrixOrdMxN<T, Const<4>, Const
     }
 }
 
+/// The entries of a group item are rounded to multiples of this value when
+/// looking for approximate duplicates. It's fine enough to tell apart the
+/// entries of the groups we build, but much coarser than floating point drift.
+const ROUND_GRID: f64 = 1e-3;
+
+/// An entry is considered to be ambiguous when it's within this fraction of
+/// the grid from the midpoint of two multiples. Copies of an item whose entries
+/// drifted further apart than this might not be recognized.
+const ROUND_MARGIN: f64 = 0.01;
+
+/// The maximum number of ambiguous entries that are recorded. Each of them
+/// doubles the number of keys that need to be looked up.
+const MAX_AMBIGUOUS: usize = 8;
+
+/// A group item whose entries have been rounded to a grid much coarser than
+/// floating point drift. Two items that only differ by drift get the same key,
+/// unless one of their entries lies close to the midpoint of two
+/// grid points. Those entries are recorded together with the other grid point
+/// they could round to.
+#[derive(Clone, Debug, Default)]
+pub struct Rounded {
+    /// The rounded entries.
+    pub key: Vec<i64>,
+
+    /// The indices of the ambiguous entries, together with the other value
+    /// they could be rounded to.
+    pub ambiguous: Vec<(usize, i64)>,
+}
+
+impl Rounded {
+    /// Rounds a list of entries.
+    pub fn new<'a, T: Float, I: IntoIterator<Item = &'a T>>(entries: I) -> Self {
+        let grid = T::f64(ROUND_GRID);
+        let margin = T::f64(0.5 - ROUND_MARGIN);
+        let mut rounded = Self::default();
+
+        for (idx, &x) in entries.into_iter().enumerate() {
+            let x = x / grid;
+            let r = <T as ordered_float::Float>::round(x);
+            let diff = x - r;
+            let r = r.to_i64().expect("entry out of range");
+            rounded.key.push(r);
+
+            if rounded.ambiguous.len() < MAX_AMBIGUOUS && diff.fabs() > margin {
+                let other = if diff > T::ZERO { r + 1 } else { r - 1 };
+                rounded.ambiguous.push((idx, other));
+            }
+        }
+
+        rounded
+    }
+
+    /// Returns every key the item could have been rounded to. The first one
+    /// is always [`Self::key`].
+    pub fn keys(&self) -> impl Iterator<Item = Vec<i64>> + '_ {
+        (0..1 << self.ambiguous.len()).map(move |mask: usize| {
+            let mut key = self.key.clone();
+            for (bit, &(idx, other)) in self.ambiguous.iter().enumerate() {
+                if mask & (1 << bit) != 0 {
+                    key[idx] = other;
+                }
+            }
+            key
+        })
+    }
+}
+
 /// A trait for a type that can be used as the elements of a group.
 pub trait GroupItem: Sized {
     /// The type of any parameters, like the dimension of a matrix or the length
@@ -125,6 +192,13 @@ pub trait GroupItem: Sized {
     /// Multiplies and assigns two elements of the type.
     fn mul_assign(&mut self, rhs: &Self);
 
+    /// Rounds the entries of the value, so that copies of it that only differ
+    /// by floating point drift can be recognized. Types without floating point
+    /// entries don't need to implement this.
+    fn rounded(&self) -> Rounded {
+        Rounded::default()
+    }
+
     /// Determines whether two values are equal, using the wrapper specified by
     /// the trait.
     fn eq(&self, other: &Self) -> bool {
@@ -169,6 +243,10 @@ impl<T: Float> GroupItem for T {
     fn mul_assign(&mut self, rhs: &T) {
         *self *= *rhs;
     }
+
+    fn rounded(&self) -> Rounded {
+        Rounded::new(std::iter::once(self))
+    }
 }
 
 impl<T: Float> GroupItem for Matrix<T> {
@@ -192,6 +270,10 @@ impl<T: Float> GroupItem for Matrix<T> {
     fn mul_assign(&mut self, rhs: &Self) {
         *self *= rhs;
     }
+
+    fn rounded(&self) -> Rounded {
+        Rounded::new(self.iter())
+    }
 }
 
 impl<T: Float> GroupItem for Quaternion<T> {
@@ -213,4 +295,8 @@ impl<T: Float> GroupItem for Quaternion<T> {
     fn mul_assign(&mut self, rhs: &Self) {
         *self *= rhs;
     }
+
+    fn rounded(&self) -> Rounded {
+        Rounded::new(self.coords.iter())
+    }
 }
@@ -1,30 +1,35 @@
 //! Contains methods to generate many symmetry groups.
 
+pub mod cached;
 pub mod cyclic;
 pub mod gen_iter;
 pub mod group_item;
 pub mod pairs;
 pub mod permutation;
+pub mod schoenflies;
 
 pub use gen_iter::*;
 
 use std::{
-    array, iter,
+    array,
+    collections::{BTreeMap, BTreeSet},
+    iter,
     iter::{Filter, Map, Once},
     vec,
 };
 
 use crate::{
     cox::{cd::CdResult, Cox},
-    float::Float,
-    geometry::Matrix,
+    float::{Float, Real},
+    geometry::{Matrix, MatrixOrd, Point, PointOrd},
 };
 
 use self::{
+    cached::CachedGroup,
     cyclic::Cyclic,
-    group_item::GroupItem,
+    group_item::{GroupItem, Wrapper},
     pairs::{AsPair, PairMap},
-    permutation::{PermutationIter, SPermutation},
+    permutation::{Permutation, PermutationIter, SPermutation},
 };
 
 use nalgebra::{
@@ -68,6 +73,10 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         self.iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
 impl<I: Iterator> Group<I>
@@ -112,6 +121,16 @@ where
         unsafe { Group::new(self.dim, self.collect::<Vec<_>>().into_iter()) }
     }
 
+    /// Gets all elements of `self` and indexes them, so that the order of the
+    /// group, membership, and products can be queried without generating the
+    /// group again.
+    pub fn cached(self) -> CachedGroup<I::Item>
+    where
+        I::Item: Clone,
+    {
+        self.into()
+    }
+
     /// Returns the permutation group associated to `self`.
     pub fn permutations<D: nalgebra::Dim>(self) -> Group<PermutationIter<I::Item, D>>
     where
@@ -123,6 +142,25 @@ where
     }
 }
 
+impl<I: Iterator + Clone> Group<I>
+where
+    I::Item: GroupItem,
+{
+    /// Returns the order of the group, without consuming it.
+    ///
+    /// If the underlying iterator knows its exact length, as is the case for a
+    /// group returned by [`Self::cache`], this takes constant time. Otherwise,
+    /// this clones the iterator and generates every element of the group,
+    /// which can be as expensive as the group is large. Cache the group first
+    /// if you'll need its elements afterwards.
+    pub fn order(&self) -> usize {
+        match self.iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => lower,
+            _ => self.iter.clone().count(),
+        }
+    }
+}
+
 impl<T: GroupItem> Group<Once<T>> {
     /// Builds the group containing only the identity.
     pub fn trivial(dim: T::Dim) -> Self {
@@ -147,7 +185,7 @@ impl<T: Float> Group<Cyclic<Matrix<T>>> {
     }
 }
 
-impl Group<GenIter<Matrix<f64>>> {
+impl Group<GenIter<Matrix<Real>>> {
     /// Parses a diagram and turns it into a Coxeter group.
     pub fn parse(input: &str) -> CdResult<Option<Self>> {
         GenIter::parse(input).map(|gens| gens.map(Into::into))
@@ -194,6 +232,60 @@ impl Group<GenIter<Matrix<f64>>> {
     }
 }
 
+impl<T: Float> Group<vec::IntoIter<Matrix<T>>> {
+    /// Generates the group spanned by a set of matrices using Dimino's
+    /// algorithm. Matrices are compared up to [`Float::EPS`].
+    ///
+    /// The generators are added one at a time. Each time, the group spanned by
+    /// the previous generators is a subgroup of the new one, so the new group
+    /// can be enumerated coset by coset, which avoids most of the redundant
+    /// products of a naive search. If the generators don't span a finite group,
+    /// this will never halt.
+    pub fn from_generators(dim: usize, gens: Vec<Matrix<T>>) -> Self {
+        let mut elements = vec![Matrix::identity(dim, dim)];
+        let mut found = BTreeSet::new();
+        found.insert(MatrixOrd::new(elements[0].clone()));
+
+        for (i, gen) in gens.iter().enumerate() {
+            if found.contains(MatrixOrd::as_wrapper(gen)) {
+                continue;
+            }
+
+            // The subgroup spanned by the previous generators.
+            let subgroup_order = elements.len();
+
+            // The representatives of the right cosets of the subgroup we've
+            // found so far, starting with the subgroup itself.
+            let mut reps = vec![Matrix::identity(dim, dim)];
+            let mut idx = 0;
+
+            while idx < reps.len() {
+                for g in &gens[..=i] {
+                    let rep = &reps[idx] * g;
+                    if found.contains(MatrixOrd::as_wrapper(&rep)) {
+                        continue;
+                    }
+
+                    // Adds the entire coset at once.
+                    for j in 0..subgroup_order {
+                        let el = &elements[j] * &rep;
+                        found.insert(MatrixOrd::new(el.clone()));
+                        elements.push(el);
+                    }
+
+                    reps.push(rep);
+                }
+
+                idx += 1;
+            }
+        }
+
+        // Safety: the elements are closed under multiplication by the
+        // generators, so they form the group they span.
+        unsafe { Group::new(dim, elements.into_iter()) }
+    }
+}
+
 impl<T: GroupItem> Group<array::IntoIter<T, 2>> {
     /// Creates a group with two elements: the identity and the specified
     /// generator.
@@ -263,6 +355,24 @@ impl<T: Float> Group<MatrixProductIter<T>> {
     }
 }
 
+/// Returns the orthogonal matrix nearest to a given one, which is the
+/// orthogonal factor of its polar decomposition. Returns `None` if the singular
+/// value decomposition fails to converge.
+pub fn nearest_orthogonal<T: Float>(mat: &Matrix<T>) -> Option<Matrix<T>> {
+    let svd = mat.clone().svd(true, true);
+    Some(svd.u? * svd.v_t?)
+}
+
+/// Returns whether a matrix, which should be orthogonal up to floating point
+/// error, is a rotation rather than a rotoreflection.
+///
+/// The determinant of the matrix itself can't be trusted, as the accumulated
+/// error might push it far from ±1. Instead, we find the nearest orthogonal
+/// matrix, whose determinant is exactly ±1, and take its sign.
+fn is_rotation<T: Float>(mat: &Matrix<T>) -> bool {
+    nearest_orthogonal(mat).map_or(false, |mat| mat.determinant() > T::ZERO)
+}
+
 /// An iterator over the elements of a matrix group.
 impl<T: Float, I: Iterator<Item = Matrix<T>>> Group<I> {
     /// Pads all elements of `self` by adding the specified amount of
@@ -277,13 +387,145 @@ impl<T: Float, I: Iterator<Item = Matrix<T>>> Group<I> {
         // and inverses.
         //
         // The determinant might not be exactly 1, so we're extra lenient and
-        // just test for positive determinants, which are likewise closed under
-        // multiplications and inverses.
-        unsafe { self.sub(|el| el.determinant() > T::ZERO) }
+        // classify each matrix by its nearest orthogonal matrix, whose
+        // determinant is exactly ±1.
+        unsafe { self.sub(is_rotation) }
+    }
+
+    /// Snaps every element of the group to the nearest orthogonal matrix. This
+    /// gets rid of the floating point error accumulated by long products of
+    /// matrices, so that equal elements are more reliably found to be equal.
+    pub fn orthogonalize(self) -> Group<impl Iterator<Item = Matrix<T>>> {
+        let dim = self.dim;
+
+        // Safety: the error in each matrix is assumed to be small, so each of
+        // them is snapped to the orthogonal matrix it approximates.
+        unsafe {
+            self.iso(dim, |el| {
+                nearest_orthogonal(&el).expect("SVD of a group element didn't converge")
+            })
+        }
+    }
+
+    /// Builds the [stabilizer](https://en.wikipedia.org/wiki/Group_action#Fixed_points_and_stabilizer_subgroups)
+    /// of a point, that is, the subgroup of elements that fix it. Points are
+    /// compared up to [`Float::EPS`].
+    pub fn stabilizer(self, p: &Point<T>) -> Group<impl Iterator<Item = Matrix<T>>> {
+        let p = p.clone();
+
+        // Safety: the elements fixing a point are closed under multiplication
+        // and inverses.
+        unsafe { self.sub(move |el| (el * &p - &p).norm() < T::EPS) }
+    }
+
+    /// Returns whether two groups contain exactly the same matrices, up to
+    /// [`Float::EPS`].
+    pub fn set_eq<J: Iterator<Item = Matrix<T>>>(self, other: Group<J>) -> bool {
+        if self.dim != other.dim {
+            return false;
+        }
+
+        let mut elements: Vec<_> = self.map(MatrixOrd::new).collect();
+        let mut other_elements: Vec<_> = other.map(MatrixOrd::new).collect();
+        elements.sort();
+        other_elements.sort();
+
+        elements == other_elements
+    }
+
+    /// Returns the orbit of a point under the group, that is, the images of the
+    /// point under every element. Points are deduplicated up to
+    /// [`Float::EPS`].
+    pub fn orbit(self, seed: &Point<T>) -> Vec<Point<T>> {
+        let mut found = BTreeSet::new();
+        let mut orbit = Vec::new();
+
+        for mat in self {
+            let point = mat * seed;
+            if found.insert(PointOrd::new(point.clone())) {
+                orbit.push(point);
+            }
+        }
+
+        orbit
+    }
+
+    /// Returns whether every pair of elements of the group commutes, up to
+    /// [`Float::EPS`]. This stops as soon as a non-commuting pair is found.
+    pub fn is_abelian(self) -> bool {
+        let elements: Vec<_> = self.collect();
+
+        elements.iter().enumerate().all(|(i, g)| {
+            elements[..i]
+                .iter()
+                .all(|x| GroupItem::eq(&(g * x), &(x * g)))
+        })
+    }
+
+    /// Returns the [center](https://en.wikipedia.org/wiki/Center_(group_theory))
+    /// of the group, that is, the subgroup of elements that commute with every
+    /// other element. Matrices are compared up to [`Float::EPS`].
+    pub fn center(self) -> Group<vec::IntoIter<Matrix<T>>> {
+        let dim = self.dim;
+        let elements: Vec<_> = self.collect();
+
+        let center: Vec<_> = elements
+            .iter()
+            .filter(|&g| elements.iter().all(|x| GroupItem::eq(&(g * x), &(x * g))))
+            .cloned()
+            .collect();
+
+        // Safety: the center of a group is always a subgroup.
+        unsafe { Group::new(dim, center.into_iter()) }
+    }
+
+    /// Partitions the elements of the group into their
+    /// [conjugacy classes](https://en.wikipedia.org/wiki/Conjugacy_class),
+    /// sorted by size. Matrices are compared up to [`Float::EPS`].
+    ///
+    /// This takes time proportional to the square of the order of the group.
+    pub fn conjugacy_classes(self) -> Vec<Vec<Matrix<T>>> {
+        let elements: Vec<_> = self.collect();
+        let inverses: Vec<_> = elements.iter().map(GroupItem::inv).collect();
+
+        // Indexes each element of the group.
+        let indices: BTreeMap<_, _> = elements
+            .iter()
+            .enumerate()
+            .map(|(i, el)| (MatrixOrd::new(el.clone()), i))
+            .collect();
+
+        let mut visited = vec![false; elements.len()];
+        let mut classes = Vec::new();
+
+        for (i, g) in elements.iter().enumerate() {
+            if visited[i] {
+                continue;
+            }
+
+            // Conjugates g by every element of the group.
+            let mut class = Vec::new();
+            for (x, x_inv) in elements.iter().zip(&inverses) {
+                let j = *indices
+                    .get(&MatrixOrd::new(x * g * x_inv))
+                    .expect("the group is not closed under conjugation");
+
+                if !visited[j] {
+                    visited[j] = true;
+                    class.push(elements[j].clone());
+                }
+            }
+
+            classes.push(class);
+        }
+
+        classes.sort_by_key(Vec::len);
+        classes
     }
 
     /// Returns the group determined by all products between elements of the
-    /// first and the second group.
+    /// first and the second group. Either of them can be a [`CachedGroup`],
+    /// through [`CachedGroup::group`].
     ///
     /// # Safety
     /// The user must make sure the groups commute with one another, or the
@@ -300,6 +542,46 @@ impl<T: Float, I: Iterator<Item = Matrix<T>>> Group<I> {
         )
     }
 
+    /// Returns the group determined by all products between elements of the
+    /// first and the second group, or `None` if these don't form a group. This
+    /// is a safe version of [`Self::matrix_product`], meant for groups that
+    /// might not commute.
+    ///
+    /// We check that no two products coincide, and that the products are
+    /// closed under multiplication, up to [`Float::EPS`]. This takes quadratic
+    /// time in the order of the product.
+    pub fn checked_matrix_product<J: Iterator<Item = Matrix<T>>>(
+        self,
+        g: Group<J>,
+    ) -> Option<Group<vec::IntoIter<Matrix<T>>>> {
+        if self.dim != g.dim {
+            return None;
+        }
+
+        let dim = self.dim;
+
+        // Safety: we check that the result is a group before returning it.
+        let elements: Vec<_> = unsafe { self.matrix_product(g) }.collect();
+        let mut found = BTreeSet::new();
+        for el in &elements {
+            if !found.insert(MatrixOrd::new(el.clone())) {
+                return None;
+            }
+        }
+
+        for a in &elements {
+            for b in &elements {
+                if !found.contains(MatrixOrd::as_wrapper(&(a * b))) {
+                    return None;
+                }
+            }
+        }
+
+        // Safety: the elements are closed under multiplication. Since there's
+        // finitely many, they also contain the identity and their inverses.
+        Some(unsafe { Group::new(dim, elements.into_iter()) })
+    }
+
     /// Returns the specified group with central inversion appended to all
     /// elements.
     ///
@@ -385,6 +667,8 @@ impl<T: Float, I: Iterator<Item = Matrix<T>>> Group<I> {
 
     /// Builds a swirlchoron group. This is the diploid group construction from
     /// "On Quaternions and Octonions" by John H. Conway and Derek A. Smith.
+    /// Either of the groups can be a [`CachedGroup`], through
+    /// [`CachedGroup::group`].
     ///
     /// # Safety
     /// Both groups must be rotation groups.
@@ -408,58 +692,46 @@ impl<T: Float, I: Iterator<Item = Matrix<T>>> Group<I> {
         self.iso(2 * dim, move |mat| direct_sum(&mat, &f(&mat)))
     }
 
-    /*
     /// Generates the [wreath product](https://en.wikipedia.org/wiki/Wreath_product)
-    /// of two symmetry groups.
-    pub fn wreath<D: nalgebra::Dim, J: Iterator<Item = Permutation<D>>>(self, h: Group<J>) -> Self
+    /// of a symmetry group with a permutation group on `n` elements.
+    ///
+    /// The result acts on `n` orthogonal copies of the space of `self`. Its
+    /// elements are the block matrices with a single nonzero block in each row
+    /// and column, arranged according to some permutation in `h`, and with
+    /// each block an arbitrary element of `self`.
+    pub fn wreath<D: nalgebra::Dim, J: Iterator<Item = Permutation<D>>>(
+        self,
+        h: Group<J>,
+    ) -> Group<impl Iterator<Item = Matrix<T>>>
     where
-        J::Item: GroupItem,
+        DefaultAllocator: Allocator<usize, D>,
     {
-        let h = h.elements();
-        let g_dim = g.dim;
-        let dim = g_dim * h.len();
-
-        // Indexes each element in h.
-        let mut h_indices = BTreeMap::new();
-
-        for (i, h_el) in h.iter().enumerate() {
-            h_indices.insert(MatrixOrd::new(h_el.clone()), i);
+        let n = h.dim.value();
+        let g_dim = self.dim;
+        let dim = g_dim * n;
+        let permutations: Vec<_> = h.collect();
+
+        // Computes the direct product of g with itself n times.
+        let g = self.cache();
+        let mut g_prod = Group::trivial(0).cache();
+        for _ in 0..n {
+            g_prod = g_prod.direct_product(g.clone()).cache();
         }
 
-        // Converts h into a permutation group.
-        let mut permutations = Vec::with_capacity(h.len());
-
-        for h_el_1 in &h {
-            let mut perm = Vec::with_capacity(h.len());
-
-            for h_el_2 in &h {
-                perm.push(
-                    *h_indices
-                        .get(&MatrixOrd::new(h_el_1 * h_el_2))
-                        .expect("h is not a valid group!"),
-                );
-            }
-
-            permutations.push(perm);
-        }
-
-        // Computes the direct product of g with itself |h| times.
-        let g_prod = vec![&g; h.len() - 1]
-            .into_iter()
-            .cloned()
-            .fold(g.clone(), Group::direct_product);
-
-        Self::new(
-            dim,
-            g_prod
-                .map(move |g_el| {
-                    let mut matrices = Vec::new();
+        // Safety: the block monomial matrices with blocks in a group and
+        // pattern in a permutation group are closed under products and
+        // inverses.
+        unsafe {
+            Group::new(
+                dim,
+                g_prod.flat_map(move |g_el| {
+                    let mut matrices = Vec::with_capacity(permutations.len());
 
                     for perm in &permutations {
                         let mut new_el = Matrix::zeros(dim, dim);
 
                         // Permutes the blocks on the diagonal of g_el.
-                        for (i, &j) in perm.iter().enumerate() {
+                        for (i, j) in perm.iter().enumerate() {
                             for x in 0..g_dim {
                                 for y in 0..g_dim {
                                     new_el[(i * g_dim + x, j * g_dim + y)] =
@@ -471,12 +743,11 @@ impl<T: Float, I: Iterator<Item = Matrix<T>>> Group<I> {
                         matrices.push(new_el);
                     }
 
-                    matrices.into_iter()
-                })
-                .flatten(),
-        )
+                    matrices
+                }),
+            )
+        }
     }
-    */
 }
 
 /// Converts a matrix into a unit quaternion.
@@ -534,18 +805,18 @@ mod tests {
     use gcd::Gcd;
 
     /// Tests a given symmetry group.
-    fn test<I: Iterator<Item = Matrix<f64>>>(
+    fn test<I: Iterator<Item = Matrix<Real>>>(
         group: Group<I>,
         order: usize,
         rot_order: usize,
         name: &str,
     ) {
         // Makes testing multiple derived groups faster.
-        let group = group.cache();
+        let group = group.cached();
 
         // Tests the order of the group.
         assert_eq!(
-            group.clone().count(),
+            group.order(),
             order,
             "{} does not have the expected order.",
             name
@@ -553,7 +824,7 @@ mod tests {
 
         // Tests the order of the rotational subgroup.
         assert_eq!(
-            group.rotations().count(),
+            group.group().rotations().count(),
             rot_order,
             "The rotational group of {} does not have the expected order.",
             name
@@ -561,7 +832,7 @@ mod tests {
     }
 
     /// Parses a CD and unwraps it.
-    fn parse_unwrap(input: &str) -> Group<GenIter<Matrix<f64>>> {
+    fn parse_unwrap(input: &str) -> Group<GenIter<Matrix<Real>>> {
         Group::parse(input).unwrap().unwrap()
     }
 
@@ -675,12 +946,87 @@ mod tests {
         test(parse_unwrap("o5o3o3o"), 14400, 7200, "H4");
     }
 
+    /// Tests groups given by diagrams with fractional edges and loops.
+    /// Diagrams that describe affine or hyperbolic groups give no group at
+    /// all.
+    #[test]
+    fn stars_and_loops() {
+        test(parse_unwrap("o5/2o5o"), 120, 60, "H3 from o5/2o5o");
+        test(parse_unwrap("o3/2o3/2o3/2*a"), 24, 12, "A3 from a loop");
+
+        for cd in ["o3o3o3*a", "o4o4o", "o4o4o4*a"] {
+            assert!(Group::parse(cd).unwrap().is_none(), "{} is infinite", cd);
+        }
+    }
+
     /// Tests the E6 symmetry group.
     #[test]
     fn e6() {
         test(parse_unwrap("o3o3o3o3o *c3o"), 51840, 25920, "E6");
     }
 
+    /// A matrix whose products are perturbed by a few multiples of
+    /// [`Float::EPS`], simulating a float type with a lot of rounding error.
+    #[derive(Clone)]
+    #[repr(transparent)]
+    struct Noisy(Matrix<Real>);
+
+    unsafe impl Wrapper<Noisy> for MatrixOrd<Real> {
+        fn from_inner(inner: Noisy) -> Self {
+            Self(inner.0)
+        }
+
+        fn into_inner(self) -> Noisy {
+            Noisy(self.0)
+        }
+    }
+
+    impl GroupItem for Noisy {
+        type Dim = usize;
+        type FuzzyOrd = MatrixOrd<Real>;
+
+        fn id(dim: usize) -> Self {
+            Self(GroupItem::id(dim))
+        }
+
+        fn inv(&self) -> Self {
+            Self(GroupItem::inv(&self.0))
+        }
+
+        fn mul(&self, rhs: &Self) -> Self {
+            let mut mat = &self.0 * &rhs.0;
+
+            // Deterministic noise, so that the test is reproducible.
+            for x in mat.iter_mut() {
+                let hash = (*x as f64).to_bits().wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 40;
+                let noise = hash as f64 / (1 << 24) as f64 - 0.5;
+                *x += Real::EPS * 4.0 * noise as Real;
+            }
+
+            Self(mat)
+        }
+
+        fn mul_assign(&mut self, rhs: &Self) {
+            *self = GroupItem::mul(self, rhs);
+        }
+
+        fn rounded(&self) -> group_item::Rounded {
+            self.0.rounded()
+        }
+    }
+
+    /// Tests that the floating point drift doesn't inflate the order of E6.
+    #[test]
+    fn e6_noisy() {
+        let gens: Vec<_> = GenIter::parse_unwrap("o3o3o3o3o *c3o")
+            .gens
+            .into_iter()
+            .map(Noisy)
+            .collect();
+
+        assert_eq!(GenIter::new(6, gens).count(), 51840);
+    }
+
     #[test]
     fn pairs() {
         assert_eq!(
@@ -697,14 +1043,335 @@ mod tests {
     #[test]
     #[cfg(not(debug_assertions))]
     fn e7() {
+        test(parse_unwrap("o3o3o3o3o3o *c3o"), 2903040, 1451520, "E7");
+    }
+
+    /// Tests the D8 symmetry group, a subgroup of index 135 of E8. This is
+    /// even more expensive, so it's only run manually.
+    #[test]
+    #[ignore]
+    fn d8() {
+        test(parse_unwrap("o3o3o3o3o3o3o *b3o"), 5160960, 2580480, "D8");
+    }
+
+    /// Checks that the elements of a group are found in the same order,
+    /// regardless of the number of threads.
+    #[test]
+    fn threads() {
+        let gen_iter = GenIter::parse_unwrap("o5o3o3o");
+        let single: Vec<_> = gen_iter.clone().with_threads(1).collect();
+        let multi: Vec<_> = gen_iter.with_threads(3).collect();
+
+        assert_eq!(single.len(), 14400);
+        assert_eq!(single, multi);
+    }
+
+    /// Returns the sizes of the conjugacy classes of a group.
+    fn class_sizes<I: Iterator<Item = Matrix<Real>>>(group: Group<I>) -> Vec<usize> {
+        group.conjugacy_classes().iter().map(Vec::len).collect()
+    }
+
+    #[test]
+    /// Tests the conjugacy classes of A3, isomorphic to the symmetric group on
+    /// four elements.
+    fn a3_classes() {
+        assert_eq!(class_sizes(Group::simplex(3)), vec![1, 3, 6, 6, 8]);
+    }
+
+    #[test]
+    /// Tests the conjugacy classes of I2(6).
+    fn i2_6_classes() {
+        assert_eq!(class_sizes(Group::dihedral_2(6)), vec![1, 1, 2, 2, 3, 3]);
+    }
+
+    #[test]
+    /// Tests that the I2(*n*) groups match the groups generated by a rotation
+    /// and a reflection.
+    fn dihedral_set_eq() {
+        for n in 2..=10 {
+            let (s, c) = (Real::TAU / n as Real).sin_cos();
+            let gens = vec![dmatrix![c, -s; s, c], dmatrix![-1.0, 0.0; 0.0, 1.0]];
+            let group: Group<_> = GenIter::new(2, gens).into();
+
+            assert!(Group::dihedral_2(n).set_eq(group));
+            assert!(!Group::dihedral_2(n).set_eq(Group::<Cyclic<Matrix<Real>>>::cyclic(n)));
+        }
+    }
+
+    #[test]
+    /// Tests the centers of BC3 and A3.
+    fn center() {
+        let center: Vec<_> = Group::hypercube(3).center().collect();
+        assert_eq!(center.len(), 2);
+        assert!(center
+            .iter()
+            .any(|g| GroupItem::eq(g, &Matrix::identity(3, 3))));
+        assert!(center
+            .iter()
+            .any(|g| GroupItem::eq(g, &-Matrix::identity(3, 3))));
+
+        let center: Vec<_> = Group::simplex(3).center().collect();
+        assert_eq!(center.len(), 1);
+        assert!(GroupItem::eq(&center[0], &Matrix::identity(3, 3)));
+    }
+
+    #[test]
+    /// Tests the orbits of a few points.
+    fn orbit() {
+        let seed = Point::from_element(3, 1.0);
+        assert_eq!(Group::hypercube(3).orbit(&seed).len(), 8);
+
+        // A3 as the subgroup of B3 with an even number of sign changes.
+        let a3 = Group::from_generators(
+            3,
+            vec![
+                dmatrix![0.0, 1.0, 0.0; 1.0, 0.0, 0.0; 0.0, 0.0, 1.0],
+                dmatrix![1.0, 0.0, 0.0; 0.0, 0.0, 1.0; 0.0, 1.0, 0.0],
+                dmatrix![0.0, -1.0, 0.0; -1.0, 0.0, 0.0; 0.0, 0.0, 1.0],
+            ],
+        );
+        assert_eq!(a3.order(), 24);
+        assert_eq!(a3.orbit(&seed).len(), 4);
+
+        assert_eq!(
+            Group::pentagonal(4)
+                .orbit(&Point::from_element(4, 0.3))
+                .len(),
+            14400
+        );
+    }
+
+    #[test]
+    /// Tests that generic points have an orbit as large as the group, and that
+    /// the orbit-stabilizer theorem holds.
+    fn generic_orbit() {
+        let generic = Point::from_vec(vec![0.1, 0.2, 0.3]);
+        assert_eq!(Group::simplex(3).orbit(&generic).len(), 24);
+        assert_eq!(Group::hypercube(3).orbit(&generic).len(), 48);
+
+        for p in [
+            Point::from_element(3, 1.0),
+            Point::from_vec(vec![1.0, 1.0, 0.0]),
+            Point::from_vec(vec![1.0, 0.0, 0.0]),
+            generic,
+        ] {
+            let orbit = Group::hypercube(3).orbit(&p).len();
+            let stabilizer = Group::hypercube(3).stabilizer(&p).count();
+            assert_eq!(orbit * stabilizer, 48);
+        }
+    }
+
+    #[test]
+    /// Tests the stabilizers of a few points under B3.
+    fn stabilizer() {
+        let vertex = Point::from_element(3, 1.0);
         test(
-            Group::parse_unwrap("o3o3o3o3o3o *c3o"),
-            2903040,
-            1451520,
-            "E7",
+            Group::hypercube(3).stabilizer(&vertex),
+            6,
+            3,
+            "B3 vertex stabilizer",
+        );
+
+        let edge = Point::from_vec(vec![1.0, 1.0, 0.0]);
+        test(
+            Group::hypercube(3).stabilizer(&edge),
+            4,
+            2,
+            "B3 edge stabilizer",
+        );
+
+        let generic = Point::from_vec(vec![3.0, 2.0, 1.0]);
+        test(
+            Group::hypercube(3).stabilizer(&generic),
+            1,
+            1,
+            "B3 generic stabilizer",
+        );
+    }
+
+    #[test]
+    /// Tests whether some groups are abelian.
+    fn abelian() {
+        assert!(Group::<Cyclic<Matrix<Real>>>::cyclic(7).is_abelian());
+        assert!(Group::<MatrixProductIter<Real>>::dihedral_2(2).is_abelian());
+        assert!(!Group::<MatrixProductIter<Real>>::dihedral_2(3).is_abelian());
+        assert!(!Group::simplex(3).is_abelian());
+    }
+
+    #[test]
+    /// Tests that the order of a group can be read without consuming it.
+    fn order() {
+        let a3 = Group::simplex(3);
+        assert_eq!(a3.order(), 24);
+        assert_eq!(a3.count(), 24);
+
+        let a3 = Group::simplex(3).cache();
+        assert_eq!(a3.order(), 24);
+        assert_eq!(a3.count(), 24);
+    }
+
+    #[test]
+    /// Tests element lookup and the multiplication table of a cached group.
+    fn cached() {
+        let a3 = Group::simplex(3).cached();
+        assert_eq!(a3.order(), 24);
+
+        let id = Matrix::identity(3, 3);
+        assert!(a3.contains(&id));
+        assert!(!a3.contains(&-&id));
+        assert!(a3.contains(&(id.clone() * (1.0 + Real::EPS / 10.0))));
+
+        for (i, el) in a3.elements().iter().enumerate() {
+            assert_eq!(a3.index_of(el), Some(i));
+        }
+
+        // Every row and column of the table must be a permutation.
+        let id = a3.index_of(&id).unwrap();
+        let table = a3.mul_table();
+        for (i, row) in table.iter().enumerate() {
+            assert_eq!(table[id][i], i);
+            assert_eq!(row[id], i);
+
+            let mut row = row.clone();
+            row.sort_unstable();
+            assert!(row.into_iter().eq(0..24));
+        }
+
+        // The cached group can be used as a lazy one.
+        let g = Group::direct_product(a3.group(), a3.group());
+        test(g, 576, 288, "A3×A3");
+    }
+
+    #[test]
+    /// Tests that the rotation subgroup of a reflection group is normal of
+    /// index 2.
+    fn rotation_cosets() {
+        for cd in ["o3o3o", "o4o3o", "o5o3o", "o3o3o3o"] {
+            let group = parse_unwrap(cd).cached();
+            let rotations = parse_unwrap(cd).rotations().cached();
+
+            let cosets = group.cosets(&rotations);
+            assert_eq!(cosets.len(), 2, "{}", cd);
+            assert!(cosets[0]
+                .iter()
+                .all(|&i| rotations.contains(&group.elements()[i])));
+            assert!(group.is_normal(&rotations), "{}", cd);
+            assert_eq!(group.quotient(&rotations).unwrap().order(), 2, "{}", cd);
+        }
+    }
+
+    #[test]
+    /// Tests that long products of elements of B4 are still classified
+    /// correctly as rotations or rotoreflections, and that snapping them to
+    /// orthogonal matrices doesn't change this.
+    fn rotation_stress() {
+        let elements: Vec<_> = Group::hypercube(4).collect();
+        let mut seed = 12345_usize;
+
+        for _ in 0..20 {
+            let mut product = Matrix::identity(4, 4);
+            let mut rotation = true;
+
+            // Composes 50 pseudorandom elements.
+            for _ in 0..50 {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let el = &elements[(seed >> 33) % elements.len()];
+                rotation ^= el.determinant() < 0.0;
+                product *= el;
+            }
+
+            let group = Group::from_generators(4, vec![product]);
+            let count = group.clone().count();
+            let rot_count = if rotation { count } else { count / 2 };
+            assert_eq!(group.clone().rotations().count(), rot_count);
+            assert_eq!(group.orthogonalize().rotations().count(), rot_count);
+        }
+
+        // Central inversion in odd dimensions is a rotoreflection.
+        assert_eq!(
+            Group::<array::IntoIter<Matrix<f64>, 2>>::central_inv(3)
+                .rotations()
+                .count(),
+            1
+        );
+        assert_eq!(
+            Group::<array::IntoIter<Matrix<f64>, 2>>::central_inv(4)
+                .rotations()
+                .count(),
+            2
         );
     }
 
+    #[test]
+    /// Tests that checked products of groups only succeed when they form a
+    /// group.
+    fn checked_matrix_product() {
+        // The pyritohedral group.
+        let g = Group::simplex(3)
+            .rotations()
+            .checked_matrix_product(Group::central_inv(3))
+            .unwrap();
+        test(g, 24, 12, "±A3+");
+
+        // B3 already contains central inversion.
+        assert!(Group::hypercube(3)
+            .checked_matrix_product(Group::central_inv(3))
+            .is_none());
+
+        // Rotations about two different axes don't span each other's products.
+        let perm = dmatrix![0.0, 0.0, 1.0; 1.0, 0.0, 0.0; 0.0, 1.0, 0.0];
+        let x_rot = unsafe {
+            Group::cyclic(4)
+                .pad(1)
+                .iso(3, |m| &perm * m * perm.transpose())
+        };
+        assert!(Group::cyclic(4)
+            .pad(1)
+            .checked_matrix_product(x_rot)
+            .is_none());
+
+        // Groups of different dimensions can't be multiplied.
+        assert!(Group::<Cyclic<Matrix<f64>>>::cyclic(3)
+            .checked_matrix_product(Group::central_inv(3))
+            .is_none());
+    }
+
+    #[test]
+    /// Tests quotients by central inversion, and by a non-normal subgroup.
+    fn quotient() {
+        let bc3 = Group::hypercube(3).cached();
+        let pm_i = Group::central_inv(3).cached();
+        let quotient = bc3.quotient(&pm_i).unwrap();
+        assert_eq!(quotient.order(), 24);
+
+        // The subgroup generated by a single reflection isn't normal.
+        let a3 = Group::simplex(3).cached();
+        let refl = a3
+            .elements()
+            .iter()
+            .find(|el| el.determinant() < 0.0)
+            .unwrap()
+            .clone();
+        let sub = Group::cyclic_gen(3, refl).cached();
+        assert_eq!(a3.cosets(&sub).len(), 12);
+        assert!(!a3.is_normal(&sub));
+        assert!(a3.quotient(&sub).is_none());
+    }
+
+    #[test]
+    /// Tests Dimino's algorithm by building H3 from its generators.
+    fn from_generators() {
+        let gens: Vec<_> = GenIter::parse_unwrap("o5o3o").gens;
+        let h3 = Group::from_generators(3, gens.clone());
+        assert!(h3.set_eq(Group::pentagonal(3)));
+
+        // H3 is also generated by a reflection and a rotation.
+        let h3 = Group::from_generators(3, vec![gens[0].clone(), &gens[1] * &gens[2]]);
+        test(h3, 120, 60, "H3");
+    }
+
     #[test]
     /// Tests the direct product of A3 with itself.
     fn a3xa3() {
@@ -713,16 +1380,16 @@ mod tests {
         test(g, 576, 288, "A3×A3");
     }
 
-    /* #[test]
+    #[test]
     /// Tests the wreath product of A3 with A1.
     fn a3_wr_a1() {
         test(
-            Group::wreath(Group::a(3), Group::a(1)),
+            Group::wreath(Group::simplex(3), Group::involution()),
             1152,
             576,
             "A3 ≀ A1",
         );
-    }*/
+    }
 
     #[test]
     /// Tests out some step prisms.
@@ -27,7 +27,7 @@ where
     DefaultAllocator: Allocator<usize, N>,
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(&other.0)
+        Some(Ord::cmp(self, other))
     }
 }
 
@@ -36,7 +36,9 @@ where
     DefaultAllocator: Allocator<usize, N>,
 {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.0.partial_cmp(&other.0).unwrap()
+        // Matrices are only partially ordered entrywise, so we compare the
+        // entries lexicographically instead.
+        self.0.as_slice().cmp(other.0.as_slice())
     }
 }
 
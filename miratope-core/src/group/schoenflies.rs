@@ -0,0 +1,221 @@
+//! Builds the 3D point groups from their
+//! [Schoenflies symbols](https://en.wikipedia.org/wiki/Schoenflies_notation).
+
+use std::{fmt::Display, vec};
+
+use super::Group;
+use crate::{
+    float::{Float, Real},
+    geometry::Matrix,
+};
+
+use nalgebra::{dmatrix, Rotation3, Unit, Vector3};
+
+/// The result of parsing a Schoenflies symbol.
+pub type SchoenfliesResult<T> = Result<T, SchoenfliesError>;
+
+/// Represents an error while parsing a Schoenflies symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchoenfliesError {
+    /// The symbol was empty.
+    Empty,
+
+    /// The order of the principal axis is missing, zero, or too large.
+    InvalidOrder {
+        /// The position at which the order was expected.
+        pos: usize,
+    },
+
+    /// An unexpected character was found.
+    InvalidSymbol {
+        /// The position at which the reader found the error.
+        pos: usize,
+    },
+}
+
+impl Display for SchoenfliesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            // The symbol was empty.
+            Self::Empty => write!(f, "empty Schoenflies symbol"),
+
+            // The order of the principal axis is invalid.
+            Self::InvalidOrder { pos } => write!(f, "invalid axis order at position {}", pos),
+
+            // An unexpected character was found.
+            Self::InvalidSymbol { pos } => write!(f, "invalid symbol at position {}", pos),
+        }
+    }
+}
+
+impl std::error::Error for SchoenfliesError {}
+
+/// Returns the rotation by 2π / n around the z axis.
+fn rotation(n: u32) -> Matrix<Real> {
+    let (s, c) = (Real::TAU / Real::u32(n)).fsin_cos();
+    dmatrix![c, -s, 0.0; s, c, 0.0; 0.0, 0.0, 1.0]
+}
+
+/// Returns the diagonal matrix with the given entries.
+fn diag(x: Real, y: Real, z: Real) -> Matrix<Real> {
+    dmatrix![x, 0.0, 0.0; 0.0, y, 0.0; 0.0, 0.0, z]
+}
+
+/// The reflection through the xy plane.
+fn sigma_h() -> Matrix<Real> {
+    diag(1.0, 1.0, -1.0)
+}
+
+/// The rotation by 2π / 3 around the (1, 1, 1) axis, which cycles the
+/// coordinates.
+fn cycle() -> Matrix<Real> {
+    dmatrix![0.0, 0.0, 1.0; 1.0, 0.0, 0.0; 0.0, 1.0, 0.0]
+}
+
+/// The rotation by 2π / 5 around the (0, 1, φ) axis, which goes through a
+/// vertex of an icosahedron with vertices at the cyclic permutations of
+/// (0, ±1, ±φ).
+fn icosahedral_rotation() -> Matrix<Real> {
+    let axis = Unit::new_normalize(Vector3::new(0.0, 1.0, (1.0 + Real::SQRT_5) / 2.0));
+    let rotation = Rotation3::from_axis_angle(&axis, Real::TAU / 5.0);
+    Matrix::from_iterator(3, 3, rotation.matrix().iter().copied())
+}
+
+/// Reads the order of the principal axis, starting at a given position.
+/// Returns the order and the position right after it.
+fn read_order(symbol: &str, pos: usize) -> SchoenfliesResult<(u32, usize)> {
+    let len = symbol[pos..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(symbol.len() - pos);
+
+    match symbol[pos..pos + len].parse() {
+        Ok(n) if n != 0 => Ok((n, pos + len)),
+        _ => Err(SchoenfliesError::InvalidOrder { pos }),
+    }
+}
+
+impl Group<vec::IntoIter<Matrix<Real>>> {
+    /// Builds a 3D point group from its Schoenflies symbol. The principal axis
+    /// is the z axis, and the polyhedral groups are aligned with the
+    /// coordinate axes.
+    ///
+    /// The supported symbols are `Cn`, `Cnv`, `Cnh`, `Sn`, `Dn`, `Dnd`, `Dnh`,
+    /// `T`, `Td`, `Th`, `O`, `Oh`, `I`, and `Ih`, together with `Cs` and `Ci`.
+    pub fn schoenflies(symbol: &str) -> SchoenfliesResult<Self> {
+        let symbol = symbol.trim();
+        let family = symbol.chars().next().ok_or(SchoenfliesError::Empty)?;
+
+        // The order of the principal axis, and the suffix after it.
+        let (n, suffix_pos) = match (family, &symbol[family.len_utf8()..]) {
+            ('C', "s") => return Ok(Self::from_generators(3, vec![sigma_h()])),
+            ('C', "i") => return Ok(Self::from_generators(3, vec![-diag(1.0, 1.0, 1.0)])),
+            ('C', _) | ('S', _) | ('D', _) => read_order(symbol, 1)?,
+            ('T', _) | ('O', _) | ('I', _) => (0, 1),
+            _ => return Err(SchoenfliesError::InvalidSymbol { pos: 0 }),
+        };
+
+        let c2 = diag(-1.0, -1.0, 1.0);
+        let gens = match (family, &symbol[suffix_pos..]) {
+            ('C', "") => vec![rotation(n)],
+            ('C', "v") => vec![rotation(n), diag(1.0, -1.0, 1.0)],
+            ('C', "h") => vec![rotation(n), sigma_h()],
+            ('S', "") => vec![rotation(n) * sigma_h()],
+            ('D', "") => vec![rotation(n), diag(1.0, -1.0, -1.0)],
+            ('D', "d") => vec![rotation(2 * n) * sigma_h(), diag(1.0, -1.0, -1.0)],
+            ('D', "h") => vec![rotation(n), diag(1.0, -1.0, -1.0), sigma_h()],
+            ('T', "") => vec![cycle(), c2],
+            ('T', "d") => vec![
+                cycle(),
+                c2,
+                dmatrix![0.0, 1.0, 0.0; 1.0, 0.0, 0.0; 0.0, 0.0, 1.0],
+            ],
+            ('T', "h") => vec![cycle(), c2, -diag(1.0, 1.0, 1.0)],
+            ('O', "") => vec![cycle(), rotation(4)],
+            ('O', "h") => vec![cycle(), rotation(4), -diag(1.0, 1.0, 1.0)],
+            ('I', "") => vec![cycle(), c2, icosahedral_rotation()],
+            ('I', "h") => vec![cycle(), c2, icosahedral_rotation(), -diag(1.0, 1.0, 1.0)],
+            _ => return Err(SchoenfliesError::InvalidSymbol { pos: suffix_pos }),
+        };
+
+        Ok(Self::from_generators(3, gens))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchoenfliesError;
+    use crate::group::Group;
+
+    /// Checks the orders of the point groups against their textbook values.
+    #[test]
+    fn orders() {
+        for &(symbol, order) in &[
+            ("C1", 1),
+            ("C3", 3),
+            ("C12", 12),
+            ("C3v", 6),
+            ("C4h", 8),
+            ("Cs", 2),
+            ("Ci", 2),
+            ("S4", 4),
+            ("S6", 6),
+            ("S3", 6),
+            ("D2", 4),
+            ("D3", 6),
+            ("D2d", 8),
+            ("D3d", 12),
+            ("D5h", 20),
+            ("D6h", 24),
+            ("T", 12),
+            ("Td", 24),
+            ("Th", 24),
+            ("O", 24),
+            ("Oh", 48),
+            ("I", 60),
+            ("Ih", 120),
+        ] {
+            let group = Group::schoenflies(symbol).unwrap();
+            assert_eq!(group.order(), order, "order of {}", symbol);
+        }
+    }
+
+    /// Checks that point groups agree with the equivalent Coxeter groups, and
+    /// that they compose with other constructions.
+    #[test]
+    fn compose() {
+        let oh = Group::schoenflies("Oh").unwrap();
+        assert!(oh.set_eq(Group::hypercube(3)));
+
+        let s6 = Group::schoenflies("S6").unwrap();
+        let c3 = Group::schoenflies("C3").unwrap();
+        // Safety: C3 doesn't contain central inversion.
+        assert!(s6.set_eq(unsafe { c3.with_central_inv() }));
+    }
+
+    /// Checks that malformed symbols give errors.
+    #[test]
+    fn errors() {
+        for &(symbol, err) in &[
+            ("", SchoenfliesError::Empty),
+            ("  ", SchoenfliesError::Empty),
+            ("X3", SchoenfliesError::InvalidSymbol { pos: 0 }),
+            ("C", SchoenfliesError::InvalidOrder { pos: 1 }),
+            ("C0", SchoenfliesError::InvalidOrder { pos: 1 }),
+            ("Dv", SchoenfliesError::InvalidOrder { pos: 1 }),
+            ("C99999999999", SchoenfliesError::InvalidOrder { pos: 1 }),
+            ("D3x", SchoenfliesError::InvalidSymbol { pos: 2 }),
+            ("S4v", SchoenfliesError::InvalidSymbol { pos: 2 }),
+            ("Tv", SchoenfliesError::InvalidSymbol { pos: 1 }),
+            ("Oh2", SchoenfliesError::InvalidSymbol { pos: 1 }),
+            ("c3", SchoenfliesError::InvalidSymbol { pos: 0 }),
+            ("∞", SchoenfliesError::InvalidSymbol { pos: 0 }),
+        ] {
+            assert_eq!(
+                Group::schoenflies(symbol).err(),
+                Some(err),
+                "error for {:?}",
+                symbol
+            );
+        }
+    }
+}
@@ -29,6 +29,7 @@ pub mod cox;
 pub mod file;
 pub mod float;
 pub mod geometry;
+pub mod greek;
 pub mod group;
 
 use std::{collections::HashSet, error::Error, iter, ops::IndexMut};
@@ -499,3 +500,44 @@ pub(crate) fn test<T: Polytope, I: IntoIterator<Item = usize>>(poly: &T, element
 
     poly.assert_valid();
 }
+
+/// Returns the values C(*n*, 0), ..., C(*n*, *n*). These are the element
+/// counts of a simplex with *n* vertices.
+#[cfg(test)]
+pub(crate) fn choose(n: usize) -> Vec<usize> {
+    let mut res = Vec::with_capacity(n + 1);
+    res.push(1);
+
+    for k in 0..n {
+        res.push(res[k] * (n - k) / (k + 1));
+    }
+
+    res
+}
+
+/// Returns an iterator over the element counts of an orthoplex of rank *n*.
+/// Reversed, these are the element counts of a hypercube of rank *n*.
+#[cfg(test)]
+pub(crate) fn orthoplex_counts(n: usize) -> impl DoubleEndedIterator<Item = usize> {
+    choose(n - 1)
+        .into_iter()
+        .enumerate()
+        .map(|(k, c)| c << k)
+        .chain(std::iter::once(1))
+}
+
+/// Returns the element counts of the duoprism of two polytopes. Ignoring the
+/// minimal elements of the factors, these are the convolution of theirs.
+#[cfg(test)]
+pub(crate) fn duoprism_counts<T: Polytope>(p: &T, q: &T) -> Vec<usize> {
+    let mut counts = vec![0; p.rank() + q.rank()];
+    counts[0] = 1;
+
+    for i in 1..=p.rank() {
+        for j in 1..=q.rank() {
+            counts[i + j - 1] += p.el_count(i) * q.el_count(j);
+        }
+    }
+
+    counts
+}
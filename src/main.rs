@@ -13,28 +13,32 @@
 
 use bevy::prelude::*;
 use bevy::reflect::TypeUuid;
-use bevy::render::{camera::PerspectiveProjection, pipeline::PipelineDescriptor};
+use bevy::render::pipeline::{Face, PipelineDescriptor};
 use bevy_egui::EguiPlugin;
 use miratope_core::file::FromFile;
 use no_cull_pipeline::PbrNoBackfaceBundle;
 
 use ui::{
-    camera::{CameraInputEvent, ProjectionType},
+    camera::{CameraInputEvent, CameraProjection, ProjectionType},
+    config::WireframeColors,
+    elements::HighlightMesh,
     MiratopePlugins,
 };
 
-use crate::mesh::Renderable;
+use crate::mesh::{EdgeColoring, Renderable};
 
 mod mesh;
 mod no_cull_pipeline;
+mod png;
+mod svg;
 mod ui;
 
 /// The link to the [Polytope Wiki](https://polytope.miraheze.org/wiki/).
 pub const WIKI_LINK: &str = "https://polytope.miraheze.org/wiki/";
 
-/// The floating-point type for the entire application. Can be either `f32` or
-/// `f64`, and it should compile the same.
-type Float = f64;
+/// The floating-point type for the entire application. This is `f64`, or
+/// `f32` when the `f32` feature is enabled.
+type Float = miratope_core::float::Real;
 
 /// A [`Concrete`](miratope_core::conc::Concrete) polytope with the floating
 /// type for the application.
@@ -42,19 +46,19 @@ type Concrete = miratope_core::conc::Concrete;
 
 /// A [`Point`](miratope_core::geometry::Point) with the floating type
 /// for the application.
-type Point = miratope_core::geometry::Point<f64>;
+type Point = miratope_core::geometry::Point<Float>;
 
 /// A [`Vector`](miratope_core::geometry::Vector) with the floating
 /// type for the application.
-type Vector = miratope_core::geometry::Vector<f64>;
+type Vector = miratope_core::geometry::Vector<Float>;
 
 /// A [`Hypersphere`](miratope_core::geometry::Hypersphere) with the
 /// floating type for the application.
-type Hypersphere = miratope_core::geometry::Hypersphere<f64>;
+type Hypersphere = miratope_core::geometry::Hypersphere<Float>;
 
 /// A [`Hyperplane`](miratope_core::geometry::Hyperplane) with the
 /// floating type for the application.
-type Hyperplane = miratope_core::geometry::Hyperplane<f64>;
+type Hyperplane = miratope_core::geometry::Hyperplane<Float>;
 
 /// The default epsilon value throughout the application.
 const EPS: Float = <Float as miratope_core::float::Float>::EPS;
@@ -82,6 +86,8 @@ fn setup(
     mut materials: ResMut<'_, Assets<StandardMaterial>>,
     mut shaders: ResMut<'_, Assets<Shader>>,
     mut pipelines: ResMut<'_, Assets<PipelineDescriptor>>,
+    wireframe_colors: Res<'_, WireframeColors>,
+    edge_coloring: Res<'_, EdgeColoring>,
 ) {
     // Default polytope.
     let poly = Concrete::from_off(include_str!("default.off")).unwrap();
@@ -89,17 +95,26 @@ fn setup(
     // Disables backface culling.
     pipelines.set_untracked(
         no_cull_pipeline::NO_CULL_PIPELINE_HANDLE,
-        no_cull_pipeline::build_no_cull_pipeline(&mut shaders),
+        no_cull_pipeline::build_pipeline(&mut shaders, None),
     );
 
-    // Selected object (unused as of yet).
+    // Backface culling, for the polyhedra that can be oriented.
+    pipelines.set_untracked(
+        no_cull_pipeline::CULL_PIPELINE_HANDLE,
+        no_cull_pipeline::build_pipeline(&mut shaders, Some(Face::Back)),
+    );
+
+    // Selected object.
     materials.set_untracked(
         WIREFRAME_SELECTED_MATERIAL,
-        Color::rgb_u8(126, 192, 255).into(),
+        WireframeColors::color(wireframe_colors.selected).into(),
     );
 
     // Wireframe material.
-    let wf_material = materials.set(WIREFRAME_UNSELECTED_MATERIAL, Color::rgb_u8(150, 150, 150).into());
+    let wf_material = materials.set(
+        WIREFRAME_UNSELECTED_MATERIAL,
+        WireframeColors::color(wireframe_colors.unselected).into(),
+    );
 
     // Mesh material.
     let mesh_material = materials.add(StandardMaterial {
@@ -124,10 +139,21 @@ fn setup(
         // Wireframe
         .with_children(|cb| {
             cb.spawn().insert_bundle(PbrNoBackfaceBundle {
-                mesh: meshes.add(poly.wireframe(ProjectionType::Perspective)),
+                mesh: meshes.add(poly.wireframe(ProjectionType::Perspective, &edge_coloring)),
                 material: wf_material,
                 ..Default::default()
             });
+
+            // Highlighted element
+            cb.spawn()
+                .insert_bundle(PbrNoBackfaceBundle {
+                    mesh: meshes.add(
+                        poly.wireframe_with_edges(None, ProjectionType::Perspective),
+                    ),
+                    material: WIREFRAME_SELECTED_MATERIAL.typed(),
+                    ..Default::default()
+                })
+                .insert(HighlightMesh);
         })
         // Polytope
         .insert(poly);
@@ -140,11 +166,7 @@ fn setup(
             // Camera
             cb.spawn_bundle(PerspectiveCameraBundle {
                 transform: cam,
-                perspective_projection: PerspectiveProjection {
-                    near: 0.01,
-                    far: 500.,
-                    ..Default::default()
-                },
+                perspective_projection: CameraProjection::perspective(),
                 ..Default::default()
             });
             // Light source
@@ -1,81 +1,63 @@
 //! Contains the methods that take a polytope and turn it into a mesh.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, iter};
 
 use crate::ui::camera::ProjectionType;
 use crate::{Concrete, Float, Point, EPS};
 
 use bevy::{
     prelude::Mesh,
-    render::{mesh::Indices, pipeline::PrimitiveTopology},
+    render::{
+        mesh::{Indices, VertexAttributeValues},
+        pipeline::PrimitiveTopology,
+        texture::{Extent3d, Texture, TextureDimension, TextureFormat},
+    },
 };
 use lyon::{math::point, path::Path, tessellation::*};
 use miratope_core::conc::cycle::CycleList;
 use miratope_core::{
     abs::{ElementList, Ranked},
-    conc::ConcretePolytope,
+    conc::{orientation::newell_normal, ConcretePolytope},
     geometry::{Subspace, Vector},
 };
 
+use serde::{Deserialize, Serialize};
 use vec_like::*;
 
-/// Attempts to turn the cycle into a 2D path, which can then be given to
-/// the tessellator. Uses the specified vertex list to grab the coordinates
-/// of the vertices on the path.
+/// Attempts to turn the cycles of a face into a 2D path, which can then be
+/// given to the tessellator. Uses the specified vertex list to grab the
+/// coordinates of the vertices on the path, which are flattened into the plane
+/// of the face.
 ///
-/// If the cycle isn't 2D, we return `None`.
+/// If the face has no cycles, isn't planar, or has no area, we return `None`.
 pub fn path(cycles: &CycleList, vertices: &[Point]) -> Option<Path> {
-    let dim = vertices[0].len();
-    let mut builder = Path::builder();
+    if cycles.is_empty() {
+        return None;
+    }
+
+    // We don't bother with any polygons that aren't in 2D space.
+    let s = Subspace::from_points_with(
+        cycles
+            .iter()
+            .flat_map(|cycle| cycle.iter().map(|&idx| &vertices[idx])),
+        2,
+    )
+    .filter(|s| s.rank() == 2)?;
+
+    // Converts a point in the polytope to a point in the path via orthogonal
+    // projection onto the plane of the face.
+    let path_point = |v: &Point| {
+        let p = s.flatten(v);
+        let coord = |i: usize| p.get(i).copied().unwrap_or_default() as f32;
+        point(coord(0), coord(1))
+    };
 
+    let mut builder = Path::builder();
     for (idx, cycle) in cycles.iter().enumerate() {
         let mut cycle_iter = cycle.iter().map(|&idx| &vertices[idx]);
 
-        // We don't bother with any polygons that aren't in 2D space.
-        let s = Subspace::from_points_with(cycle_iter.clone(), 2)?;
-
-        // We find the two axis directions most convenient for projecting down.
-        // Convenience is measured as the length of an axis vector projected
-        // down onto the plane our cycle lies in.
-
-        // The index of the axis vector that gives the largest length when
-        // projected, and that such length.
-        let mut idx0 = 0;
-        let mut len0 = 0.0;
-
-        // The index of the axis vector that gives the second largest length
-        // when projected, and that such length.
-        let mut idx1 = 0;
-        let mut len1 = 0.0;
-
-        // We compute idx0 and idx1 real quick.
-        let mut e = Point::zeros(dim);
-        for i in 0..dim {
-            e[i] = 1.0;
-
-            let len = s.project(&e).norm();
-            // This is the largest length we've found so far.
-            if len > len0 {
-                len1 = len0;
-                idx1 = idx0;
-                len0 = len;
-                idx0 = i;
-            }
-            // This is the second largest length we've found so far.
-            else if len > len1 {
-                len1 = len;
-                idx1 = i;
-            }
-
-            e[i] = 0.0;
-        }
-
-        // Converts a point in the polytope to a point in the path via
-        // orthogonal projection at our convenient axes.
-        let path_point = |v: &Point| point(v[idx0] as f32, v[idx1] as f32);
-
         // We build a path from the polygon.
-        let v = cycle_iter.next().unwrap();
+        let v = cycle_iter.next()?;
         builder.begin(path_point(v));
 
         for v in cycle_iter {
@@ -115,72 +97,148 @@ impl Triangulation {
 
         let concrete_vertex_len = polytope.vertices.len() as u32;
 
+        // If the faces can be oriented consistently, we follow their
+        // orientation, so that in 3D all triangles are wound the same way.
+        let mut oriented_faces = polytope.oriented_faces().map(Vec::into_iter);
+        let is_3d = polytope.dim() == Some(3);
+
         // We render each face separately.
         for face in faces {
-            // We tesselate this path.
-            let cycles = CycleList::from_edges(face.subs.iter().map(|&i| &edges[i].subs));
-            if let Some(path) = path(&cycles, &polytope.vertices) {
+            let oriented_face = oriented_faces.as_mut().and_then(Iterator::next);
+            let face_normal = oriented_face
+                .as_ref()
+                .filter(|_| is_3d)
+                .map(|cycle| newell_normal(cycle, &polytope.vertices));
+
+            let cycles = match oriented_face {
+                Some(cycle) => iter::once(cycle).collect(),
+                None => CycleList::from_edges(face.subs.iter().map(|&i| &edges[i].subs)),
+            };
+
+            // We tesselate this path in the plane of the face, using the
+            // even-odd rule so that star polygons are hollow in the middle.
+            // This also handles non-convex and self-intersecting faces, adding
+            // vertices where their edges cross.
+            let tessellated = path(&cycles, &polytope.vertices).and_then(|path| {
                 let mut geometry: VertexBuffers<_, u32> = VertexBuffers::new();
 
                 // Configures all of the options of the tessellator.
-                FillTessellator::new()
-                    .tessellate_with_ids(
-                        path.id_iter(),
-                        &path,
-                        None,
-                        &FillOptions::with_fill_rule(Default::default(), FillRule::NonZero)
-                            .with_tolerance(EPS as f32),
-                        &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex<'_>| {
-                            vertex.sources().next().unwrap()
-                        }),
-                    )
-                    .unwrap();
-
-                // Maps EndpointIds to the indices in the original vertex list.
-                let mut id_to_idx = Vec::new();
-                for cycle in cycles {
-                    for idx in cycle {
-                        id_to_idx.push(idx);
+                let result = FillTessellator::new().tessellate_with_ids(
+                    path.id_iter(),
+                    &path,
+                    None,
+                    &FillOptions::with_fill_rule(Default::default(), FillRule::EvenOdd)
+                        .with_tolerance(EPS as f32),
+                    &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex<'_>| {
+                        vertex.sources().next().unwrap()
+                    }),
+                );
+
+                result.ok().map(|_| geometry)
+            });
+
+            let geometry = match tessellated {
+                Some(geometry) => geometry,
+
+                // If the face isn't planar, or the tessellation fails, we
+                // triangulate the face as a fan around its centroid.
+                None => {
+                    let vertices: Vec<_> = cycles.iter().flatten().collect();
+
+                    // Faces with no area, like those whose vertices are all
+                    // collinear, are only shown through their edges.
+                    if vertices.is_empty()
+                        || Subspace::from_points(vertices.iter().map(|&&v| &polytope.vertices[v]))
+                            .rank()
+                            < 2
+                    {
+                        continue;
                     }
-                }
 
-                // We map the output vertices to the original ones, and add any
-                // extra vertices that may be needed.
-                let mut vertex_hash = HashMap::new();
+                    let centroid = vertices
+                        .iter()
+                        .map(|&&v| &polytope.vertices[v])
+                        .sum::<Point>()
+                        / vertices.len() as Float;
 
-                for (new_id, vertex_source) in geometry.vertices.into_iter().enumerate() {
-                    let new_id = new_id as u32;
+                    let centroid_idx = concrete_vertex_len + extra_vertices.len() as u32;
+                    extra_vertices.push(centroid);
 
-                    match vertex_source {
-                        // This is one of the concrete vertices of the polytope.
-                        VertexSource::Endpoint { id } => {
-                            vertex_hash.insert(new_id, id_to_idx[id.to_usize()] as u32);
+                    for cycle in &cycles {
+                        for (i, &v) in cycle.iter().enumerate() {
+                            let w = cycle[(i + 1) % cycle.len()];
+                            triangles.extend([centroid_idx, v as u32, w as u32]);
                         }
+                    }
+
+                    continue;
+                }
+            };
 
-                        // This is a new vertex that has been added to the tesselation.
-                        VertexSource::Edge { from, to, t } => {
-                            let from = &polytope.vertices[id_to_idx[from.to_usize()]];
-                            let to = &polytope.vertices[id_to_idx[to.to_usize()]];
+            // Maps EndpointIds to the indices in the original vertex list.
+            let mut id_to_idx = Vec::new();
+            for cycle in cycles {
+                for idx in cycle {
+                    id_to_idx.push(idx);
+                }
+            }
 
-                            let t = t as Float;
-                            let p = from * (1.0 - t) + to * t;
+            // We map the output vertices to the original ones, and add any
+            // extra vertices that may be needed.
+            let mut vertex_hash = HashMap::new();
 
-                            vertex_hash
-                                .insert(new_id, concrete_vertex_len + extra_vertices.len() as u32);
+            for (new_id, vertex_source) in geometry.vertices.into_iter().enumerate() {
+                let new_id = new_id as u32;
 
-                            extra_vertices.push(p);
-                        }
+                match vertex_source {
+                    // This is one of the concrete vertices of the polytope.
+                    VertexSource::Endpoint { id } => {
+                        vertex_hash.insert(new_id, id_to_idx[id.to_usize()] as u32);
+                    }
+
+                    // This is a new vertex that has been added to the tesselation.
+                    VertexSource::Edge { from, to, t } => {
+                        let from = &polytope.vertices[id_to_idx[from.to_usize()]];
+                        let to = &polytope.vertices[id_to_idx[to.to_usize()]];
+
+                        let t = t as Float;
+                        let p = from * (1.0 - t) + to * t;
+
+                        vertex_hash
+                            .insert(new_id, concrete_vertex_len + extra_vertices.len() as u32);
+
+                        extra_vertices.push(p);
                     }
                 }
+            }
 
-                // Add all of the new indices we've found onto the triangle vector.
-                for new_idx in geometry
-                    .indices
-                    .iter()
-                    .map(|idx| *vertex_hash.get(idx).unwrap())
-                {
-                    triangles.push(new_idx);
+            // Add all of the new indices we've found onto the triangle vector.
+            let face_triangles: Vec<_> = geometry
+                .indices
+                .iter()
+                .map(|idx| *vertex_hash.get(idx).unwrap())
+                .collect();
+
+            let vertex = |idx: u32| match idx.checked_sub(concrete_vertex_len) {
+                Some(extra_idx) => &extra_vertices[extra_idx as usize],
+                None => &polytope.vertices[idx as usize],
+            };
+
+            for triangle in face_triangles.chunks(3) {
+                let (a, mut b, mut c) = (triangle[0], triangle[1], triangle[2]);
+
+                // The tessellator doesn't know how the face is oriented, so we
+                // flip the triangles that are wound the wrong way.
+                if let Some(face_normal) = face_normal {
+                    let (p, q, r) = (vertex(a), vertex(b), vertex(c));
+                    let normal = (q - p).cross(&(r - p));
+                    let dot: Float = (0..3).map(|i| normal[i] * face_normal[i]).sum();
+                    if dot < 0.0 {
+                        std::mem::swap(&mut b, &mut c);
+                    }
                 }
+
+                triangles.extend([a, b, c]);
             }
         }
 
@@ -219,35 +277,332 @@ fn empty_mesh() -> Mesh {
     mesh
 }
 
+/// Returns a mesh showing the vertices of a polytope as points. Used for
+/// polytopes without edges.
+fn points_mesh(poly: &Concrete, projection_type: ProjectionType) -> Mesh {
+    let vertex_count = poly.vertex_count();
+    let vertices = vertex_coords(poly, poly.vertices.iter(), projection_type);
+
+    let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; vertex_count]);
+    mesh.set_indices(Some(Indices::U16((0..vertex_count as u16).collect())));
+
+    mesh
+}
+
+/// Returns a wireframe with the edges of a polytope colored by length. Each
+/// edge gets its own pair of vertices, whose first texture coordinate is its
+/// position in the palette.
+fn length_wireframe(
+    poly: &Concrete,
+    projection_type: ProjectionType,
+    coloring: &EdgeColoring,
+) -> Mesh {
+    let edges = &poly[2];
+    let lengths: Vec<_> = edges
+        .iter()
+        .map(|edge| (&poly.vertices[edge.subs[0]] - &poly.vertices[edge.subs[1]]).norm())
+        .collect();
+    let positions = coloring.positions(&lengths);
+
+    let coords = vertex_coords(poly, poly.vertices.iter(), projection_type);
+    let mut vertices = Vec::with_capacity(2 * edges.len());
+    let mut uvs = Vec::with_capacity(2 * edges.len());
+    for (edge, &t) in edges.iter().zip(&positions) {
+        for &v in &edge.subs {
+            vertices.push(coords[v]);
+            uvs.push([t, 0.5]);
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
+    mesh.set_indices(Some(Indices::U32((0..vertices.len() as u32).collect())));
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+
+    mesh
+}
+
+/// Copies a buffer into another, reusing its allocation unless it has to grow,
+/// in which case we leave some headroom for later frames.
+fn copy_into<T: Copy>(target: &mut Vec<T>, source: &[T]) {
+    target.clear();
+    if target.capacity() < source.len() {
+        target.reserve(source.len() + source.len() / 4);
+    }
+    target.extend_from_slice(source);
+}
+
+/// Writes a newly built mesh into an existing one. The buffers of the old mesh
+/// are written into in place whenever possible, so that meshes rebuilt every
+/// frame, like those of a cross-section being dragged, don't reallocate them.
+pub fn update_mesh(target: &mut Mesh, source: Mesh) {
+    use VertexAttributeValues::{Float32x2, Float32x3};
+
+    let same_layout = target.primitive_topology() == source.primitive_topology()
+        && [
+            Mesh::ATTRIBUTE_POSITION,
+            Mesh::ATTRIBUTE_NORMAL,
+            Mesh::ATTRIBUTE_UV_0,
+        ]
+        .iter()
+        .all(
+            |&name| match (target.attribute_mut(name), source.attribute(name)) {
+                (Some(Float32x3(old)), Some(Float32x3(new))) => {
+                    copy_into(old, new);
+                    true
+                }
+                (Some(Float32x2(old)), Some(Float32x2(new))) => {
+                    copy_into(old, new);
+                    true
+                }
+                _ => false,
+            },
+        );
+
+    let indices_copied = same_layout
+        && match (target.indices_mut(), source.indices()) {
+            (Some(Indices::U16(old)), Some(Indices::U16(new))) => {
+                copy_into(old, new);
+                true
+            }
+            (Some(Indices::U32(old)), Some(Indices::U32(new))) => {
+                copy_into(old, new);
+                true
+            }
+            _ => false,
+        };
+
+    // Otherwise, we just replace the mesh.
+    if !indices_copied {
+        *target = source;
+    }
+}
+
 /// Gets the coordinates of the vertices, after projecting down into 3D.
-fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
+pub fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
     poly: &Concrete,
     vertices: I,
     projection_type: ProjectionType,
 ) -> Vec<[f32; 3]> {
     let dim = poly.dim_or();
 
+    // If the polytope is at most 3D, we just embed it into 3D space.
+    if dim <= 3 {
+        return embed_coords(vertices);
+    }
+
+    match projection_type {
+        ProjectionType::Orthogonal | ProjectionType::Section => embed_coords(vertices),
+        ProjectionType::Perspective => perspective_coords(vertices, dim),
+        ProjectionType::Schlegel { facet, distance } => {
+            // By default, we project into the facet facing the last axis.
+            let facet = facet.or_else(|| {
+                let mut direction = Vector::zeros(dim);
+                direction[dim - 1] = 1.0;
+                poly.facet_towards(&direction)
+            });
+
+            // If the Schlegel diagram can't be built, we fall back to the
+            // perspective projection.
+            let vertices: Vec<_> = vertices.collect();
+            let projected =
+                facet.and_then(|f| poly.schlegel_project(f, distance, vertices.iter().copied()));
+
+            match projected {
+                Some(projected) => perspective_coords(projected.iter(), dim - 1),
+                None => perspective_coords(vertices.into_iter(), dim),
+            }
+        }
+    }
+}
+
+/// Embeds a set of points into 3D space, dropping every coordinate past the
+/// third.
+fn embed_coords<'a, I: Iterator<Item = &'a Point>>(vertices: I) -> Vec<[f32; 3]> {
     // Returns the ith coordinate of p, or 0 if it doesn't exist.
     let coord = |p: &Point, i: usize| p.get(i).copied().unwrap_or_default();
+    vertices
+        .map(|p| [0, 1, 2].map(|i| coord(p, i) as f32))
+        .collect()
+}
 
-    // If the polytope is at most 3D, we just embed it into 3D space.
-    if projection_type.is_orthogonal() || dim <= 3 {
-        vertices.map(|p| [0, 1, 2].map(|i| coord(p, i) as f32)).collect()
+/// Projects a set of points in a given dimension into 3D space, by projecting
+/// from a point along every coordinate axis past the third.
+fn perspective_coords<'a, I: Iterator<Item = &'a Point>>(vertices: I, dim: usize) -> Vec<[f32; 3]> {
+    if dim <= 3 {
+        return embed_coords(vertices);
+    }
+
+    let vertices: Vec<_> = vertices.collect();
+
+    // Distance from the projection planes.
+    let (min, max) = vertices
+        .iter()
+        .map(|p| p[3])
+        .fold((Float::INFINITY, Float::NEG_INFINITY), |(min, max), x| {
+            (min.min(x), max.max(x))
+        });
+    let dist = (min as f32 - 1.0).abs().max(max as f32 + 1.0).abs();
+
+    vertices
+        .into_iter()
+        .map(|p| {
+            // We scale the first three coordinates accordingly.
+            let factor: f32 = p.iter().skip(3).map(|&x| x as f32 + dist).product();
+            [0, 1, 2].map(|i| p[i] as f32 / factor)
+        })
+        .collect()
+}
+
+/// A gradient that the edges of a wireframe can be colored with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Palette {
+    /// The perceptually uniform viridis colormap, from purple to yellow.
+    Viridis,
+
+    /// The hues from blue to red.
+    Rainbow,
+
+    /// Shades of gray, from dark to light.
+    Grayscale,
+}
+
+impl Palette {
+    /// All of the palettes, in the order they're shown on the UI.
+    pub const ALL: [Self; 3] = [Self::Viridis, Self::Rainbow, Self::Grayscale];
+
+    /// The number of pixels in the texture of a palette.
+    const TEXTURE_WIDTH: usize = 256;
+
+    /// Returns the name of the palette, as shown on the UI.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Viridis => "Viridis",
+            Self::Rainbow => "Rainbow",
+            Self::Grayscale => "Grayscale",
+        }
     }
-    // Else, we project it down.
-    else {
-        // Distance from the projection planes.
-        let mut direction = Vector::zeros(dim);
-        direction[3] = 1.0;
-
-        let (min, max) = poly.minmax(direction).unwrap();
-        let dist = (min as f32 - 1.0).abs().max(max as f32 + 1.0).abs();
-
-        vertices
-            .map(|p| {
-                // We scale the first three coordinates accordingly.
-                let factor: f32 = p.iter().skip(3).map(|&x| x as f32 + dist).product();
-                [0, 1, 2].map(|i| coord(p, i) as f32 / factor)
+
+    /// Returns the sRGB color at a given position of the palette, between 0
+    /// and 1.
+    pub fn color(self, t: f32) -> [u8; 3] {
+        let stops: &[[f32; 3]] = match self {
+            Self::Viridis => &[
+                [68.0, 1.0, 84.0],
+                [59.0, 82.0, 139.0],
+                [33.0, 145.0, 140.0],
+                [94.0, 201.0, 98.0],
+                [253.0, 231.0, 37.0],
+            ],
+            Self::Rainbow => &[
+                [0.0, 0.0, 255.0],
+                [0.0, 255.0, 255.0],
+                [0.0, 255.0, 0.0],
+                [255.0, 255.0, 0.0],
+                [255.0, 0.0, 0.0],
+            ],
+            Self::Grayscale => &[[64.0; 3], [255.0; 3]],
+        };
+
+        // Linearly interpolates between the two nearest stops.
+        let x = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+        let idx = (x as usize).min(stops.len() - 2);
+        let frac = x - idx as f32;
+        let (a, b) = (stops[idx], stops[idx + 1]);
+        [0, 1, 2].map(|i| (a[i] + (b[i] - a[i]) * frac).round() as u8)
+    }
+
+    /// Builds a texture one pixel tall with the palette, which the wireframe
+    /// material samples at the positions stored as texture coordinates.
+    pub fn texture(self) -> Texture {
+        let data = (0..Self::TEXTURE_WIDTH)
+            .flat_map(|x| {
+                let [r, g, b] = self.color(x as f32 / (Self::TEXTURE_WIDTH - 1) as f32);
+                [r, g, b, 255]
+            })
+            .collect();
+
+        Texture::new(
+            Extent3d::new(Self::TEXTURE_WIDTH as u32, 1, 1),
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+}
+
+/// How the lengths of edges are mapped into a palette.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum LengthMapping {
+    /// The shortest edge takes the first color, and the longest takes the
+    /// last one.
+    Range,
+
+    /// Edges with the median length take the middle color, and those that are
+    /// off from it by the given fraction of it or more take the ends of the
+    /// palette.
+    Deviation(f32),
+}
+
+impl LengthMapping {
+    /// The default fraction of the median length that takes an edge to either
+    /// end of the palette.
+    pub const DEFAULT_SPREAD: f32 = 0.01;
+}
+
+/// How the edges of a wireframe are colored.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct EdgeColoring {
+    /// Whether the edges are colored by their length. Otherwise, the whole
+    /// wireframe takes the color of its material.
+    pub by_length: bool,
+
+    /// How the lengths of the edges are mapped into the palette.
+    pub mapping: LengthMapping,
+
+    /// The palette the edges are colored with.
+    pub palette: Palette,
+}
+
+impl Default for EdgeColoring {
+    fn default() -> Self {
+        Self {
+            by_length: false,
+            mapping: LengthMapping::Deviation(LengthMapping::DEFAULT_SPREAD),
+            palette: Palette::Viridis,
+        }
+    }
+}
+
+impl EdgeColoring {
+    /// Returns the position in the palette of each of the given lengths,
+    /// between 0 and 1.
+    pub fn positions(&self, lengths: &[Float]) -> Vec<f32> {
+        let mut sorted = lengths.to_vec();
+        sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let (min, max) = match (sorted.first(), sorted.last()) {
+            (Some(&min), Some(&max)) => (min, max),
+            _ => return Vec::new(),
+        };
+        let median = sorted[sorted.len() / 2];
+
+        lengths
+            .iter()
+            .map(|&len| {
+                let t = match self.mapping {
+                    LengthMapping::Range if max - min > EPS => (len - min) / (max - min),
+                    LengthMapping::Deviation(spread) if median > EPS && spread > 0.0 => {
+                        0.5 + (len / median - 1.0) / (2.0 * spread as Float)
+                    }
+                    _ => 0.5,
+                };
+
+                (t as f32).clamp(0.0, 1.0)
             })
             .collect()
     }
@@ -255,22 +610,17 @@ fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
 
 /// A trait for a polytope for which we can build a mesh.
 pub trait Renderable: ConcretePolytope {
-    /// Builds the mesh of a polytope.
-    fn mesh(&self, projection_type: ProjectionType) -> Mesh {
-        // If there's no vertices, returns an empty mesh.
-        if self.vertex_count() == 0 {
-            return empty_mesh();
-        }
-
+    /// Triangulates the faces of a polytope, and projects the vertices of both
+    /// the polytope and the triangulation into 3D. Returns these vertices,
+    /// together with the indices of the triangles.
+    fn triangles(&self, projection_type: ProjectionType) -> (Vec<[f32; 3]>, Vec<u32>) {
         let mut poly = self.clone();
-        
+
         if poly.rank() == 3 {
             poly = poly.ditope();
             poly.untangle_faces();
         }
 
-        // Triangulates the polytope's faces, projects the vertices of both the
-        // polytope and the triangulation.
         let triangulation = Triangulation::new(poly.con());
         let vertices = vertex_coords(
             self.con(),
@@ -280,18 +630,65 @@ pub trait Renderable: ConcretePolytope {
             projection_type,
         );
 
+        (vertices, triangulation.triangles)
+    }
+
+    /// Builds the mesh of a polytope.
+    fn mesh(&self, projection_type: ProjectionType) -> Mesh {
+        // If there's no vertices, returns an empty mesh.
+        if self.vertex_count() == 0 {
+            return empty_mesh();
+        }
+
+        let (vertices, triangles) = self.triangles(projection_type);
+
         // Builds the actual mesh.
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
         mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 1.0]; vertices.len()]);
         mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
         mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
-        mesh.set_indices(Some(Indices::U32(triangulation.triangles)));
+        mesh.set_indices(Some(Indices::U32(triangles)));
 
         mesh
     }
 
-    /// Builds the wireframe of a polytope.
-    fn wireframe(&self, projection_type: ProjectionType) -> Mesh {
+    /// Builds the wireframe of a polytope. Points, which have no edges, are
+    /// shown as such. The edges may be colored by length, as configured.
+    fn wireframe(&self, projection_type: ProjectionType, coloring: &EdgeColoring) -> Mesh {
+        if self.rank() == 1 {
+            return points_mesh(self.con(), projection_type);
+        }
+
+        if coloring.by_length && self.vertex_count() != 0 {
+            return length_wireframe(self.con(), projection_type, coloring);
+        }
+
+        self.wireframe_with_edges(0..self.edge_count(), projection_type)
+    }
+
+    /// Builds a wireframe with only the edges of a given element, so that it
+    /// can be highlighted. A vertex is shown through the edges that contain
+    /// it. Returns an empty mesh if the element doesn't exist.
+    fn element_wireframe(&self, rank: usize, idx: usize, projection_type: ProjectionType) -> Mesh {
+        let edges = if rank == 1 {
+            self.get_element(1, idx)
+                .map(|vertex| vertex.sups.iter().copied().collect())
+        } else {
+            self.abs().element_edges(rank, idx)
+        };
+
+        match edges {
+            Some(edges) => self.wireframe_with_edges(edges, projection_type),
+            None => empty_mesh(),
+        }
+    }
+
+    /// Builds a wireframe with the edges of a polytope with the given indices.
+    fn wireframe_with_edges<I: IntoIterator<Item = usize>>(
+        &self,
+        edges: I,
+        projection_type: ProjectionType,
+    ) -> Mesh {
         let vertex_count = self.vertex_count();
 
         // If there's no vertices, returns an empty mesh.
@@ -299,15 +696,14 @@ pub trait Renderable: ConcretePolytope {
             return empty_mesh();
         }
 
-        let edge_count = self.edge_count();
-
         // We add a single vertex so that Miratope doesn't crash.
         let vertices = vertex_coords(self.con(), self.vertices().iter(), projection_type);
-        let mut indices = Vec::with_capacity(edge_count * 2);
+        let mut indices = Vec::new();
 
         // Adds the edges to the wireframe.
-        if let Some(edges) = self.get_element_list(2) {
-            for edge in edges {
+        if let Some(edge_list) = self.get_element_list(2) {
+            for idx in edges {
+                let edge = &edge_list[idx];
                 debug_assert_eq!(
                     edge.subs.len(),
                     2,
@@ -332,3 +728,145 @@ pub trait Renderable: ConcretePolytope {
 }
 
 impl<U: ConcretePolytope> Renderable for U {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::Polytope;
+
+    /// Checks that a mesh has some vertices, and that all of their coordinates
+    /// are finite.
+    fn assert_finite(mesh: &Mesh) {
+        assert!(mesh.count_vertices() > 0);
+
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION).unwrap();
+        for bytes in positions.get_bytes().chunks(4) {
+            let x = f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            assert!(x.is_finite());
+        }
+    }
+
+    /// Builds the meshes and wireframes of polytopes of every rank, including
+    /// degenerate ones.
+    #[test]
+    fn meshes() {
+        let coloring = EdgeColoring {
+            by_length: true,
+            ..Default::default()
+        };
+
+        let mut dihedron = Concrete::polygon(5);
+        dihedron.ditope_mut();
+
+        let mut hosohedron = Concrete::polygon(5);
+        hosohedron.hosotope_mut();
+
+        // A square squashed into a segment.
+        let mut flat = Concrete::polygon(4);
+        for v in &mut flat.vertices {
+            v[1] = 0.0;
+        }
+
+        for poly in [
+            Concrete::point(),
+            Concrete::dyad(),
+            Concrete::polygon(5),
+            Concrete::star_polygon(5, 2),
+            flat,
+            Concrete::cube(),
+            dihedron,
+            hosohedron,
+            Concrete::hypercube(5),
+        ] {
+            for projection_type in [ProjectionType::Orthogonal, ProjectionType::Perspective] {
+                assert_finite(&poly.mesh(projection_type));
+                assert_finite(&poly.wireframe(projection_type, &Default::default()));
+                assert_finite(&poly.wireframe(projection_type, &coloring));
+            }
+        }
+    }
+
+    /// Checks that the edges of a uniform polytope all take the middle color,
+    /// and that those of a rectangle are told apart.
+    #[test]
+    fn edge_lengths() {
+        let lengths = |poly: &Concrete| -> Vec<Float> {
+            poly[2]
+                .iter()
+                .map(|edge| (&poly.vertices[edge.subs[0]] - &poly.vertices[edge.subs[1]]).norm())
+                .collect()
+        };
+
+        let coloring = EdgeColoring::default();
+        let cube = Concrete::cube();
+        assert!(coloring
+            .positions(&lengths(&cube))
+            .iter()
+            .all(|&t| (t - 0.5).abs() < 1e-4));
+
+        let mut rectangle = Concrete::hypercube(3);
+        for v in &mut rectangle.vertices {
+            v[0] *= 2.0;
+        }
+
+        for mapping in [LengthMapping::Range, LengthMapping::Deviation(0.01)] {
+            let coloring = EdgeColoring {
+                mapping,
+                ..coloring
+            };
+            let mut positions = coloring.positions(&lengths(&rectangle));
+            positions.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(positions[0], 0.0, "{:?}", mapping);
+            assert!(positions[3] >= 0.5, "{:?}", mapping);
+        }
+
+        assert_eq!(Palette::Rainbow.color(0.0), [0, 0, 255]);
+        assert_eq!(Palette::Rainbow.color(1.0), [255, 0, 0]);
+    }
+
+    /// Checks that a pentagram is tessellated into its five tips, leaving its
+    /// center hollow as per the even-odd rule, and that every vertex of the
+    /// tessellation lies on one of its edges.
+    #[test]
+    fn pentagram() {
+        use miratope_core::geometry::Segment;
+
+        let pentagram = Concrete::star_polygon(5, 2);
+        let triangulation = Triangulation::new(&pentagram);
+        let triangle_count = triangulation.triangles.len() / 3;
+        assert!(
+            (5..=10).contains(&triangle_count),
+            "{} triangles",
+            triangle_count
+        );
+
+        let points: Vec<_> = pentagram
+            .vertices
+            .iter()
+            .chain(&triangulation.extra_vertices)
+            .collect();
+
+        for &idx in &triangulation.triangles {
+            let p = points[idx as usize];
+            assert!(pentagram[2].iter().any(|edge| {
+                Segment(
+                    &pentagram.vertices[edge.subs[0]],
+                    &pentagram.vertices[edge.subs[1]],
+                )
+                .contains(p)
+            }));
+        }
+
+        // The center is outside of every triangle.
+        let side = |p: &Point, q: &Point| p[0] * q[1] - p[1] * q[0];
+        for triangle in triangulation.triangles.chunks(3) {
+            let (a, b, c) = (
+                points[triangle[0] as usize],
+                points[triangle[1] as usize],
+                points[triangle[2] as usize],
+            );
+            let sides = [side(a, b), side(b, c), side(c, a)];
+            assert!(!sides.iter().all(|&s| s > 0.0) && !sides.iter().all(|&s| s < 0.0));
+        }
+    }
+}
@@ -281,6 +281,12 @@ void main() {
     // https://github.com/glslify/glsl-face-normal/blob/master/index.glsl
     vec3 fdx = dFdx(v_WorldPosition);
     vec3 fdy = dFdy(v_WorldPosition);
-    vec4 output_color = vec4(abs(dot(normalize(cross(fdx, fdy)),normalize(PointLights[0].pos.xyz))) * base_color.xyz, 1.0);
+    vec4 color = base_color;
+#ifdef STANDARDMATERIAL_BASE_COLOR_TEXTURE
+    color *= texture(sampler2D(StandardMaterial_base_color_texture,
+                               StandardMaterial_base_color_texture_sampler),
+                     v_Uv);
+#endif
+    vec4 output_color = vec4(abs(dot(normalize(cross(fdx, fdy)),normalize(PointLights[0].pos.xyz))) * color.xyz, 1.0);
     o_Target = output_color;
 }
\ No newline at end of file
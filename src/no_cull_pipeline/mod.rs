@@ -1,6 +1,7 @@
 //! Configures a render pipeline without
 //! [backface culling](https://en.wikipedia.org/wiki/Back-face_culling), needed
-//! so that most of the non-convex polytopes work properly.
+//! so that most of the non-convex polytopes work properly, and one with it,
+//! which can be used for polyhedra with consistently oriented faces.
 
 use bevy::{
     asset::{Assets, Handle, HandleUntyped},
@@ -19,11 +20,15 @@ use bevy::{
 pub const NO_CULL_PIPELINE_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 0x7CAE7047DEE79C84);
 
-pub fn build_no_cull_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+pub const CULL_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 0x4F1E5B2AC9D0873E);
+
+/// Builds the pipeline used to draw polytopes, culling the given faces.
+pub fn build_pipeline(shaders: &mut Assets<Shader>, cull_mode: Option<Face>) -> PipelineDescriptor {
     PipelineDescriptor {
         primitive: PrimitiveState {
             front_face: FrontFace::Ccw,
-            cull_mode: None,
+            cull_mode,
             ..Default::default()
         },
         depth_stencil: Some(DepthStencilState {
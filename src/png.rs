@@ -0,0 +1,307 @@
+//! Renders a polytope, as seen from the camera, into a PNG image. The renderer
+//! has no way to read back the frames it draws, so we rasterize the faces and
+//! the visible edges ourselves.
+
+use crate::svg::{to_image, triangles, visible_edges, SvgOptions};
+use crate::Concrete;
+
+use bevy::{
+    math::{Vec2, Vec3},
+    prelude::Transform,
+};
+
+/// The options for exporting a polytope into PNG. The view itself is described
+/// by the same [`SvgOptions`] as a wireframe export.
+#[derive(Clone, Copy)]
+pub struct PngOptions {
+    /// The number of pixels in the image for each pixel of the view, along
+    /// either axis.
+    pub resolution: u32,
+
+    /// The color of the background.
+    pub background: [u8; 3],
+
+    /// The color of the faces, before shading.
+    pub face_color: [u8; 3],
+
+    /// The color of the edges.
+    pub edge_color: [u8; 3],
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self {
+            resolution: 2,
+            background: [0, 0, 0],
+            face_color: [255, 255, 255],
+            edge_color: [150, 150, 150],
+        }
+    }
+}
+
+/// An RGB image together with the depth of each of its pixels.
+struct Canvas {
+    /// The width of the image, in pixels.
+    width: usize,
+
+    /// The height of the image, in pixels.
+    height: usize,
+
+    /// The colors of the pixels, row by row.
+    pixels: Vec<[u8; 3]>,
+
+    /// The depths of the pixels, row by row.
+    depths: Vec<f32>,
+}
+
+impl Canvas {
+    /// Initializes a canvas filled with a color.
+    fn new(width: usize, height: usize, background: [u8; 3]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+            depths: vec![f32::INFINITY; width * height],
+        }
+    }
+
+    /// Returns the range of pixels along an axis that the range between two
+    /// coordinates covers.
+    fn range(min: f32, max: f32, len: usize) -> std::ops::Range<usize> {
+        let min = min.floor().max(0.0) as usize;
+        let max = (max.ceil().max(0.0) as usize).min(len);
+        min..max.max(min)
+    }
+
+    /// Fills a triangle with a color, wherever it's in front of whatever was
+    /// drawn before. The vertices are given by their coordinates in pixels,
+    /// together with their depths.
+    fn fill_triangle(&mut self, [a, b, c]: [Vec3; 3], color: [u8; 3]) {
+        let cross =
+            |p: Vec3, q: Vec3, r: Vec2| (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x);
+        let area = cross(a, b, c.truncate());
+        if area.abs() < f32::EPSILON {
+            return;
+        }
+
+        let xs = Self::range(a.x.min(b.x).min(c.x), a.x.max(b.x).max(c.x), self.width);
+        let ys = Self::range(a.y.min(b.y).min(c.y), a.y.max(b.y).max(c.y), self.height);
+
+        for y in ys {
+            for x in xs.clone() {
+                // The barycentric coordinates of the center of the pixel.
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let (u, v, w) = (
+                    cross(b, c, p) / area,
+                    cross(c, a, p) / area,
+                    cross(a, b, p) / area,
+                );
+                if u < 0.0 || v < 0.0 || w < 0.0 {
+                    continue;
+                }
+
+                let idx = y * self.width + x;
+                let depth = u * a.z + v * b.z + w * c.z;
+                if depth < self.depths[idx] {
+                    self.depths[idx] = depth;
+                    self.pixels[idx] = color;
+                }
+            }
+        }
+    }
+
+    /// Draws a line of a given width on top of everything else.
+    fn draw_line(&mut self, p: Vec2, q: Vec2, width: f32, color: [u8; 3]) {
+        let r = width / 2.0;
+        let xs = Self::range(p.x.min(q.x) - r, p.x.max(q.x) + r, self.width);
+        let ys = Self::range(p.y.min(q.y) - r, p.y.max(q.y) + r, self.height);
+        let dir = q - p;
+        let len_sq = dir.length_squared();
+
+        for y in ys {
+            for x in xs.clone() {
+                // The distance from the center of the pixel to the line.
+                let c = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let t = if len_sq > 0.0 {
+                    ((c - p).dot(dir) / len_sq).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+
+                if (p + dir * t - c).length() <= r {
+                    self.pixels[y * self.width + x] = color;
+                }
+            }
+        }
+    }
+}
+
+/// Renders the faces and edges of a polytope, as seen from a camera with a
+/// given transform, into the pixels of an image. The faces are shaded by how
+/// directly they face the camera.
+fn render(poly: &Concrete, camera: &Transform, view: &SvgOptions, opts: &PngOptions) -> Canvas {
+    let scale = opts.resolution.max(1);
+    let view = SvgOptions {
+        width: view.width * scale,
+        height: view.height * scale,
+        stroke_width: view.stroke_width * scale as f32,
+        hidden_lines: true,
+        ..*view
+    };
+    let mut canvas = Canvas::new(view.width as usize, view.height as usize, opts.background);
+
+    if poly.vertices.is_empty() {
+        return canvas;
+    }
+
+    let to_image = to_image(camera, &view);
+    for triangle in triangles(poly, view.projection_type) {
+        let [a, b, c] = triangle;
+        let centroid = (a + b + c) / 3.0;
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        let (dir, _) = view.projection.towards_camera(camera, centroid);
+        let light = 0.35 + 0.65 * normal.dot(dir.normalize_or_zero()).abs();
+        let color = opts.face_color.map(|x| (x as f32 * light).round() as u8);
+
+        if let (Some(a), Some(b), Some(c)) = (to_image(a), to_image(b), to_image(c)) {
+            canvas.fill_triangle([a, b, c], color);
+        }
+    }
+
+    for [p, q] in visible_edges(poly, camera, &view) {
+        canvas.draw_line(p, q, view.stroke_width, opts.edge_color);
+    }
+
+    canvas
+}
+
+/// Computes the CRC-32 of some bytes, as used by PNG chunks.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+/// Computes the Adler-32 checksum of some bytes, as used by zlib streams.
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+/// Appends a chunk with some data to a PNG file.
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Encodes an RGB image into PNG. The image data is stored without
+/// compression, so that we don't need a DEFLATE encoder.
+fn encode_png(width: usize, height: usize, pixels: &[[u8; 3]]) -> Vec<u8> {
+    // Every row starts with the type of its filter, and we use none.
+    let mut raw = Vec::with_capacity((width * 3 + 1) * height);
+    for row in pixels.chunks(width.max(1)) {
+        raw.push(0);
+        for pixel in row {
+            raw.extend_from_slice(pixel);
+        }
+    }
+
+    // A zlib stream made of uncompressed blocks, the last of which is marked.
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        zlib.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    // 8 bits per channel, RGB, default compression and filters, and no
+    // interlacing.
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib);
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Renders the faces and edges of a polytope, as seen from a camera with a
+/// given transform, into a PNG file. The image is as large as the view times
+/// the resolution in the options, and so are the edges, which are as wide as
+/// the stroke width of the view.
+pub fn render_png(
+    poly: &Concrete,
+    camera: &Transform,
+    view: &SvgOptions,
+    opts: &PngOptions,
+) -> Vec<u8> {
+    let canvas = render(poly, camera, view, opts);
+    encode_png(canvas.width, canvas.height, &canvas.pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::Polytope;
+
+    /// Checks the checksums against known values.
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    /// Renders a cube seen from a generic direction, and checks that it's
+    /// drawn in the middle of the image and not in its corners.
+    #[test]
+    fn cube() {
+        let cube = Concrete::cube();
+        let camera = Transform::from_xyz(3.0, 4.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y);
+        let view = SvgOptions {
+            width: 50,
+            height: 40,
+            ..Default::default()
+        };
+        let opts = PngOptions::default();
+
+        let canvas = render(&cube, &camera, &view, &opts);
+        assert_eq!((canvas.width, canvas.height), (100, 80));
+        assert_ne!(canvas.pixels[40 * 100 + 50], opts.background);
+        assert_eq!(canvas.pixels[0], opts.background);
+        assert_eq!(canvas.pixels[80 * 100 - 1], opts.background);
+
+        let png = render_png(&cube, &camera, &view, &opts);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(b"IEND\xAE\x42\x60\x82"));
+        assert_eq!(&png[16..24], &[0, 0, 0, 100, 0, 0, 0, 80]);
+    }
+}
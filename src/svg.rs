@@ -0,0 +1,285 @@
+//! Exports the wireframe of a polytope, as seen from the camera, into SVG.
+
+use std::fmt::Write;
+
+use crate::mesh::{vertex_coords, Renderable};
+use crate::ui::camera::{CameraProjection, ProjectionType};
+use crate::Concrete;
+
+use bevy::{
+    math::{Mat4, Vec2, Vec3},
+    prelude::Transform,
+    render::camera::{CameraProjection as _, OrthographicProjection, PerspectiveProjection},
+};
+use miratope_core::{abs::Ranked, conc::ConcretePolytope};
+use vec_like::*;
+
+/// The number of pieces each edge is split into when testing which parts of
+/// it are hidden.
+const SAMPLES: usize = 32;
+
+/// The projection of the camera through which a polytope is seen.
+#[derive(Clone, Copy)]
+pub enum Projection {
+    /// The perspective projection of a camera with a given vertical field of
+    /// view, in radians.
+    Perspective { fov: f32 },
+
+    /// The orthographic projection of a camera with a given scale.
+    Orthographic { scale: f32 },
+}
+
+impl Projection {
+    /// Returns the matrix that takes a point from the camera's frame into clip
+    /// space, for an image of a given size. We reuse the projections of the
+    /// camera, so that the image is what's shown on the viewport.
+    pub fn matrix(&self, width: f32, height: f32) -> Mat4 {
+        match *self {
+            Self::Perspective { fov } => {
+                let mut projection = PerspectiveProjection {
+                    fov,
+                    ..CameraProjection::perspective()
+                };
+                projection.update(width, height);
+                projection.get_projection_matrix()
+            }
+            Self::Orthographic { scale } => {
+                let mut projection = OrthographicProjection {
+                    scale,
+                    ..CameraProjection::orthographic(1.0)
+                };
+                projection.update(width, height);
+                projection.get_projection_matrix()
+            }
+        }
+    }
+
+    /// Returns the direction from a point towards the camera with a given
+    /// transform, together with the greatest multiple of it along which
+    /// something can hide the point.
+    pub fn towards_camera(&self, camera: &Transform, p: Vec3) -> (Vec3, f32) {
+        match self {
+            Self::Perspective { .. } => (camera.translation - p, 1.0),
+            Self::Orthographic { .. } => (camera.local_z(), f32::INFINITY),
+        }
+    }
+}
+
+/// The options for exporting a wireframe into SVG.
+#[derive(Clone, Copy)]
+pub struct SvgOptions {
+    /// The width of the image, in pixels.
+    pub width: u32,
+
+    /// The height of the image, in pixels.
+    pub height: u32,
+
+    /// The projection of the camera.
+    pub projection: Projection,
+
+    /// The width of the edges, in pixels.
+    pub stroke_width: f32,
+
+    /// Whether the parts of edges hidden behind faces are left out.
+    pub hidden_lines: bool,
+
+    /// How polytopes of more than three dimensions are projected into 3D.
+    pub projection_type: ProjectionType,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 800,
+            projection: Projection::Perspective {
+                fov: CameraProjection::perspective().fov,
+            },
+            stroke_width: 2.0,
+            hidden_lines: true,
+            projection_type: ProjectionType::Perspective,
+        }
+    }
+}
+
+/// Returns whether the ray from a point in a given direction crosses any of
+/// the triangles before reaching `max_t` times the direction, using the
+/// Möller–Trumbore algorithm. Triangles through the point itself don't count.
+fn occluded(p: Vec3, dir: Vec3, max_t: f32, triangles: &[[Vec3; 3]]) -> bool {
+    const EPS: f32 = 1e-6;
+
+    triangles.iter().any(|&[a, b, c]| {
+        let (e1, e2) = (b - a, c - a);
+        let h = dir.cross(e2);
+        let det = e1.dot(h);
+        if det.abs() < EPS {
+            return false;
+        }
+
+        let s = p - a;
+        let u = s.dot(h) / det;
+        if !(-EPS..=1.0 + EPS).contains(&u) {
+            return false;
+        }
+
+        let q = s.cross(e1);
+        let v = dir.dot(q) / det;
+        if v < -EPS || u + v > 1.0 + EPS {
+            return false;
+        }
+
+        let t = e2.dot(q) / det;
+        t > 1e-4 && t < max_t - EPS
+    })
+}
+
+/// Returns the triangles of the mesh of a polytope.
+pub fn triangles(poly: &Concrete, projection_type: ProjectionType) -> Vec<[Vec3; 3]> {
+    let (coords, indices) = poly.triangles(projection_type);
+    indices
+        .chunks(3)
+        .map(|t| [0, 1, 2].map(|i| Vec3::from(coords[t[i] as usize])))
+        .collect()
+}
+
+/// Returns a function that maps points into the image described by the
+/// options, as seen from a camera with a given transform. It returns their
+/// coordinates in pixels together with their depth, or `None` for those behind
+/// the camera.
+pub fn to_image(camera: &Transform, opts: &SvgOptions) -> impl Fn(Vec3) -> Option<Vec3> {
+    let (width, height) = (opts.width as f32, opts.height as f32);
+    let view_proj = opts.projection.matrix(width, height) * camera.compute_matrix().inverse();
+
+    move |p: Vec3| {
+        let clip = view_proj * p.extend(1.0);
+        (clip.w > 0.0).then(|| {
+            let ndc = clip.truncate() / clip.w;
+            Vec3::new(
+                (ndc.x + 1.0) / 2.0 * width,
+                (1.0 - ndc.y) / 2.0 * height,
+                ndc.z,
+            )
+        })
+    }
+}
+
+/// Returns the visible stretches of the edges of a polytope, as seen from a
+/// camera with a given transform, with their endpoints in pixels.
+pub fn visible_edges(poly: &Concrete, camera: &Transform, opts: &SvgOptions) -> Vec<[Vec2; 2]> {
+    let mut lines = Vec::new();
+
+    let edges = match poly
+        .get_element_list(2)
+        .filter(|_| poly.vertex_count() != 0)
+    {
+        Some(edges) => edges,
+        None => return lines,
+    };
+
+    let to_image = to_image(camera, opts);
+    let vertices: Vec<_> = vertex_coords(poly, poly.vertices.iter(), opts.projection_type)
+        .into_iter()
+        .map(Vec3::from)
+        .collect();
+
+    let triangles = if opts.hidden_lines {
+        triangles(poly, opts.projection_type)
+    } else {
+        Vec::new()
+    };
+
+    for edge in edges.iter() {
+        let (v0, v1) = (vertices[edge.subs[0]], vertices[edge.subs[1]]);
+        let at = |t: f32| v0.lerp(v1, t / SAMPLES as f32);
+
+        // Finds the runs of visible pieces of the edge, by testing their
+        // midpoints.
+        let mut start = None;
+        for k in 0..=SAMPLES {
+            let visible = k < SAMPLES && {
+                let p = at(k as f32 + 0.5);
+                let (dir, max_t) = opts.projection.towards_camera(camera, p);
+                !occluded(p, dir, max_t, &triangles)
+            };
+            match (start, visible) {
+                (None, true) => start = Some(k),
+                (Some(s), false) => {
+                    if let (Some(p), Some(q)) = (to_image(at(s as f32)), to_image(at(k as f32))) {
+                        lines.push([p.truncate(), q.truncate()]);
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    lines
+}
+
+/// Renders the edges of a polytope, as seen from a camera with a given
+/// transform and the projection in the options, into an SVG image. Every
+/// visible stretch of an edge becomes a `<line>` element.
+pub fn render_svg(poly: &Concrete, camera: &Transform, opts: &SvgOptions) -> String {
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        opts.width, opts.height
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<g stroke="black" stroke-width="{}" stroke-linecap="round">"#,
+        opts.stroke_width
+    )
+    .unwrap();
+
+    for [p, q] in visible_edges(poly, camera, opts) {
+        writeln!(
+            svg,
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}"/>"#,
+            p.x, p.y, q.x, q.y
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::Polytope;
+
+    /// Counts the lines in the SVG of a cube seen from a generic direction,
+    /// with and without hidden line removal.
+    #[test]
+    fn cube() {
+        let cube = Concrete::cube();
+        let camera = Transform::from_xyz(3.0, 4.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y);
+        let mut opts = SvgOptions {
+            hidden_lines: false,
+            ..Default::default()
+        };
+
+        let svg = render_svg(&cube, &camera, &opts);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</g>\n</svg>\n"));
+        assert_eq!(svg.matches("<line ").count(), 12);
+
+        // The three edges through the far vertex are hidden.
+        opts.hidden_lines = true;
+        let svg = render_svg(&cube, &camera, &opts);
+        assert_eq!(svg.matches("<line ").count(), 9);
+
+        // The same holds through an orthographic projection.
+        opts.projection = Projection::Orthographic { scale: 2.0 };
+        let svg = render_svg(&cube, &camera, &opts);
+        assert_eq!(svg.matches("<line ").count(), 9);
+        opts.hidden_lines = false;
+        let svg = render_svg(&cube, &camera, &opts);
+        assert_eq!(svg.matches("<line ").count(), 12);
+    }
+}
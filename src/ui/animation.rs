@@ -0,0 +1,324 @@
+//! Contains the window and systems that slowly rotate the loaded polytope.
+
+use super::{camera::ProjectionType, elements::HighlightMesh};
+use crate::{
+    mesh::{update_mesh, EdgeColoring, Renderable},
+    Concrete, Float,
+};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{conc::ConcretePolytope, float::Float as Float2, geometry::Matrix};
+
+/// The default angular speed of a new plane of rotation, in radians per
+/// second.
+const DEFAULT_SPEED: Float = 0.5;
+
+/// The plugin in charge of the rotation animation.
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Animation>()
+            .init_resource::<ShowAnimation>()
+            .init_resource::<RotatedPolytope>()
+            .add_system(show_animation.system().label("show_windows"))
+            .add_system(advance_animation.system().label("advance_animation"))
+            .add_system(rotate_transform.system().after("advance_animation"))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                rotate_vertices
+                    .system()
+                    .label("rotate_vertices")
+                    .after("update_polytopes"),
+            );
+    }
+}
+
+/// Stores whether the animation window is shown.
+#[derive(Default)]
+pub struct ShowAnimation(pub bool);
+
+/// A rotated copy of the polytope of more than three dimensions being
+/// animated, or `None` if there's no such animation. This is what's actually
+/// shown on the viewport, so picking and highlighting use its vertices.
+#[derive(Default)]
+pub struct RotatedPolytope(pub Option<Concrete>);
+
+/// A plane in which the polytope rotates.
+#[derive(Clone, Copy)]
+pub struct RotationPlane {
+    /// The two coordinate axes spanning the plane. The rotation takes the
+    /// first towards the second.
+    pub axes: (usize, usize),
+
+    /// The angular speed, in radians per second.
+    pub speed: Float,
+
+    /// The angle rotated so far, in radians.
+    pub angle: Float,
+}
+
+impl RotationPlane {
+    /// Initializes a new plane of rotation with the default speed.
+    pub fn new(axes: (usize, usize)) -> Self {
+        Self {
+            axes,
+            speed: DEFAULT_SPEED,
+            angle: 0.0,
+        }
+    }
+
+    /// Returns whether the plane makes sense in a given number of dimensions.
+    fn is_valid(&self, dim: usize) -> bool {
+        let (i, j) = self.axes;
+        i != j && i < dim && j < dim
+    }
+}
+
+/// The planes in which the polytope rotates. Rotations in planes involving the
+/// fourth axis or further can't be shown by rotating the mesh, so the vertices
+/// are rotated before being projected instead.
+#[derive(Default)]
+pub struct Animation {
+    /// The planes of rotation, applied in order.
+    pub planes: Vec<RotationPlane>,
+
+    /// Whether the animation is paused.
+    pub paused: bool,
+}
+
+impl Animation {
+    /// Returns whether the polytope is rotated at all in a given number of
+    /// dimensions.
+    fn is_active(&self, dim: usize) -> bool {
+        self.planes.iter().any(|plane| plane.is_valid(dim))
+    }
+
+    /// Builds the rotation matrix for the current angles. This is rebuilt from
+    /// the angles every time rather than updated, so that the floating point
+    /// error doesn't build up over time.
+    pub fn matrix(&self, dim: usize) -> Matrix<Float> {
+        let mut mat = Matrix::identity(dim, dim);
+
+        for plane in self.planes.iter().filter(|plane| plane.is_valid(dim)) {
+            let (i, j) = plane.axes;
+            let (s, c) = plane.angle.sin_cos();
+            let mut rot = Matrix::identity(dim, dim);
+            rot[(i, i)] = c;
+            rot[(j, j)] = c;
+            rot[(j, i)] = s;
+            rot[(i, j)] = -s;
+            mat = rot * mat;
+        }
+
+        mat
+    }
+}
+
+/// Advances the angle of every plane of rotation.
+fn advance_animation(time: Res<'_, Time>, mut animation: ResMut<'_, Animation>) {
+    if animation.paused || animation.planes.is_empty() {
+        return;
+    }
+
+    let delta = time.delta_seconds_f64() as Float;
+    for plane in animation.planes.iter_mut() {
+        plane.angle = (plane.angle + plane.speed * delta).rem_euclid(Float::TAU);
+    }
+}
+
+/// Rotates the polytope entity when it has at most three dimensions, so that
+/// we don't need to rebuild its mesh. Polytopes of fewer dimensions are
+/// rotated as if they had three.
+fn rotate_transform(
+    animation: Res<'_, Animation>,
+    mut polies: Query<'_, '_, (&Concrete, &mut Transform)>,
+) {
+    for (poly, mut transform) in polies.iter_mut() {
+        let dim = poly.dim_or();
+
+        let rotation = if dim <= 3 && animation.is_active(3) {
+            // Both matrices are stored in column-major order.
+            let mat = animation.matrix(3);
+            let mut cols = [0.0; 9];
+            for (col, &x) in cols.iter_mut().zip(mat.as_slice()) {
+                *col = x as f32;
+            }
+
+            Quat::from_mat3(&Mat3::from_cols_array(&cols))
+        } else {
+            Quat::IDENTITY
+        };
+
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
+    }
+}
+
+/// Rebuilds the meshes of a polytope of more than three dimensions from a
+/// rotated copy of its vertices. The polytope itself is never modified, so its
+/// vertices serve as the pristine base coordinates.
+fn rotate_vertices(
+    animation: Res<'_, Animation>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    polies: Query<'_, '_, (&Concrete, &Handle<Mesh>, &Children)>,
+    changed: Query<'_, '_, &Concrete, Changed<Concrete>>,
+    wfs: Query<'_, '_, &Handle<Mesh>, (Without<Concrete>, Without<HighlightMesh>)>,
+    projection_type: Res<'_, ProjectionType>,
+    edge_coloring: Res<'_, EdgeColoring>,
+    mut rotated: ResMut<'_, RotatedPolytope>,
+) {
+    let poly_changed = changed.iter().next().is_some();
+
+    for (poly, mesh_handle, children) in polies.iter() {
+        let dim = poly.dim_or();
+        let active = dim > 3 && animation.is_active(dim);
+
+        if !active {
+            // Restores the unrotated meshes once the animation stops. We only
+            // touch the resource if there's a rotated copy to drop.
+            if rotated.0.is_some() {
+                rotated.0 = None;
+
+                if !poly_changed {
+                    update_mesh(
+                        meshes.get_mut(mesh_handle).unwrap(),
+                        poly.mesh(*projection_type),
+                    );
+                    for child in children.iter() {
+                        if let Ok(wf_handle) = wfs.get(*child) {
+                            update_mesh(
+                                meshes.get_mut(wf_handle).unwrap(),
+                                poly.wireframe(*projection_type, &edge_coloring),
+                            );
+                        }
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        if !animation.is_changed() && !poly_changed && rotated.0.is_some() {
+            continue;
+        }
+
+        // We keep a copy of the polytope around, so that we only need to
+        // overwrite its vertices each frame.
+        if poly_changed || rotated.0.is_none() {
+            rotated.0 = Some(poly.clone());
+        }
+        let rotated = rotated.0.as_mut().unwrap();
+
+        let mat = animation.matrix(dim);
+        for (v, base) in rotated.vertices.iter_mut().zip(poly.vertices.iter()) {
+            *v = &mat * base;
+        }
+
+        update_mesh(
+            meshes.get_mut(mesh_handle).unwrap(),
+            rotated.mesh(*projection_type),
+        );
+        for child in children.iter() {
+            if let Ok(wf_handle) = wfs.get(*child) {
+                update_mesh(
+                    meshes.get_mut(wf_handle).unwrap(),
+                    rotated.wireframe(*projection_type, &edge_coloring),
+                );
+            }
+        }
+    }
+}
+
+/// Shows the window that sets up the planes of rotation.
+fn show_animation(
+    egui_ctx: Res<'_, EguiContext>,
+    polies: Query<'_, '_, &Concrete>,
+    mut animation: ResMut<'_, Animation>,
+    mut show_animation: ResMut<'_, ShowAnimation>,
+) {
+    if !show_animation.0 {
+        return;
+    }
+
+    let dim = polies.iter().next().map_or(0, |poly| poly.dim_or());
+    let animation = &mut *animation;
+    let mut removed = None;
+
+    egui::Window::new("Animate rotation")
+        .open(&mut show_animation.0)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            for (idx, plane) in animation.planes.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    let max_axis = dim.saturating_sub(1);
+                    ui.add(egui::DragValue::new(&mut plane.axes.0).clamp_range(0..=max_axis));
+                    ui.add(egui::DragValue::new(&mut plane.axes.1).clamp_range(0..=max_axis));
+                    ui.add(egui::Slider::new(&mut plane.speed, -3.0..=3.0).text("rad/s"));
+
+                    if ui.button("Remove").clicked() {
+                        removed = Some(idx);
+                    }
+                });
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Add plane").clicked() {
+                    // Rotates in the xw plane by default, if it exists.
+                    let axes = if dim > 3 { (0, 3) } else { (0, 2) };
+                    animation.planes.push(RotationPlane::new(axes));
+                }
+
+                let label = if animation.paused { "Resume" } else { "Pause" };
+                if ui.button(label).clicked() {
+                    animation.paused = !animation.paused;
+                }
+
+                if ui.button("Reset").clicked() {
+                    for plane in animation.planes.iter_mut() {
+                        plane.angle = 0.0;
+                    }
+                }
+            });
+        });
+
+    if let Some(idx) = removed {
+        animation.planes.remove(idx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    /// Builds an animation with a few planes of rotation at some angles.
+    fn animation(angles: [Float; 3]) -> Animation {
+        let mut animation = Animation::default();
+        for (axes, angle) in [(0, 3), (1, 2), (2, 0)].iter().zip(angles) {
+            animation.planes.push(RotationPlane {
+                angle,
+                ..RotationPlane::new(*axes)
+            });
+        }
+
+        animation
+    }
+
+    /// Checks that the rotation matrices are orthogonal, and that they're the
+    /// identity before anything has rotated.
+    #[test]
+    fn matrix() {
+        let identity = Matrix::identity(4, 4);
+        assert_abs_diff_eq!(animation([0.0; 3]).matrix(4), identity);
+
+        let mat = animation([0.7, -1.3, 2.0]).matrix(4);
+        assert_abs_diff_eq!(&mat * mat.transpose(), identity, epsilon = Float::EPS);
+        assert_abs_diff_eq!(mat.determinant(), 1.0, epsilon = Float::EPS);
+
+        // Planes involving missing axes are ignored.
+        assert_abs_diff_eq!(animation([0.7, 0.0, 0.0]).matrix(3), Matrix::identity(3, 3));
+    }
+}
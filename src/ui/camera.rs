@@ -1,15 +1,22 @@
 //! Contains the methods to setup the camera.
 
-use std::ops::Mul;
+use std::{collections::HashMap, ops::Mul};
 
 use bevy::{
-    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    input::{
+        mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        touch::Touches,
+    },
     math::EulerRot,
     prelude::*,
-    render::camera::Camera,
+    render::camera::{
+        Camera, DepthCalculation, OrthographicProjection, PerspectiveProjection, ScalingMode,
+    },
 };
 use bevy_egui::{egui::CtxRef, EguiContext};
 
+use crate::Float;
+
 /// The plugin handling all camera input.
 pub struct InputPlugin;
 
@@ -17,20 +24,40 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<CameraInputEvent>()
             .init_resource::<ProjectionType>()
+            .init_resource::<CameraProjection>()
             // We register inputs after the library has been shown, so that we
             // know whether mouse input should register.
             .add_system(add_cam_input_events.system().after("show_library"))
-            .add_system(update_cameras_and_anchors.system());
+            .add_system(update_cameras_and_anchors.system())
+            .add_system(update_camera_projection.system());
     }
 }
 
-#[derive(Clone, Copy)]
+/// How polytopes of more than three dimensions are reduced to 3D before being
+/// meshed.
+#[derive(Clone, Copy, PartialEq)]
 pub enum ProjectionType {
-    /// We're projecting orthogonally.
+    /// We're projecting orthogonally, dropping every coordinate past the
+    /// third.
     Orthogonal,
 
-    /// We're projecting from a point.
+    /// We're projecting from a point along each of the extra coordinate axes.
     Perspective,
+
+    /// We're projecting from a point outside of a facet into its hyperplane,
+    /// giving a Schlegel diagram.
+    Schlegel {
+        /// The facet we project into, or `None` to use the facet nearest to
+        /// the camera.
+        facet: Option<usize>,
+
+        /// The distance from the facet to the point we project from.
+        distance: Float,
+    },
+
+    /// We're showing a cross-section of the polytope, which is embedded into
+    /// 3D space as it is.
+    Section,
 }
 
 impl Default for ProjectionType {
@@ -40,11 +67,16 @@ impl Default for ProjectionType {
 }
 
 impl ProjectionType {
-    /// Flips the projection type.
-    pub fn flip(&mut self) {
+    /// The default distance from the facet in a Schlegel diagram.
+    pub const SCHLEGEL_DISTANCE: Float = 0.5;
+
+    /// Returns the name of the projection type, as shown on the UI.
+    pub fn name(&self) -> &'static str {
         match self {
-            Self::Orthogonal => *self = Self::Perspective,
-            Self::Perspective => *self = Self::Orthogonal,
+            Self::Orthogonal => "Orthogonal",
+            Self::Perspective => "Perspective",
+            Self::Schlegel { .. } => "Schlegel",
+            Self::Section => "Cross-section",
         }
     }
 
@@ -54,6 +86,52 @@ impl ProjectionType {
     }
 }
 
+/// The projection used by the 3D camera.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CameraProjection {
+    /// Farther objects look smaller.
+    Perspective,
+
+    /// Objects look the same size regardless of their distance.
+    Orthographic,
+}
+
+impl Default for CameraProjection {
+    fn default() -> Self {
+        Self::Perspective
+    }
+}
+
+impl CameraProjection {
+    /// The perspective projection for the camera.
+    pub fn perspective() -> PerspectiveProjection {
+        PerspectiveProjection {
+            near: 0.01,
+            far: 500.,
+            ..Default::default()
+        }
+    }
+
+    /// The orthographic projection for the camera, showing the same area as
+    /// the perspective projection does at a given distance.
+    pub fn orthographic(distance: f32) -> OrthographicProjection {
+        OrthographicProjection {
+            near: -500.,
+            far: 500.,
+            scale: Self::orthographic_scale(distance),
+            scaling_mode: ScalingMode::FixedVertical,
+            depth_calculation: DepthCalculation::Distance,
+            ..Default::default()
+        }
+    }
+
+    /// The scale of the orthographic projection at a given distance from the
+    /// anchor.
+    fn orthographic_scale(distance: f32) -> f32 {
+        distance * (Self::perspective().fov / 2.).tan()
+    }
+}
+
 /// An input event for the camera.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CameraInputEvent {
@@ -104,7 +182,27 @@ impl Mul<CameraInputEvent> for f32 {
     }
 }
 
+/// The maximum time between two taps for them to count as a double tap, in
+/// seconds.
+const DOUBLE_TAP_TIME: f64 = 0.3;
+
+/// The state of touch input that must be remembered between frames.
+#[derive(Default)]
+pub struct TouchState {
+    /// The time at which the screen was last tapped with a single finger.
+    last_tap: Option<f64>,
+
+    /// The last known position of each finger touching the screen.
+    positions: HashMap<u64, Vec2>,
+}
+
 impl CameraInputEvent {
+    /// Returns the rotation about the anchor caused by dragging the mouse or a
+    /// finger, given the drag in pixels and the height of the window.
+    fn drag_rotation(delta: Vec2, height: f32, real_scale: f32) -> Self {
+        Self::RotateAnchor(-800. * real_scale * delta / height)
+    }
+
     fn rotate(vec: Vec2, anchor_tf: &mut Transform) {
         anchor_tf.rotate(Quat::from_euler(EulerRot::YXZ, vec.x, vec.y, 0.));
     }
@@ -199,10 +297,8 @@ impl CameraInputEvent {
         cam_inputs: &mut EventWriter<'_, '_, Self>,
     ) {
         if mouse_button.pressed(MouseButton::Left) || mouse_button.pressed(MouseButton::Right) {
-            for MouseMotion { mut delta } in mouse_move.iter() {
-                delta.x /= height;
-                delta.y /= height;
-                cam_inputs.send(Self::RotateAnchor(-800. * real_scale * delta))
+            for MouseMotion { delta } in mouse_move.iter() {
+                cam_inputs.send(Self::drag_rotation(*delta, height, real_scale))
             }
         }
     }
@@ -222,9 +318,69 @@ impl CameraInputEvent {
             cam_inputs.send(Self::Zoom(unit_scale * -scale * y))
         }
     }
+
+    /// Processes camera events coming from a touch screen. Dragging a finger
+    /// rotates the camera, pinching two fingers zooms it, dragging two fingers
+    /// translates it, and double tapping resets it.
+    fn cam_events_from_touch(
+        touches: &Touches,
+        time: &Time,
+        touch_state: &mut TouchState,
+        height: f32,
+        real_scale: f32,
+        cam_inputs: &mut EventWriter<'_, '_, Self>,
+    ) {
+        // Recognizes double taps.
+        if touches.iter_just_pressed().next().is_some() && touches.iter().count() == 1 {
+            let now = time.seconds_since_startup();
+
+            match touch_state.last_tap {
+                Some(last_tap) if now - last_tap < DOUBLE_TAP_TIME => {
+                    cam_inputs.send(Self::Reset);
+                    touch_state.last_tap = None;
+                }
+                _ => touch_state.last_tap = Some(now),
+            }
+        }
+
+        // The movement of each finger since the last frame.
+        let positions = &mut touch_state.positions;
+        let mut moves = Vec::new();
+        for touch in touches.iter() {
+            let position = touch.position();
+            let previous = positions.insert(touch.id(), position).unwrap_or(position);
+            moves.push((previous, position));
+        }
+        positions.retain(|id, _| touches.get_pressed(*id).is_some());
+
+        match moves.as_slice() {
+            [(previous, position)] => cam_inputs.send(Self::drag_rotation(
+                *position - *previous,
+                height,
+                real_scale,
+            )),
+
+            [(prev_a, pos_a), (prev_b, pos_b)] => {
+                // Pinching scales the distance to the anchor by the inverse
+                // of the change in distance between the fingers.
+                let prev_dist = prev_a.distance(*prev_b);
+                let dist = pos_a.distance(*pos_b);
+                if prev_dist > 0. && dist > 0. {
+                    cam_inputs.send(Self::Zoom(prev_dist / dist - 1.));
+                }
+
+                // The screen's y axis points downwards.
+                let delta = (*pos_a - *prev_a + *pos_b - *prev_b) / (2. * height);
+                cam_inputs.send(Self::Translate(Vec3::new(-delta.x, delta.y, 0.) * 4.));
+            }
+
+            _ => {}
+        }
+    }
 }
 
-/// The system that processes all input from the mouse and keyboard.
+/// The system that processes all input from the mouse, keyboard, and touch
+/// screen.
 #[allow(clippy::too_many_arguments)]
 fn add_cam_input_events(
     time: Res<'_, Time>,
@@ -232,6 +388,8 @@ fn add_cam_input_events(
     mouse_button: Res<'_, Input<MouseButton>>,
     mouse_move: EventReader<'_, '_, MouseMotion>,
     mouse_wheel: EventReader<'_, '_, MouseWheel>,
+    touches: Res<'_, Touches>,
+    mut touch_state: Local<'_, TouchState>,
     windows: Res<'_, Windows>,
     mut cam_inputs: EventWriter<'_, '_, CameraInputEvent>,
     egui_ctx: Res<'_, EguiContext>,
@@ -256,6 +414,14 @@ fn add_cam_input_events(
             cam_inputs,
         );
         CameraInputEvent::cam_events_from_wheel(mouse_wheel, scale, cam_inputs);
+        CameraInputEvent::cam_events_from_touch(
+            &touches,
+            &time,
+            &mut touch_state,
+            height,
+            real_scale,
+            cam_inputs,
+        );
     }
 }
 
@@ -289,3 +455,78 @@ fn update_cameras_and_anchors(
         }
     }
 }
+
+/// Switches the projection of the cameras whenever it's changed, and keeps the
+/// scale of the orthographic projection in sync with the zoom.
+fn update_camera_projection(
+    mut commands: Commands<'_, '_>,
+    projection: Res<'_, CameraProjection>,
+    mut cams: Query<
+        '_,
+        '_,
+        (Entity, &Transform, Option<&mut OrthographicProjection>),
+        With<Camera>,
+    >,
+) {
+    for (cam, cam_tf, ortho) in cams.iter_mut() {
+        let distance = cam_tf.translation.z;
+
+        match (*projection, ortho) {
+            (CameraProjection::Perspective, Some(_)) => {
+                commands
+                    .entity(cam)
+                    .remove::<OrthographicProjection>()
+                    .insert(CameraProjection::perspective());
+            }
+            (CameraProjection::Orthographic, None) => {
+                commands
+                    .entity(cam)
+                    .remove::<PerspectiveProjection>()
+                    .insert(CameraProjection::orthographic(distance));
+            }
+            (CameraProjection::Orthographic, Some(mut ortho)) => {
+                let scale = CameraProjection::orthographic_scale(distance);
+                if (ortho.scale - scale).abs() > f32::EPSILON {
+                    ortho.scale = scale;
+                }
+            }
+            (CameraProjection::Perspective, None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies an event to the default camera and anchor, and returns them.
+    fn apply(event: CameraInputEvent) -> (Transform, Transform) {
+        let mut anchor_tf = Transform::default();
+        let mut cam_tf = Transform::default();
+        CameraInputEvent::reset(&mut anchor_tf, &mut cam_tf);
+        event.update_camera_and_anchor(&mut anchor_tf, &mut cam_tf, &GlobalTransform::identity());
+        (anchor_tf, cam_tf)
+    }
+
+    #[test]
+    fn drag_rotates_anchor() {
+        let (start, _) = apply(CameraInputEvent::Zoom(0.));
+        let delta = Vec2::new(30., -20.);
+        let event = CameraInputEvent::drag_rotation(delta, 600., 0.01);
+        let (anchor_tf, _) = apply(event);
+
+        let angles = -800. * 0.01 * delta / 600.;
+        let expected = Quat::from_euler(EulerRot::YXZ, angles.x, angles.y, 0.) * start.rotation;
+        assert!(anchor_tf.rotation.abs_diff_eq(expected, 1e-6));
+    }
+
+    #[test]
+    fn pinch_zooms_camera() {
+        let (_, start) = apply(CameraInputEvent::Zoom(0.));
+
+        // Spreading the fingers to twice their distance halves the distance to
+        // the anchor.
+        let (_, cam_tf) = apply(CameraInputEvent::Zoom(0.5 - 1.));
+        assert!((cam_tf.translation.z - start.translation.z / 2.).abs() < 1e-6);
+    }
+}
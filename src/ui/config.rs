@@ -7,6 +7,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::mesh::{EdgeColoring, LengthMapping, Palette};
+
 use bevy::{app::AppExit, prelude::*};
 use bevy_egui::{egui, EguiContext};
 use directories::ProjectDirs;
@@ -36,10 +38,15 @@ impl Plugin for ConfigPlugin {
         // Makes resources from the configuration, which may or may not
         // correspond to the actual stored values themselves.
         app.insert_resource(config_path)
-            .insert_resource(LibPath::default())
+            .insert_resource(config.lib_path.clone())
             .insert_resource(config.background_color.clear_color())
             .insert_resource(config.light_mode.visuals())
+            .insert_resource(config.wireframe_colors)
+            .insert_resource(config.edge_coloring)
+            .init_resource::<ShowPreferences>()
             .add_system(update_visuals.system())
+            .add_system(show_preferences.system())
+            .add_system(update_wireframe_colors.system())
             .add_system_to_stage(CoreStage::Last, save_config.system());
     }
 }
@@ -67,6 +74,13 @@ impl Default for LibPath {
     }
 }
 
+impl LibPath {
+    /// Creates a new library path from a given path.
+    pub fn new(path: PathBuf) -> Self {
+        Self(path.to_string_lossy().into_owned())
+    }
+}
+
 impl AsRef<OsStr> for LibPath {
     fn as_ref(&self) -> &OsStr {
         self.0.as_ref()
@@ -106,6 +120,162 @@ impl LightMode {
     }
 }
 
+/// The colors of the wireframe in sRGB.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WireframeColors {
+    /// The color of the highlighted elements.
+    pub selected: [u8; 3],
+
+    /// The color of the rest of the wireframe.
+    pub unselected: [u8; 3],
+}
+
+impl Default for WireframeColors {
+    fn default() -> Self {
+        Self {
+            selected: [126, 192, 255],
+            unselected: [150, 150, 150],
+        }
+    }
+}
+
+impl WireframeColors {
+    /// Converts one of the stored colors into a Bevy color.
+    pub fn color([r, g, b]: [u8; 3]) -> Color {
+        Color::rgb_u8(r, g, b)
+    }
+}
+
+/// Stores whether the preferences window is shown.
+#[derive(Default)]
+pub struct ShowPreferences(pub bool);
+
+/// Shows a color picker for an sRGB color.
+fn rgb_edit(ui: &mut egui::Ui, rgb: &mut [u8; 3], label: &str) {
+    ui.horizontal(|ui| {
+        let [r, g, b] = *rgb;
+        let mut color = egui::Color32::from_rgb(r, g, b);
+        egui::color_picker::color_edit_button_srgba(
+            ui,
+            &mut color,
+            egui::color_picker::Alpha::Opaque,
+        );
+        *rgb = [color.r(), color.g(), color.b()];
+
+        ui.label(label);
+    });
+}
+
+/// Shows the window with the appearance preferences.
+fn show_preferences(
+    egui_ctx: Res<'_, EguiContext>,
+    mut show_preferences: ResMut<'_, ShowPreferences>,
+    mut wireframe_colors: ResMut<'_, WireframeColors>,
+    mut edge_coloring: ResMut<'_, EdgeColoring>,
+) {
+    if !show_preferences.0 {
+        return;
+    }
+
+    // We only write back the colors when they change, so that the materials
+    // and wireframes aren't updated every frame.
+    let mut colors = *wireframe_colors;
+    let mut coloring = *edge_coloring;
+
+    egui::Window::new("Preferences")
+        .open(&mut show_preferences.0)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            ui.heading("Wireframe");
+            rgb_edit(ui, &mut colors.unselected, "Edges");
+            rgb_edit(ui, &mut colors.selected, "Highlighted elements");
+
+            if ui.button("Reset").clicked() {
+                colors = Default::default();
+            }
+
+            ui.separator();
+            ui.checkbox(&mut coloring.by_length, "Color edges by length");
+
+            // The options only matter when edges are colored by length.
+            if coloring.by_length {
+                egui::ComboBox::from_label("Palette")
+                    .selected_text(coloring.palette.name())
+                    .show_ui(ui, |ui| {
+                        for palette in Palette::ALL {
+                            ui.selectable_value(&mut coloring.palette, palette, palette.name());
+                        }
+                    });
+
+                ui.radio_value(
+                    &mut coloring.mapping,
+                    LengthMapping::Range,
+                    "From shortest to longest",
+                );
+
+                ui.horizontal(|ui| {
+                    let mut spread = match coloring.mapping {
+                        LengthMapping::Deviation(spread) => spread,
+                        LengthMapping::Range => LengthMapping::DEFAULT_SPREAD,
+                    };
+
+                    ui.radio_value(
+                        &mut coloring.mapping,
+                        LengthMapping::Deviation(spread),
+                        "Deviation from median",
+                    );
+
+                    if let LengthMapping::Deviation(_) = coloring.mapping {
+                        ui.add(
+                            egui::DragValue::new(&mut spread)
+                                .speed(0.001)
+                                .clamp_range(0.0001..=1.0),
+                        );
+                        coloring.mapping = LengthMapping::Deviation(spread);
+                    }
+                });
+            }
+        });
+
+    if colors != *wireframe_colors {
+        *wireframe_colors = colors;
+    }
+
+    if coloring != *edge_coloring {
+        *edge_coloring = coloring;
+    }
+}
+
+/// Updates the wireframe materials whenever their colors are changed. When
+/// edges are colored by length, the unselected material takes the palette as
+/// its texture instead.
+fn update_wireframe_colors(
+    wireframe_colors: Res<'_, WireframeColors>,
+    edge_coloring: Res<'_, EdgeColoring>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    mut textures: ResMut<'_, Assets<Texture>>,
+) {
+    if !wireframe_colors.is_changed() && !edge_coloring.is_changed() {
+        return;
+    }
+
+    let selected = crate::WIREFRAME_SELECTED_MATERIAL.typed::<StandardMaterial>();
+    if let Some(material) = materials.get_mut(&selected) {
+        material.base_color = WireframeColors::color(wireframe_colors.selected);
+    }
+
+    let unselected = crate::WIREFRAME_UNSELECTED_MATERIAL.typed::<StandardMaterial>();
+    if let Some(material) = materials.get_mut(&unselected) {
+        if edge_coloring.by_length {
+            material.base_color = Color::WHITE;
+            material.base_color_texture = Some(textures.add(edge_coloring.palette.texture()));
+        } else {
+            material.base_color = WireframeColors::color(wireframe_colors.unselected);
+            material.base_color_texture = None;
+        }
+    }
+}
+
 /// Updates the application appearance whenever the visuals are changed. This
 /// occurs at application startup and whenever the user toggles light/dark mode.
 fn update_visuals(egui_ctx: Res<'_, EguiContext>, visuals: Res<'_, egui::Visuals>) {
@@ -124,6 +294,18 @@ pub struct Config {
 
     /// Whether light mode is enabled.
     pub light_mode: LightMode,
+
+    /// The folder the library is read from.
+    #[serde(default)]
+    pub lib_path: LibPath,
+
+    /// The colors of the wireframe.
+    #[serde(default)]
+    pub wireframe_colors: WireframeColors,
+
+    /// How the edges of the wireframe are colored.
+    #[serde(default)]
+    pub edge_coloring: EdgeColoring,
 }
 
 impl Config {
@@ -195,12 +377,18 @@ fn save_config(
 
     background_color: Res<'_, ClearColor>,
     visuals: Res<'_, egui::Visuals>,
+    lib_path: Res<'_, LibPath>,
+    wireframe_colors: Res<'_, WireframeColors>,
+    edge_coloring: Res<'_, EdgeColoring>,
 ) {
     // If the application is being exited:
     if exit.iter().next().is_some() {
         let config = Config {
             background_color: BgColor::new(background_color.as_ref()),
             light_mode: LightMode(!visuals.dark_mode),
+            lib_path: (*lib_path).clone(),
+            wireframe_colors: *wireframe_colors,
+            edge_coloring: *edge_coloring,
         };
 
         config.save(&config_path.0);
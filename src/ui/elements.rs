@@ -0,0 +1,223 @@
+//! Contains the window that lists the elements of the loaded polytope, and
+//! the systems that highlight the selected element on the viewport.
+
+use super::{
+    animation::RotatedPolytope, camera::ProjectionType, main_window::PolyName, picking::Selection,
+};
+use crate::{mesh::Renderable, Concrete};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{
+    abs::Ranked,
+    conc::{element_types::EL_NAMES, ConcretePolytope},
+};
+
+/// The number of elements shown in each page of the list.
+const PAGE_SIZE: usize = 50;
+
+/// The plugin in charge of the element list and the highlighted element.
+pub struct ElementsPlugin;
+
+impl Plugin for ElementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Highlight>()
+            .init_resource::<ShowElements>()
+            .add_system(show_elements.system().label("show_windows"))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_highlight.system().after("rotate_vertices"),
+            );
+    }
+}
+
+/// Stores whether the element list is shown.
+#[derive(Default)]
+pub struct ShowElements(pub bool);
+
+/// The element currently highlighted on the viewport, if any.
+#[derive(Default)]
+pub struct Highlight {
+    /// The rank and index of the selected element.
+    pub selection: Option<(usize, usize)>,
+}
+
+/// Marks the mesh that shows the highlighted element.
+#[derive(Clone, Copy, Default)]
+pub struct HighlightMesh;
+
+/// Returns the name of the elements of a given rank.
+fn rank_name(rank: usize) -> String {
+    match EL_NAMES.get(rank) {
+        Some(name) => name.to_string(),
+        None => format!("{}-elements", rank - 1),
+    }
+}
+
+/// Shows the window that lists the elements of the polytope, grouped by rank.
+/// Since a polytope can have thousands of elements, these are shown in pages.
+fn show_elements(
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut poly_name: ResMut<'_, PolyName>,
+    mut show_elements: ResMut<'_, ShowElements>,
+    mut highlight: ResMut<'_, Highlight>,
+    picked: Res<'_, Selection>,
+    mut pages: Local<'_, Vec<usize>>,
+) {
+    if !show_elements.0 {
+        return;
+    }
+
+    let mut poly = match query.iter_mut().next() {
+        Some(poly) => poly,
+        None => return,
+    };
+
+    let rank = poly.rank();
+    pages.resize(rank + 1, 0);
+    let mut selection = highlight.selection;
+    let mut open_element = false;
+    let mut open_vertex_figure = false;
+
+    egui::Window::new("Elements")
+        .open(&mut show_elements.0)
+        .show(egui_ctx.ctx(), |ui| {
+            // Shows the element picked on the viewport.
+            match picked.element {
+                Some((1, idx)) => {
+                    ui.label(format!("Picked vertex #{}", idx));
+                }
+                Some((r, idx)) => {
+                    let length = poly.get_element(r, idx).map(|edge| {
+                        let vertices = poly.vertices();
+                        (&vertices[edge.subs[0]] - &vertices[edge.subs[1]]).norm()
+                    });
+
+                    ui.label(match length {
+                        Some(length) => format!("Picked edge #{}, length {}", idx, length),
+                        None => format!("Picked edge #{}", idx),
+                    });
+                }
+                None => {
+                    ui.label("Click on a vertex or edge to pick it.");
+                }
+            }
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                // We skip the minimal and maximal elements.
+                for r in 1..rank {
+                    let count = poly.el_count(r);
+                    let page_count = (count + PAGE_SIZE - 1) / PAGE_SIZE;
+                    let page = &mut pages[r];
+                    *page = (*page).min(page_count.saturating_sub(1));
+
+                    egui::CollapsingHeader::new(format!("{}: {}", rank_name(r), count))
+                        .id_source(r)
+                        .show(ui, |ui| {
+                            if page_count > 1 {
+                                ui.horizontal(|ui| {
+                                    if ui.button("<").clicked() && *page > 0 {
+                                        *page -= 1;
+                                    }
+
+                                    ui.label(format!("Page {} of {}", *page + 1, page_count));
+
+                                    if ui.button(">").clicked() && *page + 1 < page_count {
+                                        *page += 1;
+                                    }
+                                });
+                            }
+
+                            let start = *page * PAGE_SIZE;
+                            for idx in start..count.min(start + PAGE_SIZE) {
+                                let selected = selection == Some((r, idx));
+                                let label = match poly.abs.element_vertices(r, idx) {
+                                    Some(vertices) if r > 1 => {
+                                        format!("#{} ({} vertices)", idx, vertices.len())
+                                    }
+                                    _ => format!("#{}", idx),
+                                };
+
+                                if ui.selectable_label(selected, label).clicked() {
+                                    selection = if selected { None } else { Some((r, idx)) };
+                                }
+                            }
+                        });
+                }
+            });
+
+            if let Some((r, _)) = selection {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    open_element = ui.button("Open element").clicked();
+                    if r == 1 {
+                        open_vertex_figure = ui.button("Open vertex figure").clicked();
+                    }
+
+                    if ui.button("Clear highlight").clicked() {
+                        selection = None;
+                    }
+                });
+            }
+        });
+
+    // Replaces the polytope by the selected element or its vertex figure.
+    if let Some((r, idx)) = selection {
+        let new_poly = if open_element {
+            poly.flat_element(r, idx)
+                .map(|el| (el, format!("{} #{} of {}", rank_name(r), idx, poly_name.0)))
+        } else if open_vertex_figure {
+            poly.vertex_figure(idx)
+                .map(|fig| (fig, format!("Vertex figure #{} of {}", idx, poly_name.0)))
+        } else {
+            None
+        };
+
+        if let Some((new_poly, name)) = new_poly {
+            *poly = new_poly;
+            poly_name.0 = name;
+            selection = None;
+        }
+    }
+
+    // We only touch the resource if the selection changed.
+    if highlight.selection != selection {
+        highlight.selection = selection;
+    }
+}
+
+/// Rebuilds the highlight mesh whenever the selection changes, or whenever the
+/// polytope is rotated in more than three dimensions. Changing the polytope
+/// clears the selection.
+fn update_highlight(
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut highlight: ResMut<'_, Highlight>,
+    polies: Query<'_, '_, &Concrete>,
+    changed: Query<'_, '_, &Concrete, Changed<Concrete>>,
+    highlight_meshes: Query<'_, '_, &Handle<Mesh>, With<HighlightMesh>>,
+    projection_type: Res<'_, ProjectionType>,
+    rotated: Res<'_, RotatedPolytope>,
+) {
+    let poly_changed = changed.iter().next().is_some();
+    if poly_changed && highlight.selection.is_some() {
+        highlight.selection = None;
+    }
+
+    let rotated_changed = rotated.is_changed() && highlight.selection.is_some();
+    if !poly_changed && !highlight.is_changed() && !rotated_changed {
+        return;
+    }
+
+    if let Some(poly) = rotated.0.as_ref().or_else(|| polies.iter().next()) {
+        let mesh = match highlight.selection {
+            Some((rank, idx)) => poly.element_wireframe(rank, idx, *projection_type),
+            None => poly.wireframe_with_edges(None, *projection_type),
+        };
+
+        for handle in highlight_meshes.iter() {
+            *meshes.get_mut(handle).unwrap() = mesh.clone();
+        }
+    }
+}
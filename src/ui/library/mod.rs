@@ -2,13 +2,18 @@
 
 use std::{
     ffi::{OsStr, OsString},
-    fs, io,
-    path::PathBuf,
+    fs,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
 };
 
-use super::{config::LibPath, main_window::PolyName};
+use super::{
+    config::LibPath,
+    main_window::PolyName,
+    top_panel::{FileDialogToken, FileErrorMessage, ValidityMessage},
+};
 use crate::Concrete;
-use miratope_core::file::FromFile;
+use miratope_core::file::{FileResult, FromFile};
 use special::*;
 
 use bevy::prelude::*;
@@ -20,19 +25,28 @@ mod special;
 /// The plugin that loads the library.
 pub struct LibraryPlugin;
 
+/// The maximum number of file headers that are read each frame. This way,
+/// opening a folder with many files doesn't freeze the application.
+const HEADERS_PER_FRAME: usize = 8;
+
 impl Plugin for LibraryPlugin {
     fn build(&self, app: &mut App) {
         // This must run after the Config resource has been added.
-        let library = Library::new_folder(&LibPath::default());
+        let library = app
+            .world
+            .get_resource::<LibPath>()
+            .and_then(|lib_path| Library::new_folder(lib_path));
 
         // The library must be shown after the top panel, to avoid incorrect
         // positioning.
-        app.insert_resource(library).add_system(
-            show_library
-                .system()
-                .label("show_library")
-                .after("show_top_panel"),
-        );
+        app.insert_resource(library)
+            .add_system(
+                show_library
+                    .system()
+                    .label("show_library")
+                    .after("show_top_panel"),
+            )
+            .add_system(update_library.system().after("show_library"));
     }
 }
 
@@ -110,6 +124,11 @@ pub enum Library {
     File {
         /// The file name.
         name: String,
+
+        /// A short description of the file read from its header, or the
+        /// error that occured while reading it. This is read lazily.
+        #[serde(skip)]
+        header: Option<Result<String, String>>,
     },
 
     /// Any special file in the library.
@@ -136,6 +155,7 @@ impl Library {
                 .unwrap()
                 .to_string_lossy()
                 .into_owned(),
+            header: None,
         }
     }
 
@@ -189,21 +209,28 @@ impl Library {
                 }
             }
 
+            contents.sort_by(|a, b| a.path_name().cmp(b.path_name()));
             Ok(contents)
         }
     }
 
-    /// Shows the library in a given `Ui`, starting from a given path.
-    pub fn show(&mut self, ui: &mut Ui, path: PathBuf) -> ShowResult {
+    /// Shows the library in a given `Ui`, starting from a given path. At most
+    /// `budget` file headers are read, and the budget is decreased accordingly.
+    pub fn show(&mut self, ui: &mut Ui, path: PathBuf, budget: &mut usize) -> ShowResult {
         match self {
             // Shows a collapsing drop-down, and loads the folder in case it's clicked.
             Self::UnloadedFolder { name, .. } => {
+                let contents = Self::folder_contents(&path).unwrap_or_else(|err| {
+                    eprintln!("Could not read folder {}: {}", path.display(), err);
+                    Vec::new()
+                });
+
                 *self = Self::LoadedFolder {
                     name: name.clone(),
-                    contents: Self::folder_contents(&path).unwrap(),
+                    contents,
                 };
 
-                self.show(ui, path)
+                self.show(ui, path, budget)
             }
 
             // Shows a drop-down with all of the files and folders.
@@ -214,7 +241,7 @@ impl Library {
                     for lib in contents.iter_mut() {
                         let mut new_path = path.clone();
                         new_path.push(lib.path_name());
-                        res |= lib.show(ui, new_path);
+                        res |= lib.show(ui, new_path, budget);
                     }
 
                     res
@@ -222,15 +249,27 @@ impl Library {
                 .body_returned
                 .unwrap_or_default(),
 
-            // Shows a button that loads the file if clicked.
-            Self::File { name, .. } => {
-                let label = PathBuf::from(name as &_)
+            // Shows a button that loads the file if clicked. Files whose
+            // header couldn't be read are marked with a warning.
+            Self::File { name, header } => {
+                if header.is_none() && *budget > 0 {
+                    *budget -= 1;
+                    *header = Some(read_header(&path));
+                }
+
+                let stem = PathBuf::from(name as &_)
                     .file_stem()
                     .unwrap()
                     .to_string_lossy()
                     .into_owned();
 
-                if ui.button(label).clicked() {
+                let button = match header {
+                    Some(Ok(summary)) => ui.button(stem).on_hover_text(summary.as_str()),
+                    Some(Err(err)) => ui.button(format!("⚠ {}", stem)).on_hover_text(err.as_str()),
+                    None => ui.button(stem),
+                };
+
+                if button.clicked() {
                     ShowResult::Load(path.into_os_string())
                 } else {
                     ShowResult::None
@@ -243,13 +282,72 @@ impl Library {
     }
 }
 
+/// Reads the header of a file in the library, and returns a short description
+/// of it, or the reason it couldn't be read.
+fn read_header(path: &Path) -> Result<String, String> {
+    let ext = path.extension().and_then(OsStr::to_str).unwrap_or_default();
+    if ext == "ggb" {
+        return Ok("GeoGebra file".to_string());
+    }
+
+    // We only read the file up to the element counts, skipping comments and
+    // empty lines, since it could be huge.
+    let file = fs::File::open(path).map_err(|err| err.to_string())?;
+    let mut lines = BufReader::new(file).lines();
+    let mut next_line = || -> Result<Option<String>, String> {
+        for line in lines.by_ref() {
+            let line = line.map_err(|err| err.to_string())?;
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if !line.is_empty() {
+                return Ok(Some(line.to_string()));
+            }
+        }
+
+        Ok(None)
+    };
+
+    let dim = match next_line()?.as_deref() {
+        Some("OFF") => 3,
+        Some(line) => line
+            .strip_suffix("OFF")
+            .and_then(|dim| dim.parse::<usize>().ok())
+            .ok_or_else(|| "invalid OFF header".to_string())?,
+        None => return Err("empty file".to_string()),
+    };
+
+    if dim == 0 {
+        return Ok("Point".to_string());
+    }
+
+    let vertices = next_line()?
+        .as_deref()
+        .and_then(|line| line.split_whitespace().next())
+        .and_then(|count| count.parse::<usize>().ok())
+        .ok_or_else(|| "missing element counts".to_string())?;
+
+    Ok(format!("{}D, {} vertices", dim, vertices))
+}
+
+/// Loads a file from the library. OFF files are cached as MIR files next to
+/// them, which load much faster the next time they're opened.
+fn load_file(file: &OsString) -> FileResult<'_, Concrete> {
+    if Path::new(file).extension() == Some(OsStr::new("off")) {
+        Concrete::from_off_cached(file)
+    } else {
+        Concrete::from_path(file)
+    }
+}
+
 /// The system that shows the Miratope library.
 fn show_library(
     egui_ctx: Res<'_, EguiContext>,
     mut query: Query<'_, '_, &mut Concrete>,
     mut poly_name: ResMut<'_, PolyName>,
     mut library: ResMut<'_, Option<Library>>,
-    lib_path: Res<'_, LibPath>,
+    mut file_error: ResMut<'_, FileErrorMessage>,
+    mut validity: ResMut<'_, ValidityMessage>,
+    mut lib_path: ResMut<'_, LibPath>,
+    file_dialog: NonSend<'_, FileDialogToken>,
 ) {
     // Shows the polytope library.
     if let Some(library) = library.as_mut() {
@@ -257,20 +355,40 @@ fn show_library(
             .default_width(300.0)
             .max_width(450.0)
             .show(egui_ctx.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    // Changes the folder the library is read from.
+                    if ui.button("Change folder...").clicked() {
+                        if let Some(path) = file_dialog.pick_folder() {
+                            *lib_path = LibPath::new(path);
+                        }
+                    }
+
+                    // Reads the library again from disk.
+                    if ui.button("Rescan").clicked() {
+                        lib_path.set_changed();
+                    }
+                });
+                ui.separator();
+
+                let mut budget = HEADERS_PER_FRAME;
                 egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
-                    match library.show(ui, PathBuf::from(lib_path.as_ref())) {
+                    match library.show(ui, PathBuf::from(&*lib_path), &mut budget) {
                         // No action needs to be taken.
                         ShowResult::None => {}
 
                         // Loads a selected file.
-                        ShowResult::Load(file) => match Concrete::from_path(&file) {
+                        ShowResult::Load(file) => match load_file(&file) {
                             Ok(q) => {
+                                validity.warn(&q);
                                 *query.iter_mut().next().unwrap() = q;
                                 let path_buf = PathBuf::from(file);
                                 let file_name = path_buf.file_name().unwrap().to_str().unwrap();
                                 poly_name.0 = file_name[..file_name.len()-4].into();
+                                poly_name.recognize(&query.iter_mut().next().unwrap());
                             },
-                            Err(err) => eprintln!("File open failed: {}", err),
+                            Err(err) => {
+                                file_error.0 = Some(format!("Could not open {}: {}", file.to_string_lossy(), err))
+                            }
                         },
 
                         // Loads a special polytope.
@@ -284,3 +402,13 @@ fn show_library(
             });
     }
 }
+
+/// Reloads the library whenever its path changes.
+fn update_library(lib_path: Res<'_, LibPath>, mut library: ResMut<'_, Option<Library>>) {
+    if lib_path.is_changed() && !lib_path.is_added() {
+        *library = Library::new_folder(&*lib_path);
+        if library.is_none() {
+            eprintln!("Could not find the library folder.");
+        }
+    }
+}
@@ -10,6 +10,51 @@ use miratope_core::Polytope;
 
 use super::ShowResult;
 
+/// The names of the simplices of ranks 0 through 7.
+const SIMPLEX_NAMES: [&str; 8] = [
+    "point",
+    "dyad",
+    "triangle",
+    "tetrahedron",
+    "pentachoron",
+    "hexateron",
+    "heptapeton",
+    "octaexon",
+];
+
+/// The names of the hypercubes of ranks 0 through 7.
+const HYPERCUBE_NAMES: [&str; 8] = [
+    "point",
+    "dyad",
+    "square",
+    "cube",
+    "tesseract",
+    "penteract",
+    "hexeract",
+    "hepteract",
+];
+
+/// The names of the orthoplices of ranks 0 through 7.
+const ORTHOPLEX_NAMES: [&str; 8] = [
+    "point",
+    "dyad",
+    "square",
+    "octahedron",
+    "hexadecachoron",
+    "triacontaditeron",
+    "hexacontatetrapeton",
+    "hecatonicosoctaexon",
+];
+
+/// Returns the name of the polytope of a given rank in a family of regular
+/// polytopes, falling back to a generic name for higher ranks.
+fn regular_name(names: &[&str], rank: isize, family: &str) -> String {
+    match usize::try_from(rank).ok().and_then(|r| names.get(r)) {
+        Some(name) => name.to_string(),
+        None => format!("{}-{}", rank, family),
+    }
+}
+
 /// Represents any of the special polytopes in Miratope's library, namely those
 /// families that are generated by code.
 ///
@@ -107,7 +152,7 @@ impl SpecialLibrary {
                     );
 
                     // Turning number.
-                    let max_n = *n * 2 / 3;
+                    let max_n = (*n * 2 - 1) / 3;
                     ui.label("d:");
                     ui.add(egui::DragValue::new(d).speed(0.03).clamp_range(1..=max_n));
 
@@ -250,19 +295,19 @@ impl SpecialLibrary {
             // Loads a simplex with a given rank.
             Self::Simplex(rank) => (
                 Concrete::simplex((rank + 1) as usize),
-                format!("{}-simplex", rank)
+                regular_name(&SIMPLEX_NAMES, rank, "simplex")
             ),
 
             // Loads a hypercube with a given rank.
             Self::Hypercube(rank) => (
                 Concrete::hypercube((rank + 1) as usize),
-                format!("{}-cube", rank)
+                regular_name(&HYPERCUBE_NAMES, rank, "cube")
             ),
 
             // Loads an orthoplex with a given rank.
             Self::Orthoplex(rank) => (
                 Concrete::orthoplex((rank + 1) as usize),
-                format!("{}-orthoplex", rank)
+                regular_name(&ORTHOPLEX_NAMES, rank, "orthoplex")
             ),
         }
     }
@@ -1,8 +1,9 @@
 //! The systems that update the main window.
 
 use super::right_panel::ElementTypesRes;
-use super::{camera::ProjectionType, top_panel::SectionState};
-use crate::mesh::Renderable;
+use super::{camera::ProjectionType, elements::HighlightMesh, top_panel::SectionState};
+use crate::mesh::{update_mesh, EdgeColoring, Renderable};
+use crate::no_cull_pipeline::{CULL_PIPELINE_HANDLE, NO_CULL_PIPELINE_HANDLE};
 use crate::Concrete;
 
 use bevy::prelude::*;
@@ -18,11 +19,25 @@ impl Plugin for MainWindowPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_to_stage(CoreStage::PreUpdate, update_visible.system())
             .add_system(update_scale_factor.system())
-            .add_system_to_stage(CoreStage::PostUpdate, update_changed_polytopes.system())
-            .init_resource::<PolyName>();
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_changed_polytopes.system().label("update_polytopes"),
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, update_edge_coloring.system())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_backface_culling.system().after("update_polytopes"),
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, update_title.system())
+            .init_resource::<PolyName>()
+            .init_resource::<BackfaceCulling>();
     }
 }
 
+/// The maximum number of characters of the polytope name shown in the title
+/// bar. Longer names are elided.
+const MAX_TITLE_LEN: usize = 60;
+
 pub struct PolyName(pub String);
 
 impl Default for PolyName {
@@ -31,10 +46,24 @@ impl Default for PolyName {
     }
 }
 
+impl PolyName {
+    /// Renames the polytope after the regular polytope it is, if any.
+    pub fn recognize(&mut self, p: &Concrete) {
+        if let Some(name) = p.regular_name() {
+            self.0 = name;
+        }
+    }
+}
+
+/// Stores whether the back faces of polyhedra are hidden. This only applies
+/// to polyhedra whose faces can be consistently oriented.
+#[derive(Default)]
+pub struct BackfaceCulling(pub bool);
+
 pub fn update_visible(
     keyboard: Res<'_, Input<KeyCode>>,
     mut polies_vis: Query<'_, '_, &mut Visible, With<Concrete>>,
-    mut wfs_vis: Query<'_, '_, &mut Visible, Without<Concrete>>,
+    mut wfs_vis: Query<'_, '_, &mut Visible, (Without<Concrete>, Without<HighlightMesh>)>,
 ) {
     if keyboard.just_pressed(KeyCode::V) {
         if let Some(mut visible) = polies_vis.iter_mut().next() {
@@ -62,13 +91,12 @@ pub fn update_scale_factor(mut egui_settings: ResMut<'_, EguiSettings>, windows:
 pub fn update_changed_polytopes(
     mut meshes: ResMut<'_, Assets<Mesh>>,
     mut polies: Query<'_, '_, (&mut Concrete, &Handle<Mesh>, &Children), Changed<Concrete>>,
-    wfs: Query<'_, '_, &Handle<Mesh>, Without<Concrete>>,
-    mut windows: ResMut<'_, Windows>,
+    wfs: Query<'_, '_, &Handle<Mesh>, (Without<Concrete>, Without<HighlightMesh>)>,
     mut section_state: ResMut<'_, SectionState>,
     mut element_types: ResMut<'_, ElementTypesRes>,
-    name: Res<'_, PolyName>,
 
     orthogonal: Res<'_, ProjectionType>,
+    edge_coloring: Res<'_, EdgeColoring>,
 ) {
     for (mut poly, mesh_handle, children) in polies.iter_mut() {
         poly.untangle_faces();
@@ -82,12 +110,15 @@ pub fn update_changed_polytopes(
             element_types.main_updating = false;
         }
 
-        *meshes.get_mut(mesh_handle).unwrap() = poly.mesh(*orthogonal);
+        update_mesh(meshes.get_mut(mesh_handle).unwrap(), poly.mesh(*orthogonal));
 
         // Updates all wireframes.
         for child in children.iter() {
             if let Ok(wf_handle) = wfs.get_component::<Handle<Mesh>>(*child) {
-                *meshes.get_mut(wf_handle).unwrap() = poly.wireframe(*orthogonal);
+                update_mesh(
+                    meshes.get_mut(wf_handle).unwrap(),
+                    poly.wireframe(*orthogonal, &edge_coloring),
+                );
             }
         }
 
@@ -95,11 +126,75 @@ pub fn update_changed_polytopes(
         if !section_state.is_changed() {
             section_state.close();
         }
+    }
+}
+
+/// Draws the polytopes with backface culling whenever it's enabled and their
+/// faces can be oriented, and without it otherwise.
+pub fn update_backface_culling(
+    culling: Res<'_, BackfaceCulling>,
+    mut polies: Query<'_, '_, (&Concrete, &mut RenderPipelines)>,
+    changed: Query<'_, '_, (), Changed<Concrete>>,
+) {
+    if !culling.is_changed() && changed.iter().next().is_none() {
+        return;
+    }
+
+    for (poly, mut render_pipelines) in polies.iter_mut() {
+        let handle = if culling.0 && poly.dim() == Some(3) && poly.oriented_faces().is_some() {
+            CULL_PIPELINE_HANDLE
+        } else {
+            NO_CULL_PIPELINE_HANDLE
+        };
+
+        for pipeline in render_pipelines.pipelines.iter_mut() {
+            if pipeline.pipeline.id != handle.id {
+                pipeline.pipeline = handle.typed();
+            }
+        }
+    }
+}
+
+/// Rebuilds the wireframes whenever the way their edges are colored changes.
+pub fn update_edge_coloring(
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    polies: Query<'_, '_, (&Concrete, &Children)>,
+    wfs: Query<'_, '_, &Handle<Mesh>, (Without<Concrete>, Without<HighlightMesh>)>,
+    orthogonal: Res<'_, ProjectionType>,
+    edge_coloring: Res<'_, EdgeColoring>,
+) {
+    if !edge_coloring.is_changed() {
+        return;
+    }
+
+    for (poly, children) in polies.iter() {
+        for child in children.iter() {
+            if let Ok(wf_handle) = wfs.get(*child) {
+                update_mesh(
+                    meshes.get_mut(wf_handle).unwrap(),
+                    poly.wireframe(*orthogonal, &edge_coloring),
+                );
+            }
+        }
+    }
+}
+
+/// Returns the window title for a polytope with a given name, eliding the
+/// name if it's too long.
+fn window_title(name: &str) -> String {
+    let name = match name.char_indices().nth(MAX_TITLE_LEN) {
+        Some((idx, _)) => format!("{}…", &name[..idx]),
+        None => name.to_string(),
+    };
 
-        windows
-            .get_primary_mut()
-            .unwrap()
-            .set_title(format!("{} - Miratope v{}", name.0, env!("CARGO_PKG_VERSION")));
+    format!("{} - Miratope v{}", name, env!("CARGO_PKG_VERSION"))
+}
 
+/// Updates the window title whenever the polytope is renamed.
+pub fn update_title(name: Res<'_, PolyName>, mut windows: ResMut<'_, Windows>) {
+    if name.is_changed() {
+        if let Some(window) = windows.get_primary_mut() {
+            window.set_title(window_title(&name.0));
+        }
     }
 }
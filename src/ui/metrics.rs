@@ -0,0 +1,216 @@
+//! Contains the window that shows some basic measurements of the loaded
+//! polytope.
+
+use super::main_window::PolyName;
+use crate::{Concrete, Float};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{
+    abs::Ranked,
+    conc::{element_types::EL_NAMES, ConcretePolytope},
+    Polytope,
+};
+
+/// The plugin in charge of the metrics window.
+pub struct MetricsPlugin;
+
+impl Plugin for MetricsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Metrics>()
+            .init_resource::<ShowMetrics>()
+            .add_system(show_metrics.system())
+            .add_system_to_stage(CoreStage::PostUpdate, update_metrics.system());
+    }
+}
+
+/// Stores whether the metrics window is shown.
+#[derive(Default)]
+pub struct ShowMetrics(pub bool);
+
+/// Some basic measurements of the loaded polytope.
+pub struct Metrics {
+    /// Whether the polytope has changed since these were computed.
+    stale: bool,
+
+    /// The number of elements of each rank.
+    el_counts: Vec<usize>,
+
+    /// The circumradius, if the polytope is circumscribable.
+    circumradius: Option<Float>,
+
+    /// The least and greatest edge lengths, if there are any edges.
+    edge_lengths: Option<(Float, Float)>,
+
+    /// The volume, if it's defined.
+    volume: Option<Float>,
+
+    /// The sum of the volumes of the facets, if it's defined.
+    surface_area: Option<Float>,
+
+    /// The order of the symmetry group, and whether it contains the central
+    /// inversion, if these could be computed.
+    symmetry: Option<(usize, bool)>,
+
+    /// The classes of dihedral angles in radians with their multiplicities,
+    /// and the number of ridges that were skipped.
+    dihedral_angles: (Vec<(usize, Float)>, usize),
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            stale: true,
+            el_counts: Vec::new(),
+            circumradius: None,
+            edge_lengths: None,
+            volume: None,
+            surface_area: None,
+            symmetry: None,
+            dihedral_angles: (Vec::new(), 0),
+        }
+    }
+}
+
+impl Metrics {
+    /// Computes the metrics of a polytope.
+    fn new(poly: &Concrete) -> Self {
+        let mut poly = poly.clone();
+        poly.element_sort();
+
+        let edge_lengths = poly.edge_lengths().into_iter().fold(None, |minmax, len| {
+            Some(match minmax {
+                Some((min, max)) => (Float::min(min, len), Float::max(max, len)),
+                None => (len, len),
+            })
+        });
+
+        Self {
+            stale: false,
+            el_counts: poly.el_count_iter().collect(),
+            circumradius: poly.circumsphere().map(|sphere| sphere.radius()),
+            edge_lengths,
+            volume: poly.volume(),
+            surface_area: poly.surface_area(),
+            symmetry: if poly.rank() > 2 {
+                poly.symmetry_order()
+            } else {
+                None
+            },
+            dihedral_angles: poly.dihedral_angle_classes(),
+        }
+    }
+}
+
+/// Formats an optional value for the metrics window.
+fn fmt_option(value: Option<Float>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "undefined".to_string(),
+    }
+}
+
+/// Marks the metrics as outdated whenever the polytope changes.
+fn update_metrics(
+    query: Query<'_, '_, &Concrete, Changed<Concrete>>,
+    mut metrics: ResMut<'_, Metrics>,
+) {
+    if query.iter().next().is_some() {
+        metrics.stale = true;
+    }
+}
+
+/// Shows the metrics window, recomputing the metrics if they're outdated. We
+/// only do this while the window is open, since computing the volume or the
+/// symmetry group can be expensive.
+fn show_metrics(
+    egui_ctx: Res<'_, EguiContext>,
+    query: Query<'_, '_, &Concrete>,
+    mut metrics: ResMut<'_, Metrics>,
+    mut show_metrics: ResMut<'_, ShowMetrics>,
+    poly_name: Res<'_, PolyName>,
+) {
+    if !show_metrics.0 {
+        return;
+    }
+
+    if metrics.stale {
+        if let Some(poly) = query.iter().next() {
+            *metrics = Metrics::new(poly);
+        }
+    }
+
+    egui::Window::new("Metrics")
+        .open(&mut show_metrics.0)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            // The window title may elide the name, so we show it in full.
+            egui::CollapsingHeader::new("Name")
+                .default_open(true)
+                .show(ui, |ui| {
+                    ui.add(egui::Label::new(&poly_name.0).wrap(true));
+                });
+
+            ui.separator();
+            ui.heading("Element counts");
+            for (r, count) in metrics.el_counts.iter().enumerate().skip(1) {
+                if r + 1 == metrics.el_counts.len() && r > 1 {
+                    break;
+                }
+
+                let name = match EL_NAMES.get(r) {
+                    Some(name) => name.to_string(),
+                    None => format!("{}-elements", r - 1),
+                };
+                ui.label(format!("{}: {}", name, count));
+            }
+
+            ui.separator();
+            ui.label(format!(
+                "Circumradius: {}",
+                fmt_option(metrics.circumradius)
+            ));
+
+            match metrics.edge_lengths {
+                Some((min, max)) => {
+                    ui.label(format!("Edge lengths: {} to {}", min, max));
+                }
+                None => {
+                    ui.label("Edge lengths: undefined");
+                }
+            }
+
+            ui.label(format!("Volume: {}", fmt_option(metrics.volume)));
+            ui.label(format!(
+                "Surface area: {}",
+                fmt_option(metrics.surface_area)
+            ));
+
+            ui.separator();
+            match metrics.symmetry {
+                Some((order, central_inv)) => {
+                    ui.label(format!("Symmetry order: {}", order));
+                    ui.label(format!(
+                        "Central inversion: {}",
+                        if central_inv { "yes" } else { "no" }
+                    ));
+                }
+                None => {
+                    ui.label("Symmetry order: undefined");
+                }
+            }
+
+            ui.separator();
+            ui.heading("Dihedral angles");
+            let (classes, skipped) = &metrics.dihedral_angles;
+            if classes.is_empty() {
+                ui.label("undefined");
+            }
+            for (count, angle) in classes {
+                ui.label(format!("{:.4}° × {}", angle.to_degrees(), count));
+            }
+            if *skipped > 0 {
+                ui.label(format!("Skipped {} degenerate ridges", skipped));
+            }
+        });
+}
@@ -4,15 +4,20 @@ use crate::{Point, EPS};
 use approx::abs_diff_eq;
 use bevy_egui::egui::{self, Ui, Widget};
 
+pub mod animation;
 pub mod camera;
 pub mod config;
+pub mod elements;
 pub mod library;
 pub mod main_window;
 pub mod memory;
+pub mod metrics;
+pub mod picking;
 pub mod wiki;
 pub mod window;
 pub mod top_panel;
 pub mod right_panel;
+pub mod truncation;
 
 /// All of the plugins specific to Miratope.
 pub struct MiratopePlugins;
@@ -25,6 +30,11 @@ impl bevy::prelude::PluginGroup for MiratopePlugins {
             .add(window::WindowPlugin)
             .add(library::LibraryPlugin)
             .add(main_window::MainWindowPlugin)
+            .add(metrics::MetricsPlugin)
+            .add(elements::ElementsPlugin)
+            .add(animation::AnimationPlugin)
+            .add(picking::PickingPlugin)
+            .add(truncation::TruncationPlugin)
             .add(top_panel::TopPanelPlugin)
             .add(right_panel::RightPanelPlugin);
     }
@@ -0,0 +1,181 @@
+//! Contains the systems that pick the vertex or edge of the loaded polytope
+//! under the cursor.
+
+use super::{animation::RotatedPolytope, camera::ProjectionType, elements::Highlight};
+use crate::{mesh::vertex_coords, Concrete, Float, Point};
+
+use bevy::{input::mouse::MouseMotion, prelude::*, render::camera::Camera};
+use bevy_egui::EguiContext;
+use miratope_core::{abs::Ranked, conc::ConcretePolytope, geometry::Segment};
+
+/// The greatest distance in pixels from the cursor to an element for it to be
+/// picked.
+const PICK_RADIUS: Float = 8.0;
+
+/// The plugin in charge of picking elements on the viewport.
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Selection>()
+            // We pick after the windows have been shown, so that we know
+            // whether the click was meant for them.
+            .add_system(pick_element.system().after("show_windows"))
+            .add_system_to_stage(CoreStage::PostUpdate, clear_selection.system());
+    }
+}
+
+/// The element last picked on the viewport, if any.
+#[derive(Clone, Copy, Default)]
+pub struct Selection {
+    /// The rank and index of the picked element. Only vertices and edges can
+    /// be picked, so the rank is either 1 or 2.
+    pub element: Option<(usize, usize)>,
+}
+
+impl Selection {
+    /// Returns the rank of the picked element.
+    pub fn rank(&self) -> Option<usize> {
+        self.element.map(|(rank, _)| rank)
+    }
+
+    /// Returns the index of the picked element.
+    pub fn index(&self) -> Option<usize> {
+        self.element.map(|(_, idx)| idx)
+    }
+}
+
+/// Converts a position on the screen into a [`Point`].
+fn screen_point(p: Vec2) -> Point {
+    Point::from_vec(vec![p.x as Float, p.y as Float])
+}
+
+/// Finds the vertex or edge of a polytope nearest to a point on the screen,
+/// given the screen positions of its vertices. Vertices take precedence over
+/// the edges that contain them. Returns `None` if nothing is within
+/// [`PICK_RADIUS`] of the point.
+fn nearest_element(
+    poly: &Concrete,
+    screen: &[Option<Point>],
+    cursor: &Point,
+) -> Option<(usize, usize)> {
+    let mut nearest = None;
+    let mut nearest_dist = PICK_RADIUS;
+
+    for (idx, p) in screen.iter().enumerate() {
+        if let Some(p) = p {
+            let dist = (p - cursor).norm();
+            if dist <= nearest_dist {
+                nearest = Some((1, idx));
+                nearest_dist = dist;
+            }
+        }
+    }
+
+    if nearest.is_some() {
+        return nearest;
+    }
+
+    if let Some(edges) = poly.get_element_list(2) {
+        for (idx, edge) in edges.iter().enumerate() {
+            if let (Some(p0), Some(p1)) = (&screen[edge.subs[0]], &screen[edge.subs[1]]) {
+                let dist = Segment(p0, p1).distance(cursor);
+                if dist <= nearest_dist {
+                    nearest = Some((2, idx));
+                    nearest_dist = dist;
+                }
+            }
+        }
+    }
+
+    nearest
+}
+
+/// Picks the vertex or edge under the cursor whenever the viewport is clicked
+/// without dragging, and highlights it. Clicking on empty space clears the
+/// selection.
+///
+/// A click casts a ray from the camera through the cursor. Rather than
+/// intersecting it with the wireframe, we project the vertices onto the
+/// screen, where the ray becomes a single point, and measure the distance from
+/// it to every vertex and edge. While the polytope is rotated in more than
+/// three dimensions, we use the rotated vertices that are being shown.
+#[allow(clippy::too_many_arguments)]
+fn pick_element(
+    mouse_button: Res<'_, Input<MouseButton>>,
+    mut mouse_move: EventReader<'_, '_, MouseMotion>,
+    mut dragged: Local<'_, bool>,
+    windows: Res<'_, Windows>,
+    egui_ctx: Res<'_, EguiContext>,
+    projection_type: Res<'_, ProjectionType>,
+    polies: Query<'_, '_, (&Concrete, &GlobalTransform)>,
+    cameras: Query<'_, '_, (&Camera, &GlobalTransform)>,
+    mut selection: ResMut<'_, Selection>,
+    mut highlight: ResMut<'_, Highlight>,
+    rotated: Res<'_, RotatedPolytope>,
+) {
+    // A click that moves the mouse rotates the camera instead.
+    if mouse_button.just_pressed(MouseButton::Left) {
+        *dragged = false;
+    }
+    if mouse_move.iter().next().is_some() && mouse_button.pressed(MouseButton::Left) {
+        *dragged = true;
+    }
+    if !mouse_button.just_released(MouseButton::Left)
+        || *dragged
+        || egui_ctx.ctx().wants_pointer_input()
+    {
+        return;
+    }
+
+    let cursor = match windows.get_primary().and_then(Window::cursor_position) {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    let (camera, cam_gtf) = match cameras.iter().next() {
+        Some(camera) => camera,
+        None => return,
+    };
+
+    let (poly, poly_gtf) = match polies.iter().next() {
+        Some(poly) => poly,
+        None => return,
+    };
+
+    // The positions of the vertices on the screen, or `None` for those behind
+    // the camera.
+    let vertices = rotated.0.as_ref().map_or(poly.vertices(), |r| r.vertices());
+    let screen: Vec<_> = vertex_coords(poly, vertices.iter(), *projection_type)
+        .into_iter()
+        .map(|v| {
+            camera
+                .world_to_screen(&windows, cam_gtf, poly_gtf.mul_vec3(Vec3::from(v)))
+                .map(screen_point)
+        })
+        .collect();
+
+    let element = if poly.rank() > 1 {
+        nearest_element(poly, &screen, &screen_point(cursor))
+    } else {
+        None
+    };
+
+    // We only touch the resources if the selection changed.
+    if selection.element != element {
+        selection.element = element;
+    }
+    if highlight.selection != element {
+        highlight.selection = element;
+    }
+}
+
+/// Clears the selection whenever the polytope changes.
+fn clear_selection(
+    changed: Query<'_, '_, &Concrete, Changed<Concrete>>,
+    mut selection: ResMut<'_, Selection>,
+) {
+    if changed.iter().next().is_some() && selection.element.is_some() {
+        selection.element = None;
+    }
+}
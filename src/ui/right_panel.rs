@@ -1,6 +1,6 @@
 //! Contains all code related to the right side panel.
 
-use crate::Concrete;
+use crate::{Concrete, Float};
 
 use bevy::prelude::*;
 use bevy_egui::{
@@ -27,7 +27,7 @@ pub struct ElementTypeWithData {
     fig_facets: usize,
 
     /// The circumradius of the element, or distance from the origin if it's a vertex.
-    radius: Option<f64>,
+    radius: Option<Float>,
 }
 
 #[derive(Clone)]
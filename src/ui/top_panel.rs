@@ -2,12 +2,15 @@
 
 use std::path::PathBuf;
 
-use super::{camera::ProjectionType, memory::Memory, window::{Window, *}, UnitPointWidget, main_window::PolyName};
-use crate::{Concrete, Float, Hyperplane, Point, Vector};
+use super::{animation::ShowAnimation, camera::{CameraProjection, ProjectionType}, config::{ShowPreferences, WireframeColors}, elements::ShowElements, memory::Memory, metrics::ShowMetrics, truncation::DepthTruncation, window::{Window, *}, UnitPointWidget, main_window::{BackfaceCulling, PolyName}};
+use crate::{png::{render_png, PngOptions}, svg::{render_svg, Projection, SvgOptions}, Concrete, Float, Hyperplane, Point, Vector};
 
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    render::camera::{Camera, OrthographicProjection, PerspectiveProjection},
+};
 use bevy_egui::{egui::{self, menu, Ui}, EguiContext};
-use miratope_core::{conc::{ConcretePolytope, faceting::GroupEnum, symmetry::Vertices}, file::FromFile, float::Float as Float2, Polytope, abs::Ranked};
+use miratope_core::{conc::{ConcretePolytope, faceting::GroupEnum, section::SectionCache, symmetry::Vertices}, file::FromFile, float::Float as Float2, Polytope, abs::Ranked};
 
 /// The plugin in charge of everything on the top panel.
 pub struct TopPanelPlugin;
@@ -21,8 +24,17 @@ impl Plugin for TopPanelPlugin {
             .init_resource::<ShowMemory>()
             .init_resource::<ShowHelp>()
             .init_resource::<ExportMemory>()
+            .init_resource::<FileErrorMessage>()
+            .init_resource::<ValidityMessage>()
+            .init_resource::<SvgOptions>()
+            .init_resource::<PngOptions>()
             .init_non_send_resource::<FileDialogToken>()
             .add_system(file_dialog.system())
+            .add_system(show_file_error.system())
+            .add_system(show_validity.system())
+            .add_system(show_path_box.system())
+            // The sweep must advance before the cross-section is updated.
+            .add_system(sweep_cross_section.system().before("show_top_panel"))
             // Windows must be the first thing shown.
             .add_system(
                 show_top_panel
@@ -57,6 +69,22 @@ pub enum SectionState {
 
         /// Whether to update the polytope. This is a bodge.
         update: bool,
+
+        /// Whether the last slice is being swept through the polytope.
+        sweep: bool,
+
+        /// The speed of the sweep, in units per second.
+        sweep_speed: Float,
+
+        /// The range the sweep moves through, computed when it starts.
+        sweep_range: (Float, Float),
+
+        /// Whether the sweep is moving towards the end of its range.
+        sweep_forward: bool,
+
+        /// The extents of the edges of the original polytope along the
+        /// direction of the first slice, so that moving it is cheaper.
+        cache: Option<SectionCache>,
     },
 
     /// The view is inactive.
@@ -90,7 +118,7 @@ impl SectionState {
 		}
     }
 
-    pub fn open(&mut self, original_polytope: Concrete, name: String, minmax: Vec<(f64, f64)>) {
+    pub fn open(&mut self, original_polytope: Concrete, name: String, minmax: Vec<(Float, Float)>) {
         *self = SectionState::Active {
             original_polytope,
             original_name: name,
@@ -99,6 +127,11 @@ impl SectionState {
             flatten: true,
             lock: false,
             update: false,
+            sweep: false,
+            sweep_speed: 0.5,
+            sweep_range: (0.0, 0.0),
+            sweep_forward: true,
+            cache: None,
         }
     }
 }
@@ -113,6 +146,11 @@ impl Clone for SectionState {
 				flatten,
 				lock,
                 update,
+                sweep,
+                sweep_speed,
+                sweep_range,
+                sweep_forward,
+                cache,
 			} = self{
 				
 			SectionState::Active{
@@ -123,6 +161,11 @@ impl Clone for SectionState {
 				flatten: *flatten,
 				lock: *lock,
                 update: *update,
+                sweep: *sweep,
+                sweep_speed: *sweep_speed,
+                sweep_range: *sweep_range,
+                sweep_forward: *sweep_forward,
+                cache: cache.clone(),
 			}
 		}
 		else
@@ -197,6 +240,11 @@ impl FileDialogToken {
     fn save_file(&self, name: &str) -> Option<PathBuf> {
         Self::new_file_dialog().set_file_name(name).save_file()
     }
+
+    /// Returns the path given by a folder dialog.
+    pub fn pick_folder(&self) -> Option<PathBuf> {
+        rfd::FileDialog::new().pick_folder()
+    }
 }
 
 /// The type of file dialog we're showing.
@@ -207,8 +255,20 @@ enum FileDialogMode {
     /// We're showing a file dialog to open a file.
     Open,
 
+    /// We're opening a file at a given path, without showing a dialog.
+    OpenPath(PathBuf),
+
     /// We're showing a file dialog to save a file.
     Save,
+
+    /// We're showing a file dialog to export a file into GeoGebra.
+    ExportGgb,
+
+    /// We're showing a file dialog to export the wireframe into SVG.
+    ExportSvg,
+
+    /// We're showing a file dialog to export the view into PNG.
+    ExportPng,
 }
 
 /// The file dialog is disabled by default.
@@ -218,6 +278,9 @@ impl Default for FileDialogMode {
     }
 }
 
+/// The number of recently opened files that are remembered.
+const MAX_RECENT_FILES: usize = 5;
+
 /// The state the file dialog is in.
 #[derive(Default)]
 pub struct FileDialogState {
@@ -226,6 +289,13 @@ pub struct FileDialogState {
 
     /// The name of the file to load or save, if any.
     name: Option<String>,
+
+    /// The paths of the most recently opened files, from newest to oldest.
+    recent: Vec<PathBuf>,
+
+    /// The contents of the text box to type a path into, if it's shown. This
+    /// is used on platforms without native file dialogs.
+    path_box: Option<String>,
 }
 
 impl FileDialogState {
@@ -234,6 +304,30 @@ impl FileDialogState {
         self.mode = FileDialogMode::Open;
     }
 
+    /// Changes the file dialog mode to [`FileDialogMode::OpenPath`], so that
+    /// the file at the given path is opened.
+    pub fn open_path(&mut self, path: PathBuf) {
+        self.mode = FileDialogMode::OpenPath(path);
+    }
+
+    /// Shows the text box to type the path of a file to open.
+    pub fn open_path_box(&mut self) {
+        self.path_box = Some(String::new());
+    }
+
+    /// Returns the paths of the most recently opened files, from newest to
+    /// oldest.
+    pub fn recent(&self) -> &[PathBuf] {
+        &self.recent
+    }
+
+    /// Adds a path to the list of recently opened files.
+    fn add_recent(&mut self, path: PathBuf) {
+        self.recent.retain(|recent| recent != &path);
+        self.recent.insert(0, path);
+        self.recent.truncate(MAX_RECENT_FILES);
+    }
+
     /// Changes the file dialog mode to [`FileDialogMode::Save`], and loads the
     /// name of the file.
     pub fn save(&mut self, name: String) {
@@ -241,21 +335,269 @@ impl FileDialogState {
         self.name = Some(name);
     }
 
+    /// Changes the file dialog mode to [`FileDialogMode::ExportGgb`], and
+    /// loads the name of the file.
+    pub fn export_ggb(&mut self, name: String) {
+        self.mode = FileDialogMode::ExportGgb;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::ExportSvg`], and
+    /// loads the name of the file.
+    pub fn export_svg(&mut self, name: String) {
+        self.mode = FileDialogMode::ExportSvg;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::ExportPng`], and
+    /// loads the name of the file.
+    pub fn export_png(&mut self, name: String) {
+        self.mode = FileDialogMode::ExportPng;
+        self.name = Some(name);
+    }
+
     /// Gets the name of the file dialog.
     pub fn unwrap_name(&self) -> &str {
         self.name.as_ref().unwrap()
     }
 }
 
+/// Stores the message of the last error while opening or exporting a file,
+/// until the user dismisses it.
+#[derive(Default)]
+pub struct FileErrorMessage(pub Option<String>);
+
+/// The system that shows the last error while opening or exporting a file in a
+/// popup.
+pub fn show_file_error(
+    egui_ctx: Res<'_, EguiContext>,
+    mut file_error: ResMut<'_, FileErrorMessage>,
+) {
+    let mut open = true;
+
+    if let Some(message) = &file_error.0 {
+        egui::Window::new("Error")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(egui_ctx.ctx(), |ui| {
+                ui.label(message);
+            });
+    }
+
+    if !open {
+        file_error.0 = None;
+    }
+}
+
+/// Stores the result of the last validity check of a polytope, until the user
+/// dismisses it.
+#[derive(Default)]
+pub struct ValidityMessage(pub Option<String>);
+
+impl ValidityMessage {
+    /// Checks whether a polytope satisfies every axiom of an abstract
+    /// polytope, and stores a message with the result.
+    pub fn validate(&mut self, p: &Concrete) {
+        self.0 = Some(match p.is_polytope() {
+            Ok(()) => "The polytope is valid.".to_owned(),
+            Err(err) => format!("The polytope is not valid. {}.", err),
+        });
+    }
+
+    /// Runs the cheap validity checks on a polytope, and stores a warning
+    /// only if it fails them. Strong connectivity is only checked on demand,
+    /// through [`Self::validate`].
+    pub fn warn(&mut self, p: &Concrete) {
+        if let Err(err) = p.is_valid() {
+            self.0 = Some(format!(
+                "The polytope was loaded, but it is not valid. Some operations may fail. {}.",
+                err
+            ));
+        }
+    }
+}
+
+/// The system that shows the result of the last validity check in a popup.
+pub fn show_validity(egui_ctx: Res<'_, EguiContext>, mut validity: ResMut<'_, ValidityMessage>) {
+    let mut open = true;
+
+    if let Some(message) = &validity.0 {
+        egui::Window::new("Validity")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(egui_ctx.ctx(), |ui| {
+                ui.label(message);
+            });
+    }
+
+    if !open {
+        validity.0 = None;
+    }
+}
+
+/// The system that shows the text box to type the path of a file to open.
+pub fn show_path_box(
+    egui_ctx: Res<'_, EguiContext>,
+    mut file_dialog_state: ResMut<'_, FileDialogState>,
+) {
+    // We check this first so that the state isn't marked as changed.
+    if file_dialog_state.path_box.is_none() {
+        return;
+    }
+
+    let mut open = true;
+    let mut path = None;
+
+    if let Some(text) = &mut file_dialog_state.path_box {
+        egui::Window::new("Open path")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(egui_ctx.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(text);
+
+                    if ui.button("Open").clicked() {
+                        path = Some(PathBuf::from(text.trim()));
+                    }
+                });
+            });
+    }
+
+    if let Some(path) = path {
+        file_dialog_state.open_path(path);
+        file_dialog_state.path_box = None;
+    } else if !open {
+        file_dialog_state.path_box = None;
+    }
+}
+
+/// Toggles the cross-section view of a polytope. When the view is closed, the
+/// original polytope is restored.
+fn toggle_cross_section(
+    p: &mut Concrete,
+    poly_name: &mut PolyName,
+    section_state: &mut SectionState,
+    section_direction: &mut Vec<SectionDirection>,
+) {
+    match section_state {
+        // The view is active, but will be inactivated.
+        SectionState::Active {
+            original_polytope,
+            original_name,
+            ..
+        } => {
+            *p = original_polytope.clone();
+            poly_name.0 = original_name.clone();
+            section_state.close();
+        }
+
+        // The view is inactive, but will be activated.
+        SectionState::Inactive => {
+            // Cannot slice a polygon or lower.
+            if p.rank() < 4 {
+                println!("Slicing polytopes of rank less than 3 is not supported!");
+            } else {
+                p.flatten();
+
+                // The default direction is in the last coordinate axis.
+                let dim = p.dim_or();
+                let mut direction = Vector::zeros(dim);
+                if dim > 0 {
+                    direction[dim - 1] = 1.0;
+                }
+
+                let minmax = p.minmax(direction.clone()).unwrap_or((-1.0, 1.0));
+                let original_polytope = p.clone();
+
+                section_state.open(original_polytope, poly_name.0.clone(), vec![minmax]);
+                section_direction.clear();
+                section_direction.push(SectionDirection(direction));
+            }
+        }
+    }
+}
+
+/// Opens the file at a given path, replacing the polytope on screen. Any error
+/// is shown in a popup, as is a warning if the polytope isn't valid.
+fn open_file(
+    path: PathBuf,
+    p: &mut Concrete,
+    name: &mut PolyName,
+    (file_error, validity): (&mut FileErrorMessage, &mut ValidityMessage),
+    file_dialog_state: &mut FileDialogState,
+) {
+    match Concrete::from_path(&path) {
+        Ok(q) => {
+            validity.warn(&q);
+            *p = q;
+            if let Some(file_name) = path.file_stem() {
+                name.0 = file_name.to_string_lossy().into_owned();
+            }
+            name.recognize(p);
+            file_dialog_state.add_recent(path);
+        }
+        Err(err) => {
+            file_error.0 = Some(format!("Could not open {}: {}", path.display(), err))
+        }
+    }
+}
+
+/// The cameras, together with whichever projection each of them uses.
+type CameraProjections<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static GlobalTransform,
+        Option<&'static PerspectiveProjection>,
+        Option<&'static OrthographicProjection>,
+    ),
+    With<Camera>,
+>;
+
+/// Returns the transform of the camera, together with the projection it
+/// currently uses.
+fn camera_projection(cameras: &CameraProjections<'_, '_>) -> Option<(Transform, Projection)> {
+    cameras.iter().find_map(|(camera, perspective, orthographic)| {
+        let projection = match (perspective, orthographic) {
+            (Some(perspective), _) => Projection::Perspective {
+                fov: perspective.fov,
+            },
+            (None, Some(orthographic)) => Projection::Orthographic {
+                scale: orthographic.scale,
+            },
+            (None, None) => return None,
+        };
+
+        Some((Transform::from(*camera), projection))
+    })
+}
+
 /// The system in charge of showing the file dialog.
+#[allow(clippy::too_many_arguments)]
 pub fn file_dialog(
     mut query: Query<'_, '_, &mut Concrete>,
     mut name: ResMut<'_, PolyName>,
-    file_dialog_state: Res<'_, FileDialogState>,
+    mut file_error: ResMut<'_, FileErrorMessage>,
+    mut validity: ResMut<'_, ValidityMessage>,
+    mut file_dialog_state: ResMut<'_, FileDialogState>,
     file_dialog: NonSend<'_, FileDialogToken>,
+    (svg_options, png_options, projection_type): (
+        Res<'_, SvgOptions>,
+        Res<'_, PngOptions>,
+        Res<'_, ProjectionType>,
+    ),
+    (clear_color, wireframe_colors): (Res<'_, ClearColor>, Res<'_, WireframeColors>),
+    (cameras, windows): (CameraProjections<'_, '_>, Res<'_, Windows>),
 ) {
-    if file_dialog_state.is_changed() {
-        match file_dialog_state.mode {
+    if file_dialog_state.is_changed() && !matches!(file_dialog_state.mode, FileDialogMode::Disabled)
+    {
+        // The file dialog is only shown once per request.
+        let mode = std::mem::take(&mut file_dialog_state.mode);
+
+        match mode {
             // We want to save a file.
             FileDialogMode::Save => {
                 if let Some(path) = file_dialog.save_file(file_dialog_state.unwrap_name()) {
@@ -267,22 +609,111 @@ pub fn file_dialog(
                 }
             }
 
+            // We want to export a GGB file.
+            FileDialogMode::ExportGgb => {
+                let name = format!("{}.ggb", file_dialog_state.unwrap_name());
+                if let Some(path) = file_dialog.save_file(&name) {
+                    if let Some(p) = query.iter_mut().next() {
+                        if let Err(err) = p.con().to_ggb(path.with_extension("ggb"), Default::default()) {
+                            eprintln!("GeoGebra export failed: {}", err);
+                        }
+                    }
+                }
+            }
+
+            // We want to export the wireframe as seen from the camera.
+            FileDialogMode::ExportSvg => {
+                let name = format!("{}.svg", file_dialog_state.unwrap_name());
+                if let Some(path) = file_dialog.save_file(&name) {
+                    let path = path.with_extension("svg");
+
+                    if let Some(p) = query.iter_mut().next() {
+                        let res = match camera_projection(&cameras) {
+                            Some((camera, projection)) => {
+                                let opts = SvgOptions {
+                                    projection,
+                                    projection_type: *projection_type,
+                                    ..*svg_options
+                                };
+                                let svg = render_svg(p.con(), &camera, &opts);
+                                std::fs::write(&path, svg).map_err(|err| err.to_string())
+                            }
+                            None => Err("no camera was found".to_string()),
+                        };
+
+                        if let Err(err) = res {
+                            file_error.0 =
+                                Some(format!("Could not export {}: {}", path.display(), err));
+                        }
+                    }
+                }
+            }
+
+            // We want to export the view from the camera.
+            FileDialogMode::ExportPng => {
+                let name = format!("{}.png", file_dialog_state.unwrap_name());
+                if let Some(path) = file_dialog.save_file(&name) {
+                    let path = path.with_extension("png");
+
+                    if let Some(p) = query.iter_mut().next() {
+                        let res = match (camera_projection(&cameras), windows.get_primary()) {
+                            (Some((camera, projection)), Some(window)) => {
+                                let view = SvgOptions {
+                                    width: window.width() as u32,
+                                    height: window.height() as u32,
+                                    projection,
+                                    projection_type: *projection_type,
+                                    ..*svg_options
+                                };
+                                let color = clear_color.0;
+                                let opts = PngOptions {
+                                    background: [color.r(), color.g(), color.b()]
+                                        .map(|x| (x * 255.0).round() as u8),
+                                    edge_color: wireframe_colors.unselected,
+                                    ..*png_options
+                                };
+                                let png = render_png(p.con(), &camera, &view, &opts);
+                                std::fs::write(&path, png).map_err(|err| err.to_string())
+                            }
+                            _ => Err("no camera was found".to_string()),
+                        };
+
+                        if let Err(err) = res {
+                            file_error.0 =
+                                Some(format!("Could not export {}: {}", path.display(), err));
+                        }
+                    }
+                }
+            }
+
             // We want to open a file.
             FileDialogMode::Open => {
                 if let Some(path) = file_dialog.pick_file() {
                     if let Some(mut p) = query.iter_mut().next() {
-                        match Concrete::from_path(&path) {
-                            Ok(q) => {
-                                *p = q;
-                                let file_name = path.file_name().unwrap().to_str().unwrap();
-                                name.0 = file_name[..file_name.len()-4].into();
-                            }
-                            Err(err) => eprintln!("File open failed: {}", err),
-                        }
+                        open_file(
+                            path,
+                            &mut p,
+                            &mut name,
+                            (&mut file_error, &mut validity),
+                            &mut file_dialog_state,
+                        );
                     }
                 }
             }
 
+            // We want to open a file at a known path.
+            FileDialogMode::OpenPath(path) => {
+                if let Some(mut p) = query.iter_mut().next() {
+                    open_file(
+                        path,
+                        &mut p,
+                        &mut name,
+                        (&mut file_error, &mut validity),
+                        &mut file_dialog_state,
+                    );
+                }
+            }
+
             // There's nothing to do with the file dialog this frame.
             FileDialogMode::Disabled => {}
         }
@@ -313,6 +744,7 @@ pub type EguiWindows<'a> = (
 	ResMut<'a, RotateWindow>,
 	ResMut<'a, PlaneWindow>,
     ResMut<'a, WikiWindow>,
+    ResMut<'a, DepthTruncation>,
 );
 
 macro_rules! element_sort {
@@ -334,12 +766,27 @@ pub fn show_top_panel(
     // The Miratope resources controlled by the top panel.
     mut section_state: ResMut<'_, SectionState>,
     mut section_direction: ResMut<'_, Vec<SectionDirection>>,
-    mut file_dialog_state: ResMut<'_, FileDialogState>,
-    mut projection_type: ResMut<'_, ProjectionType>,
+    (mut file_dialog_state, mut validity, mut svg_options, mut png_options): (
+        ResMut<'_, FileDialogState>,
+        ResMut<'_, ValidityMessage>,
+        ResMut<'_, SvgOptions>,
+        ResMut<'_, PngOptions>,
+    ),
+    (mut projection_type, mut camera_projection, mut backface_culling): (
+        ResMut<'_, ProjectionType>,
+        ResMut<'_, CameraProjection>,
+        ResMut<'_, BackfaceCulling>,
+    ),
     mut poly_name: ResMut<'_, PolyName>,
     mut memory: ResMut<'_, Memory>,
     mut show_memory: ResMut<'_, ShowMemory>,
     mut show_help: ResMut<'_, ShowHelp>,
+    (mut show_metrics, mut show_elements, mut show_preferences, mut show_animation): (
+        ResMut<'_, ShowMetrics>,
+        ResMut<'_, ShowElements>,
+        ResMut<'_, ShowPreferences>,
+        ResMut<'_, ShowAnimation>,
+    ),
     mut export_memory: ResMut<'_, ExportMemory>,
     mut background_color: ResMut<'_, ClearColor>,
 
@@ -364,6 +811,7 @@ pub fn show_top_panel(
 		mut rotate_window,
 		mut plane_window,
         mut wiki_window,
+        mut depth_truncation,
     ): EguiWindows<'_>,
 ) {
     // The top bar.
@@ -377,11 +825,21 @@ pub fn show_top_panel(
                     file_dialog_state.open();
                 }
 
+                // Loads a file from a typed path.
+                if ui.button("Open path...").clicked() {
+                    file_dialog_state.open_path_box();
+                }
+
                 // Saves a file.
                 if ui.button("Save").clicked() {
                     file_dialog_state.save(poly_name.0.clone());
                 }
 
+                // Exports a file into GeoGebra.
+                if ui.button("Export to GeoGebra").clicked() {
+                    file_dialog_state.export_ggb(poly_name.0.clone());
+                }
+
                 if ui.button("Export all memory slots").clicked() {
                     export_memory.0 = true;
                     export_memory.1 = 0;
@@ -389,12 +847,69 @@ pub fn show_top_panel(
 
                 ui.separator();
 
+                // Reopens a recently opened file.
+                if !file_dialog_state.recent().is_empty() {
+                    let mut reopen = None;
+
+                    for path in file_dialog_state.recent() {
+                        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                        if ui
+                            .button(file_name)
+                            .on_hover_text(path.display().to_string())
+                            .clicked()
+                        {
+                            reopen = Some(path.clone());
+                        }
+                    }
+
+                    if let Some(path) = reopen {
+                        file_dialog_state.open_path(path);
+                    }
+
+                    ui.separator();
+                }
+
                 // Quits the application.
                 if ui.button("Exit").clicked() {
                     std::process::exit(0);
                 }
             });
 
+            // Exports figures of the polytope.
+            menu::menu(ui, "Export", |ui| {
+                // Exports the view from the camera.
+                if ui.button("Screenshot (PNG)").clicked() {
+                    file_dialog_state.export_png(poly_name.0.clone());
+                }
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut png_options.resolution)
+                            .speed(0.05)
+                            .clamp_range(1..=8),
+                    );
+                    ui.label("Resolution multiplier");
+                });
+
+                ui.separator();
+
+                // Exports the wireframe as seen from the camera.
+                if ui.button("Wireframe (SVG)").clicked() {
+                    file_dialog_state.export_svg(poly_name.0.clone());
+                }
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut svg_options.stroke_width)
+                            .speed(0.1)
+                            .clamp_range(0.1..=20.0),
+                    );
+                    ui.label("Stroke width");
+                });
+
+                ui.checkbox(&mut svg_options.hidden_lines, "Remove hidden lines");
+            });
+
             if export_memory.0 {
                 let idx = export_memory.1;
                 if idx == memory.len() {
@@ -421,13 +936,83 @@ pub fn show_top_panel(
 
             // Configures the view.
             menu::menu(ui, "View", |ui| {
-                let mut checked = projection_type.is_orthogonal();
+                let mut orthographic = *camera_projection == CameraProjection::Orthographic;
+                if ui.checkbox(&mut orthographic, "Orthographic camera").clicked() {
+                    *camera_projection = if orthographic {
+                        CameraProjection::Orthographic
+                    } else {
+                        CameraProjection::Perspective
+                    };
+                }
+
+                // Only applies to polyhedra whose faces can be oriented.
+                let mut culling = backface_culling.0;
+                if ui.checkbox(&mut culling, "Hide back faces").clicked() {
+                    backface_culling.0 = culling;
+                }
+
+                ui.separator();
 
-                if ui.checkbox(&mut checked, "Orthogonal projection").clicked() {
-                    projection_type.flip();
+                // How polytopes of more than three dimensions are shown.
+                let old_projection = *projection_type;
+                egui::ComboBox::from_label("Projection")
+                    .selected_text(projection_type.name())
+                    .show_ui(ui, |ui| {
+                        for projection in [
+                            ProjectionType::Orthogonal,
+                            ProjectionType::Perspective,
+                            ProjectionType::Schlegel {
+                                facet: None,
+                                distance: ProjectionType::SCHLEGEL_DISTANCE,
+                            },
+                            ProjectionType::Section,
+                        ] {
+                            let selected = projection_type.name() == projection.name();
+                            let label = ui.selectable_label(selected, projection.name());
+                            if label.clicked() && !selected {
+                                *projection_type = projection;
+                            }
+                        }
+                    });
 
-                    // Forces an update on all polytopes.
+                // Lets the user pick the facet of the Schlegel diagram.
+                if let ProjectionType::Schlegel { facet, distance } = projection_type.as_mut() {
+                    ui.add(egui::Slider::new(distance, 0.05..=5.0).text("Distance"));
+
+                    if let Some(p) = query.iter_mut().next() {
+                        let facet_count = p.facet_count();
+
+                        ui.horizontal(|ui| {
+                            if ui.button("Nearest facet").clicked() {
+                                *facet = None;
+                            }
+
+                            if facet_count > 0 && ui.button("Next facet").clicked() {
+                                *facet = Some(facet.map_or(0, |f| (f + 1) % facet_count));
+                            }
+                        });
+                    }
+                }
+
+                // Opens or closes the cross-section view when switching to or
+                // from it.
+                if old_projection != *projection_type {
                     if let Some(mut p) = query.iter_mut().next() {
+                        let was_section = old_projection == ProjectionType::Section;
+                        let is_section = *projection_type == ProjectionType::Section;
+                        let section_active =
+                            matches!(*section_state, SectionState::Active { .. });
+
+                        if is_section != section_active && (was_section || is_section) {
+                            toggle_cross_section(
+                                &mut p,
+                                &mut poly_name,
+                                &mut section_state,
+                                &mut section_direction,
+                            );
+                        }
+
+                        // Forces an update on all polytopes.
                         p.set_changed();
                     }
                 }
@@ -522,14 +1107,23 @@ pub fn show_top_panel(
 						}
                     }
                 }
+
+                // Checks whether the polytope satisfies the axioms of an
+                // abstract polytope.
+                if ui.button("Validate").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        validity.validate(&p);
+                    }
+                }
             });
 
             menu::menu(ui, "Transform", |ui| {
             
+                // Scales a polytope so that its mean edge length is 1.
                 if ui.button("Scale to unit edge length").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    let e_l = (&p.vertices[p.abs[2][0].subs[0]] - &p.vertices[p.abs[2][0].subs[1]]).norm();
-                    p.scale(1.0/e_l);
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.normalize_edge_length();
+                    }
                 }
 
                 if ui.button("Scale to unit circumradius").clicked() {
@@ -564,6 +1158,13 @@ pub fn show_top_panel(
                 if ui.button("Recenter by gravicenter").clicked() {
                     query.iter_mut().next().unwrap().recenter();
                 }
+
+                // Recenters a polytope and scales it to fit in the unit sphere.
+                if ui.button("Normalize").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.normalize();
+                    }
+                }
 				
 				ui.separator();
 				
@@ -591,6 +1192,7 @@ pub fn show_top_panel(
                         match p.try_dual_mut() {
                             Ok(_) => {
                                 poly_name.0 = format!("Dual of {}", poly_name.0);
+                                poly_name.recognize(&p);
                                 println!("Dual succeeded.")
                             },
                             Err(err) => eprintln!("Dual failed: {}", err),
@@ -638,7 +1240,7 @@ pub fn show_top_panel(
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Pyramid").clicked() {
                         *p = p.pyramid();
-                        poly_name.0 = format!("Pyramid of {}", poly_name.0);
+                        poly_name.0 = operation_name(&poly_name.0, "pyramid");
                     }
                 }
 
@@ -650,7 +1252,7 @@ pub fn show_top_panel(
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Prism").clicked() {
                         *p = p.prism();
-                        poly_name.0 = format!("Prism of {}", poly_name.0);
+                        poly_name.0 = operation_name(&poly_name.0, "prism");
                     }
                 }
 
@@ -662,7 +1264,7 @@ pub fn show_top_panel(
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Tegum").clicked() {
                         *p = p.tegum();
-                        poly_name.0 = format!("Tegum of {}", poly_name.0);
+                        poly_name.0 = operation_name(&poly_name.0, "tegum");
                     }
                 }
 
@@ -676,7 +1278,7 @@ pub fn show_top_panel(
                         match p.try_antiprism() {
                             Ok(q) => {
                                 *p = q;
-                                poly_name.0 = format!("Antiprism of {}", poly_name.0);
+                                poly_name.0 = operation_name(&poly_name.0, "antiprism");
                             },
                             Err(err) => eprintln!("Antiprism failed: {}", err),
                         }
@@ -738,6 +1340,13 @@ pub fn show_top_panel(
                 if ui.button("Truncate...").clicked() {
                     truncate_window.open();
                 }
+
+                // Opens the window to truncate by an adjustable depth.
+                if ui.button("Truncate by depth...").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        depth_truncation.open(p.clone(), poly_name.0.clone());
+                    }
+                }
 				
                 ui.separator();
 
@@ -747,47 +1356,37 @@ pub fn show_top_panel(
                         println!("Fuse succeeded!");
                     }
                 }
-            });
 
-            // Toggles cross-section mode.
-            if ui.button("Cross-section").clicked() {
-                match section_state.as_mut() {
-                    // The view is active, but will be inactivated.
-                    SectionState::Active {
-                        original_polytope,
-                        original_name,
-                        ..
-                    } => {
-                        *query.iter_mut().next().unwrap() = original_polytope.clone();
-                        poly_name.0 = original_name.clone();
-                        section_state.close();
+                // Merges coincident vertices and removes degenerate elements.
+                if ui.button("Repair").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.repair() {
+                            Ok(report) => println!("Repair succeeded: {}.", report),
+                            Err(err) => eprintln!("Repair failed: {}", err),
+                        }
                     }
+                }
 
-                    // The view is inactive, but will be activated.
-                    SectionState::Inactive => {
-                        let mut p = query.iter_mut().next().unwrap();
-
-                        if p.rank() < 4 { // Cannot slice a polygon or lower.
-                            println!("Slicing polytopes of rank less than 3 is not supported!");
-                        } else {
-                            p.flatten();
-
-                            // The default direction is in the last coordinate axis.
-                            let dim = p.dim_or();
-                            let mut direction = Vector::zeros(dim);
-                            if dim > 0 {
-                                direction[dim - 1] = 1.0;
-                            }
-    
-                            let minmax = p.minmax(direction.clone()).unwrap_or((-1.0, 1.0));
-                            let original_polytope = p.clone();
-    
-                            section_state.open(original_polytope, poly_name.0.clone(), vec![minmax]);
-                            section_direction.clear();
-                            section_direction.push(SectionDirection{0:direction});
-                        }
+                // Replaces the active polytope by the convex hull of its vertices.
+                if ui.button("Convex hull").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        *p = Concrete::convex_hull(p.vertices.clone());
+                        poly_name.0 = format!("Convex hull of {}", poly_name.0);
+                        println!("Convex hull succeeded!");
                     }
-                };
+                }
+            });
+
+            // Toggles cross-section mode.
+            if ui.button("Cross-section").clicked() {
+                if let Some(mut p) = query.iter_mut().next() {
+                    toggle_cross_section(
+                        &mut p,
+                        &mut poly_name,
+                        &mut section_state,
+                        &mut section_direction,
+                    );
+                }
             }
 
             menu::menu(ui, "Faceting", |ui| {
@@ -795,7 +1394,11 @@ pub fn show_top_panel(
                     if let Some(p) = query.iter_mut().next() {
                         let mut vertices_thing = (Vertices(vec![]), vec![]);
                         if let GroupEnum2::FromSlot(slot) = faceting_settings.group {
-                            vertices_thing = Vertices(p.vertices.clone()).copy_by_symmetry(slot.to_poly(&mut memory, &p).unwrap().clone().get_symmetry_group().unwrap().0);
+                            let mut group = slot.to_poly(&mut memory, &p).unwrap().clone().get_symmetry_group().unwrap().0;
+                            if faceting_settings.slot_rotations {
+                                group = group.rotations().cache();
+                            }
+                            vertices_thing = Vertices(p.vertices.clone()).copy_by_symmetry(group);
                         }
                         let facetings = p.clone().faceting(
                             match faceting_settings.group {
@@ -847,6 +1450,22 @@ pub fn show_top_panel(
                 wiki_window.open();
             }
 
+            if ui.button("Metrics").clicked() {
+                show_metrics.0 = !show_metrics.0;
+            }
+
+            if ui.button("Elements").clicked() {
+                show_elements.0 = !show_elements.0;
+            }
+
+            if ui.button("Animate").clicked() {
+                show_animation.0 = !show_animation.0;
+            }
+
+            if ui.button("Preferences").clicked() {
+                show_preferences.0 = !show_preferences.0;
+            }
+
             if ui.button("Help").clicked() {
                 show_help.0 = !show_help.0;
             }
@@ -919,6 +1538,8 @@ fn show_views(
         hyperplane_pos,
         flatten,
         lock,
+        sweep,
+        sweep_speed,
         ..
     } = (*section_state).clone()
     {
@@ -960,7 +1581,7 @@ fn show_views(
 				));
 
 				if ui.button("Diagonal").clicked() {
-					new_direction = Point::from_element(new_direction.len(), 1.0/(new_direction.len() as f64).sqrt());
+					new_direction = Point::from_element(new_direction.len(), 1.0/(new_direction.len() as Float).sqrt());
 				}
 			});
 			
@@ -973,9 +1594,18 @@ fn show_views(
 			i = i + 1;
 		}
 
+        // Whether the slice misses the polytope.
+        let empty = query.iter_mut().next().map_or(true, |p| p.vertices.is_empty());
+
         ui.horizontal(|ui| {
             // Makes the current cross-section into the main polytope.
-            if ui.button("Make main").clicked() {
+            if ui.add(egui::Button::new("Extract cross-section").enabled(!empty)).clicked() {
+                if let Some(mut p) = query.iter_mut().next() {
+                    if !flatten {
+                        p.flatten();
+                    }
+                }
+
                 section_state.close();
             }
 
@@ -1027,6 +1657,53 @@ fn show_views(
                 }
             }
         });
+
+        if empty {
+            ui.label("The slice doesn't intersect the polytope.");
+        }
+
+        ui.horizontal(|ui| {
+            let mut new_sweep = sweep;
+            ui.add(egui::Checkbox::new(&mut new_sweep, "Sweep"));
+
+            let mut new_sweep_speed = sweep_speed;
+            ui.add(
+                egui::DragValue::new(&mut new_sweep_speed)
+                    .speed(0.01)
+                    .clamp_range(0.0..=10.0),
+            );
+            ui.label("Sweep speed");
+
+            // Starts or stops the sweep. The range is only computed once, when
+            // the sweep starts.
+            if sweep != new_sweep {
+                if let SectionState::Active {
+                    sweep,
+                    sweep_range,
+                    sweep_forward,
+                    ..
+                } = section_state.as_mut()
+                {
+                    *sweep = new_sweep;
+                    if let Some(&(min, max)) = minmax.last() {
+                        *sweep_range = (min + 0.0000001, max - 0.0000001);
+                    }
+                    *sweep_forward = true;
+                } else {
+                    unreachable!()
+                }
+            }
+
+            // Updates the sweep speed.
+            #[allow(clippy::float_cmp)]
+            if sweep_speed != new_sweep_speed {
+                if let SectionState::Active { sweep_speed, .. } = section_state.as_mut() {
+                    *sweep_speed = new_sweep_speed;
+                } else {
+                    unreachable!()
+                }
+            }
+        });
     }
 
     if section_direction.is_changed() {
@@ -1047,6 +1724,8 @@ fn show_views(
             flatten,
             lock,
             update,
+            cache,
+            ..
         } = section_state.as_mut() {
             *update = false;
 
@@ -1062,22 +1741,47 @@ fn show_views(
 					let hyp_pos = hyperplane_pos[i];
 
 					if let Some(dim) = r.dim() {
-						let hyperplane = Hyperplane::new(section_direction[i].0.clone(), hyp_pos);
-						minmax[i] = r
-							.minmax(section_direction[i].0.clone())
-							.unwrap_or((-1.0, 1.0));
-
-						minmax[i].0 += f64::EPS;
-						let mut slice = r.cross_section(&hyperplane);
-
-						if *flatten {
-							slice.flatten_into(&hyperplane.subspace);
-							slice.recenter_with(
-								&hyperplane.flatten(&hyperplane.project(&Point::zeros(dim))),
-							);
+						// Falls back to the last axis if the direction is degenerate.
+						let mut direction = section_direction[i].0.clone();
+						if direction.len() != dim || direction.try_normalize_mut(Float::EPS).is_none() {
+							direction = Vector::zeros(dim);
+							direction[dim - 1] = 1.0;
 						}
 
-						r = slice;
+						minmax[i] = r
+							.minmax(direction.clone())
+							.unwrap_or((-1.0, 1.0));
+						minmax[i].0 += Float::EPS;
+
+						// Keeps the slice inside of the polytope when the direction
+						// changes, so that the view doesn't flicker.
+						let hyp_pos = hyp_pos.clamp(minmax[i].0, minmax[i].1 - Float::EPS);
+						hyperplane_pos[i] = hyp_pos;
+
+						r = if i == 0 {
+							// The first slice is always of the original polytope,
+							// so we only need to check the edges near it.
+							if cache.as_ref().map_or(true, |c| c.normal() != &direction) {
+								*cache = Some(SectionCache::new(&r, direction));
+							}
+							let cache = cache.as_ref().unwrap();
+
+							if *flatten {
+								cache
+									.flat_cross_section(&r, hyp_pos)
+									.unwrap_or_else(Concrete::nullitope)
+							} else {
+								cache.cross_section(&r, hyp_pos)
+							}
+						} else {
+							let hyperplane = Hyperplane::new(direction, hyp_pos);
+							if *flatten {
+								r.flat_cross_section(&hyperplane)
+									.unwrap_or_else(Concrete::nullitope)
+							} else {
+								r.cross_section(&hyperplane)
+							}
+						};
 					}
 					i += 1;
 				}
@@ -1088,3 +1792,47 @@ fn show_views(
         }
     }
 }
+
+/// Advances the last slice of the cross-section view while sweeping, bouncing
+/// back at either end of the polytope.
+fn sweep_cross_section(time: Res<'_, Time>, mut section_state: ResMut<'_, SectionState>) {
+    // We check without borrowing mutably, so that the cross-section isn't
+    // recomputed every frame.
+    if !matches!(
+        *section_state,
+        SectionState::Active {
+            sweep: true,
+            lock: false,
+            ..
+        }
+    ) {
+        return;
+    }
+
+    if let SectionState::Active {
+        hyperplane_pos,
+        sweep_speed,
+        sweep_range: (min, max),
+        sweep_forward,
+        ..
+    } = section_state.as_mut()
+    {
+        if let Some(pos) = hyperplane_pos.last_mut() {
+            let step = *sweep_speed * time.delta_seconds_f64() as Float;
+
+            if *sweep_forward {
+                *pos += step;
+                if *pos >= *max {
+                    *pos = *max;
+                    *sweep_forward = false;
+                }
+            } else {
+                *pos -= step;
+                if *pos <= *min {
+                    *pos = *min;
+                    *sweep_forward = true;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Contains the window that truncates the polytope to an adjustable depth,
+//! regenerating it as the depth is changed.
+
+use super::main_window::PolyName;
+use crate::{Concrete, Float};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::conc::ConcretePolytope;
+
+/// The plugin in charge of the depth truncation window.
+pub struct TruncationPlugin;
+
+impl Plugin for TruncationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DepthTruncation>()
+            .add_system(show_depth_truncation.system().label("show_windows"));
+    }
+}
+
+/// The state of the depth truncation window.
+pub struct DepthTruncation {
+    /// The polytope being truncated and its name, while the window is open.
+    original: Option<(Concrete, String)>,
+
+    /// The depth of the truncation, as a fraction of the edge length.
+    depth: Float,
+
+    /// The depth of the truncation currently shown on screen, if any.
+    shown: Option<Float>,
+}
+
+impl Default for DepthTruncation {
+    fn default() -> Self {
+        Self {
+            original: None,
+            depth: 1.0 / 3.0,
+            shown: None,
+        }
+    }
+}
+
+impl DepthTruncation {
+    /// Opens the window, keeping a copy of the polytope to truncate.
+    pub fn open(&mut self, original: Concrete, name: String) {
+        self.original = Some((original, name));
+        self.shown = None;
+    }
+}
+
+/// Shows the depth truncation window, and truncates the polytope whenever the
+/// depth changes.
+fn show_depth_truncation(
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut poly_name: ResMut<'_, PolyName>,
+    mut truncation: ResMut<'_, DepthTruncation>,
+) {
+    let truncation = &mut *truncation;
+    let (original, name) = match &truncation.original {
+        Some(original) => original,
+        None => return,
+    };
+
+    let depth = &mut truncation.depth;
+    let mut open = true;
+    let mut ok = false;
+    let mut cancel = false;
+
+    egui::Window::new("Depth truncation")
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            ui.add(egui::Slider::new(depth, 0.0..=0.5).text("Depth"));
+
+            ui.horizontal(|ui| {
+                if ui.button("Ok").clicked() {
+                    ok = true;
+                } else if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    #[allow(clippy::float_cmp)]
+    let changed = truncation.shown != Some(truncation.depth);
+
+    if let Some(mut poly) = query.iter_mut().next() {
+        // Restores the original polytope.
+        if cancel {
+            *poly = original.clone();
+            poly_name.0 = name.clone();
+        }
+        // Truncates the original polytope if the depth changed.
+        else if changed {
+            *poly = original.truncate(truncation.depth);
+            poly_name.0 = format!("Truncated {}", name);
+            truncation.shown = Some(truncation.depth);
+        }
+    }
+
+    // The truncated polytope stays on screen unless we cancelled.
+    if ok || cancel || !open {
+        truncation.original = None;
+    }
+}
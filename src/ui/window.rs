@@ -10,10 +10,10 @@ use super::{
     PointWidget,
     wiki::{WikiArticle, LinkType, InfoboxField}, right_panel::ElementTypesRes,
 };
-use crate::{Concrete, Float, Hypersphere, Point, ui::{main_window::PolyName, wiki::WikiElement}};
+use crate::{Concrete, Float, Hypersphere, Point, EPS, ui::{main_window::PolyName, wiki::WikiElement}};
 
 use egui::TextEdit;
-use miratope_core::{conc::{ConcretePolytope, element_types::EL_NAMES}, Polytope, abs::Ranked};
+use miratope_core::{conc::{ConcretePolytope, element_types::EL_NAMES}, greek::polygon_sides, Polytope, abs::Ranked};
 
 use bevy::prelude::*;
 use bevy_egui::{
@@ -22,7 +22,7 @@ use bevy_egui::{
 };
 
 // https://users.rust-lang.org/t/nice-floating-point-number-formatting/13213/6
-fn n_decimals(value: f64, digits: usize) -> String {
+fn n_decimals(value: Float, digits: usize) -> String {
     format!("{:.*}",
         if value.abs() < 5e-11 {0}
         else if value.abs() >= 1. {digits}
@@ -107,12 +107,32 @@ fn resize(point: &mut Point, dim: usize) {
     *point = point.clone().resize_vertically(dim, 0.0)
 }
 
+/// The name of the result of applying an operation like `prism` to a polytope
+/// with a given name. Polygons, named like `5-gon`, `5/2-gon` or `Pentagon`,
+/// give the usual names like `5-gonal prism` or `Pentagonal prism`.
+pub fn operation_name(name: &str, operation: &str) -> String {
+    if let Some(polygon) = name.strip_suffix("-gon") {
+        return format!("{}-gonal {}", polygon, operation);
+    }
+
+    match polygon_sides(name) {
+        Some(3) => format!("Triangular {}", operation),
+        Some(4) => format!("Square {}", operation),
+        Some(_) => format!("{}al {}", name.trim(), operation),
+        None => format!("{}{} of {}", operation[..1].to_uppercase(), &operation[1..], name),
+    }
+}
+
 /// The base trait for a window, containing the common code. You probably don't
 /// want to implement **only** this.
 pub trait Window: Send + Sync + Default {
     /// The name on the window, shown on the upper left.
     const NAME: &'static str;
 
+    /// Whether the result of the action might be a regular polytope, in which
+    /// case the polytope is renamed after it.
+    const RECOGNIZE: bool = false;
+
     /// Returns whether the window is open.
     fn is_open(&self) -> bool;
 
@@ -172,6 +192,13 @@ macro_rules! impl_show {
                         self_.action(polytope.as_mut());
                     }
                     self_.name_action(&mut poly_name.0);
+
+                    if Self::RECOGNIZE {
+                        if let Some(polytope) = query.iter_mut().next() {
+                            poly_name.recognize(&polytope);
+                        }
+                    }
+
                     self_.close()
                 }
                 ShowResult::Close => self_.close(),
@@ -567,6 +594,10 @@ pub struct DualWindow {
 
     /// The radius of the sphere.
     radius: Float,
+
+    /// Whether the dual is reflected through the center of the sphere, which
+    /// amounts to using a negative squared radius.
+    reflect: bool,
 }
 
 impl Default for DualWindow {
@@ -575,12 +606,14 @@ impl Default for DualWindow {
             open: false,
             center: Point::zeros(0),
             radius: 1.0,
+            reflect: false,
         }
     }
 }
 
 impl Window for DualWindow {
     const NAME: &'static str = "Dual";
+    const RECOGNIZE: bool = true;
 
     fn is_open(&self) -> bool {
         self.open
@@ -593,7 +626,15 @@ impl Window for DualWindow {
 
 impl UpdateWindow for DualWindow {
     fn action(&self, polytope: &mut Concrete) {
-        let sphere = Hypersphere::with_radius(self.center.clone(), self.radius);
+        let squared_radius = self.radius * self.radius;
+        let sphere = Hypersphere::with_squared_radius(
+            self.center.clone(),
+            if self.reflect {
+                -squared_radius
+            } else {
+                squared_radius
+            },
+        );
 
         if let Err(err) = polytope.try_dual_mut_with(&sphere) {
             eprintln!("Dual failed: {}", err);
@@ -616,6 +657,8 @@ impl UpdateWindow for DualWindow {
 
             ui.label("Radius");
         });
+
+        ui.checkbox(&mut self.reflect, "Reflect through center");
     }
 
     fn dim(&self) -> usize {
@@ -675,7 +718,7 @@ impl UpdateWindow for PyramidWindow {
     }
 
     fn name_action(&self, name: &mut String) {
-        *name = format!("Pyramid of {}", name);
+        *name = operation_name(name, "pyramid");
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -736,7 +779,7 @@ impl PlainWindow for PrismWindow {
     }
 
     fn name_action(&self, name: &mut String) {
-        *name = format!("Prism of {}", name);
+        *name = operation_name(name, "prism");
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -809,7 +852,7 @@ impl UpdateWindow for TegumWindow {
     }
 
     fn name_action(&self, name: &mut String) {
-        *name = format!("Tegum of {}", name);
+        *name = operation_name(name, "tegum");
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -899,7 +942,7 @@ impl UpdateWindow for AntiprismWindow {
     }
 
     fn name_action(&self, name: &mut String) {
-        *name = format!("Dual of {}", name);
+        *name = operation_name(name, "antiprism");
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -915,7 +958,11 @@ impl UpdateWindow for AntiprismWindow {
         });
 
         ui.horizontal(|ui| {
-            ui.add(egui::DragValue::new(&mut self.height).speed(0.01));
+            ui.add(
+                egui::DragValue::new(&mut self.height)
+                    .speed(0.01)
+                    .clamp_range(EPS..=Float::MAX),
+            );
             ui.label("Height");
         });
 
@@ -1407,7 +1454,7 @@ pub struct TruncateWindow {
     truncate_type: Vec<bool>,
 
     /// The weights applied to the coordinates. Intuitively, the truncation depths.
-    depth: Vec<f64>,
+    depth: Vec<Float>,
 }
 
 impl Window for TruncateWindow {
@@ -1474,7 +1521,7 @@ pub struct ScaleWindow {
     open: bool,
 
     /// The scale factor.
-    scale: f64,
+    scale: Float,
 }
 
 impl Window for ScaleWindow {
@@ -1536,6 +1583,10 @@ pub struct FacetingSettings {
     /// Where to get the symmetry group from.
     pub group: GroupEnum2,
 
+    /// Whether to only use the rotations in the group of the other polytope,
+    /// to compare chiral and achiral facetings.
+    pub slot_rotations: bool,
+
     /// Whether to check for all possible edge lengths and facet with each of them.
     /// If `false`, allows picking a range of edge lengths.
     pub any_single_edge_length: bool,
@@ -1545,25 +1596,25 @@ pub struct FacetingSettings {
     pub do_min_edge_length: bool,
 
     /// The minimum edge length.
-    pub min_edge_length: f64,
+    pub min_edge_length: Float,
 
     /// Whether to use a maximum edge length.
     pub do_max_edge_length: bool,
 
     /// The maximum edge length.
-    pub max_edge_length: f64,
+    pub max_edge_length: Float,
 
     /// Whether to use a minimum inradius.
     pub do_min_inradius: bool,
 
     /// The minimum inradius.
-    pub min_inradius: f64,
+    pub min_inradius: Float,
 
     /// Whether to use a maximum inradius.
     pub do_max_inradius: bool,
 
     /// The maximum inradius.
-    pub max_inradius: f64,
+    pub max_inradius: Float,
 
     /// Whether to exclude planes passing through the origin.
     pub exclude_hemis: bool,
@@ -1605,6 +1656,7 @@ impl Default for FacetingSettings {
             max_facet_types: 0,
             max_per_hyperplane: 0,
             group: GroupEnum2::Chiral(false),
+            slot_rotations: false,
             any_single_edge_length: false,
             do_min_edge_length: true,
             min_edge_length: 1.,
@@ -1733,6 +1785,10 @@ impl MemoryWindow for FacetingSettings {
             });
         });
 
+        if let GroupEnum2::FromSlot(_) = self.group {
+            ui.checkbox(&mut self.slot_rotations, "Rotation subgroup only");
+        }
+
         ui.separator();
 
         ui.radio_value(&mut self.any_single_edge_length, true, "Any single edge length");
@@ -1849,7 +1905,7 @@ pub struct RotateWindow {
     rank: usize,
 
     /// List of rotations (in radians). Rotates around xy plane, then yz plane, then zw plane, etc.
-    rots: Vec<f64>,
+    rots: Vec<Float>,
 	
 	/// Determines if radians or degrees are used.
 	degcheck: bool,
@@ -1951,7 +2007,7 @@ pub struct PlaneWindow {
     rank: usize,
 
     /// Rotation amount (radians).
-    rot: f64,
+    rot: Float,
 	
 	/// Coordinates of points.
 	p1: Point,
@@ -1997,7 +2053,7 @@ impl Window for PlaneWindow {
     }
 }
 
-fn dot(u: &Vec<f64>, v: &Vec<f64>) -> f64 {
+fn dot(u: &Vec<Float>, v: &Vec<Float>) -> Float {
 	let mut sum = 0.0;
 	for i in 0..u.len() {
 		sum += u[i]*v[i];
@@ -2015,13 +2071,13 @@ impl UpdateWindow for PlaneWindow {
 		}
 		else {			
 			//Step 0: Make plane of orthonormal basis based on input
-			//Make points p1 and p2 into unit Vec<f64> objects.
+			//Make points p1 and p2 into unit Vec<Float> objects.
 			//Also subtract po from p1 and p2
-			let ss1: f64 = self.p1.iter().map(|&x| x*x).sum();
-			let ss2: f64 = self.p2.iter().map(|&x| x*x).sum();
+			let ss1: Float = self.p1.iter().map(|&x| x*x).sum();
+			let ss2: Float = self.p2.iter().map(|&x| x*x).sum();
 			
-			let mut v1: Vec<f64> = Vec::new();
-			let mut v2: Vec<f64> = Vec::new();
+			let mut v1: Vec<Float> = Vec::new();
+			let mut v2: Vec<Float> = Vec::new();
 			
 			for i in 0..self.rank {
 				v1.push( (self.p1[i]-self.po[i])/ss1.sqrt() );
@@ -2030,17 +2086,17 @@ impl UpdateWindow for PlaneWindow {
 			
 			//Implement Gram-Schmidt process to make vectors orthonormal
 			let prod = dot(&v1,&v2)/dot(&v2,&v2);
-			let mut u2: Vec<f64> = Vec::new();
+			let mut u2: Vec<Float> = Vec::new();
 			for i in 0..self.rank {
 				u2.push(v2[i] - v1[i] * prod);
 			}
-			let ss3: f64 = u2.iter().map(|&x| x*x).sum();
+			let ss3: Float = u2.iter().map(|&x| x*x).sum();
 			
 			for i in 0..self.rank {
 				v2[i] = u2[i]/ss3.sqrt();
 			}
 			
-			let theta: f64;
+			let theta: Float;
 			if self.degcheck { //theta is the rotation amount in radians, which may or may not need conversion
 				theta = self.rot * 0.017453292519943295;
 			}